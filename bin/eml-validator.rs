@@ -1,27 +1,34 @@
 use std::{
+    borrow::Cow,
     ffi::OsStr,
     path::{Path, PathBuf},
 };
 
 use anyhow::Context;
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use eml_nl::{
+    diagnostics::{Diagnostic, render_errors},
+    diff::diff,
     documents::EML,
-    io::{EMLParsingMode, EMLRead as _, EMLWrite as _},
+    hash::DocumentHash,
+    io::{EMLParsingMode, EMLRead as _, EMLWrite as _, decode_eml_bytes},
 };
-use sha2::{Digest as _, Sha256};
 use tokio::io::AsyncReadExt;
 use tracing::{info, level_filters::LevelFilter, warn};
 use tracing_subscriber::EnvFilter;
 
 #[derive(Debug, Parser)]
-#[command(version, about, long_about = None)]
+#[command(version, about, long_about = None, args_conflicts_with_subcommands = true)]
 struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+
     /// Path to the EML file to validate
-    path: PathBuf,
+    #[arg(required = true)]
+    path: Option<PathBuf>,
 
     /// Whether to use strict parsing where no value parse errors are tolerated
-    #[arg(long, default_value_t = false)]
+    #[arg(long, global = true, default_value_t = false)]
     strict: bool,
 
     /// Print debug representation of EML document
@@ -33,6 +40,18 @@ struct Cli {
     print: bool,
 }
 
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Compare two EML files of the same type and print the differences
+    Diff {
+        /// Path to the old version of the EML file
+        old: PathBuf,
+
+        /// Path to the new version of the EML file
+        new: PathBuf,
+    },
+}
+
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     let args = Cli::try_parse().context("Failed to parse command line arguments")?;
@@ -51,17 +70,23 @@ async fn main() -> anyhow::Result<()> {
         EMLParsingMode::StrictFallback
     };
 
-    if args.path == OsStr::new("-") {
-        info!("Reading EML file as UTF-8 from stdin");
-        let mut data = String::new();
+    let path = match (args.command, args.path) {
+        (Some(Command::Diff { old, new }), _) => return diff_files(&old, &new, parsing_mode).await,
+        (None, Some(path)) => path,
+        (None, None) => unreachable!("Path is required without a subcommand"),
+    };
+
+    if path == OsStr::new("-") {
+        info!("Reading EML file from stdin");
+        let mut data = Vec::new();
         tokio::io::stdin()
-            .read_to_string(&mut data)
+            .read_to_end(&mut data)
             .await
             .context("Failed to read EML file from stdin")?;
-        handle_file(&data, parsing_mode, args.print, args.debug).await?;
-    } else if args.path.is_dir() {
+        handle_file(&data, "<stdin>", parsing_mode, args.print, args.debug).await?;
+    } else if path.is_dir() {
         info!("EML path is a directory, processing all .eml.xml files inside recursively");
-        let eml_files = collect_eml_files(&args.path).await?;
+        let eml_files = collect_eml_files(&path).await?;
         info!("Found {} EML files to process", eml_files.len());
         let mut results = vec![];
         for eml_file in eml_files {
@@ -93,16 +118,63 @@ async fn main() -> anyhow::Result<()> {
                 .count()
         );
     } else {
-        info!("Reading EML file as UTF-8 from {:?}", args.path);
-        let content = tokio::fs::read_to_string(&args.path)
+        info!("Reading EML file from {:?}", path);
+        let content = tokio::fs::read(&path)
             .await
             .context("Failed to read EML file")?;
-        handle_file(&content, parsing_mode, args.print, args.debug).await?;
+        let file_name = path.display().to_string();
+        handle_file(&content, &file_name, parsing_mode, args.print, args.debug).await?;
+    }
+
+    Ok(())
+}
+
+async fn diff_files(old: &Path, new: &Path, parsing_mode: EMLParsingMode) -> anyhow::Result<()> {
+    let old = read_and_parse(old, parsing_mode).await?;
+    let new = read_and_parse(new, parsing_mode).await?;
+
+    let changes = diff(&old, &new).context("Failed to compare EML files")?;
+    if changes.is_empty() {
+        info!("EML files are equal");
+    } else {
+        info!("Found {} change(s):", changes.len());
+        for change in &changes {
+            info!(" - {}", change);
+        }
     }
 
     Ok(())
 }
 
+async fn read_and_parse(path: &Path, parsing_mode: EMLParsingMode) -> anyhow::Result<EML> {
+    info!("Reading EML file from {:?}", path);
+    let content = tokio::fs::read(path)
+        .await
+        .context(format!("Failed to read EML file {:?}", path))?;
+    let file_name = path.display().to_string();
+    let content = decode(&content, &file_name)?;
+    let (doc, errors) = match EML::parse_eml(&content, parsing_mode).ok_with_errors() {
+        Ok(result) => result,
+        Err(e) => {
+            warn!(
+                "Failed to parse EML file:\n{}",
+                Diagnostic::new(&e, &content)
+                    .with_file_name(&file_name)
+                    .as_fatal()
+            );
+            return Err(e).context(format!("Failed to parse EML file {:?}", path));
+        }
+    };
+    if !errors.is_empty() {
+        warn!(
+            "EML file was parsed with {} warning(s):\n{}",
+            errors.len(),
+            render_errors(&errors, &content, Some(&file_name))
+        );
+    }
+    Ok(doc)
+}
+
 enum ProcessResult {
     Success,
     WithWarnings,
@@ -116,8 +188,16 @@ async fn process_file_and_log_errors(
     debug: bool,
 ) -> ProcessResult {
     let path = file.as_ref();
-    match tokio::fs::read_to_string(path).await {
-        Ok(content) => match handle_file(&content, parsing_mode, print, debug).await {
+    match tokio::fs::read(path).await {
+        Ok(content) => match handle_file(
+            &content,
+            &path.display().to_string(),
+            parsing_mode,
+            print,
+            debug,
+        )
+        .await
+        {
             Ok(warnings) => {
                 if warnings == 0 {
                     ProcessResult::Success
@@ -161,8 +241,27 @@ async fn collect_eml_files(dir: impl AsRef<Path>) -> anyhow::Result<Vec<PathBuf>
     Ok(eml_files)
 }
 
+/// Decodes the EML file to UTF-8, so that errors can be shown in the decoded text.
+fn decode<'a>(file_content: &'a [u8], file_name: &str) -> anyhow::Result<Cow<'a, str>> {
+    match decode_eml_bytes(file_content) {
+        Ok(content) => Ok(content),
+        Err(e) => {
+            // The span is in the raw bytes, which match the lossy text up to the error
+            let lossy = String::from_utf8_lossy(file_content);
+            warn!(
+                "Failed to decode EML file:\n{}",
+                Diagnostic::new(&e, &lossy)
+                    .with_file_name(file_name)
+                    .as_fatal()
+            );
+            Err(e).context("Failed to decode EML file")
+        }
+    }
+}
+
 async fn handle_file(
-    file_content: &str,
+    file_content: &[u8],
+    file_name: &str,
     parsing_mode: EMLParsingMode,
     print: bool,
     debug: bool,
@@ -173,36 +272,32 @@ async fn handle_file(
     );
 
     info!("Computing SHA-256 hash of the EML file");
-    let digest = Sha256::digest(file_content.as_bytes());
-    let hex = format!("{:x}", digest);
-
-    info!(
-        "SHA-256 hash: {}",
-        hex.as_bytes()
-            .chunks(4)
-            .map(|c| std::str::from_utf8(c).unwrap())
-            .collect::<Vec<_>>()
-            .join(" ")
-    );
+    let hash = DocumentHash::of_bytes(file_content);
+    info!("SHA-256 hash: {}", hash);
 
     info!("Parsing EML file");
-    let (doc, errors) = EML::parse_eml(file_content, parsing_mode)
-        .ok_with_errors()
-        .context("Failed to parse EML file")?;
+    let file_content = decode(file_content, file_name)?;
+    let (doc, errors) = match EML::parse_eml(&file_content, parsing_mode).ok_with_errors() {
+        Ok(result) => result,
+        Err(e) => {
+            warn!(
+                "Failed to parse EML file:\n{}",
+                Diagnostic::new(&e, &file_content)
+                    .with_file_name(file_name)
+                    .as_fatal()
+            );
+            return Err(e).context("Failed to parse EML file");
+        }
+    };
 
     if errors.is_empty() {
         info!("EML file was parsed succesfully");
     } else {
-        info!(
-            "EML file was parsed succesfully, but with {} warning(s):",
-            errors.len()
+        warn!(
+            "EML file was parsed succesfully, but with {} warning(s):\n{}",
+            errors.len(),
+            render_errors(&errors, &file_content, Some(file_name))
         );
-        for error in &errors {
-            match error.span() {
-                Some(span) => warn!(" - At position {}: {}", span, error.kind()),
-                None => warn!(" - {}", error.kind()),
-            }
-        }
     }
 
     info!(
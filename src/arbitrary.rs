@@ -0,0 +1,1342 @@
+//! [`proptest`] generators for EML_NL documents, for use in property based tests.
+//!
+//! This module is only available with the `arbitrary` feature enabled. It
+//! implements [`Arbitrary`] for the [`ElectionDefinition`], [`PollingStations`],
+//! [`Nomination`], [`CandidateLists`], [`Count`] and [`ElectionResult`]
+//! documents and all of their nested types, so that
+//! `any::<ElectionDefinition>()` can be used as a strategy.
+//!
+//! Generated documents are structurally valid and can always be written and
+//! read again in [`EMLParsingMode::Strict`](crate::io::EMLParsingMode::Strict)
+//! mode. To make sure writing and reading a document does not change it:
+//!
+//! * All values are stored as [`StringValue::Raw`], using the canonical
+//!   representation of a valid value (e.g. `12` and not `012`).
+//! * Optional text content is never empty, as an empty element is read as an
+//!   absent value.
+//! * No `CanonicalizationMethod` is generated, as it is never written.
+//!
+//! No attempt is made to generate semantically valid documents, use the
+//! `validate` methods of the documents to check those.
+
+use std::fmt::Debug;
+
+use proptest::{
+    arbitrary::{Arbitrary, any},
+    collection::vec,
+    option,
+    prelude::{BoxedStrategy, Strategy, prop_oneof},
+    sample::select,
+};
+
+use crate::{
+    common::{
+        AffiliationIdentifier, AuthorityAddress, AuthorityAddressDetails, AuthorityIdentifier,
+        CandidateIdentifier, ContestIdentifier, ContestIdentifierGeen, CreatedByAuthority,
+        CreationDateTime, ElectionDomain, ElectionTree, ElectionTreeCommittee, ElectionTreeRegion,
+        FirstName, IssueDate, LastName, ListData, ListDataContest, LocalityName, ManagingAuthority,
+        NameLineInitials, NamePrefix, PersonName, PersonNameStructure, PostalCode,
+        PostalCodeNumber, ReportingUnitIdentifier, TransactionId,
+    },
+    documents::{
+        candidate_lists::{
+            self, AddressLine, CandidateLists, CandidateListsAffiliation, CandidateListsCandidate,
+            CandidateListsCandidateList, CandidateListsContest, CandidateListsElection,
+            CandidateListsElectionIdentifier, CandidateListsListDate, CountryNameCode,
+            QualifyingAddress, QualifyingAddressCountry, QualifyingAddressLocality,
+        },
+        count::{
+            Count, CountCandidate, CountCandidateIdentifier, CountContest, CountCount,
+            CountElection, CountElectionIdentifier, CountEventIdentifier, CountInvestigation,
+            CountLevel, CountRejectedVotes, CountReportingUnitVotes, CountSelected, CountSelection,
+            CountTotalVotes, CountUncountedVotes,
+        },
+        election_definition::{
+            ElectionDefinition, ElectionDefinitionContest, ElectionDefinitionElection,
+            ElectionDefinitionElectionEvent, ElectionDefinitionElectionEventIdentifier,
+            ElectionDefinitionElectionIdentifier, ElectionDefinitionRegisteredParty,
+        },
+        election_result::{
+            ElectionResult, ElectionResultCandidate, ElectionResultContest, ElectionResultElection,
+            ElectionResultResult, ElectionResultSelected, ElectionResultSelection,
+        },
+        nomination::{Nomination, NominationNomination},
+        polling_stations::{
+            PhysicalLocation, PhysicalLocationAddress, PhysicalLocationLocality,
+            PhysicalLocationPollingStation, PollingPlace, PollingStations, PollingStationsContest,
+            PollingStationsElection, PollingStationsElectionEvent,
+            PollingStationsElectionEventIdentifier, PollingStationsElectionIdentifier,
+            PollingStationsReportingUnit,
+        },
+    },
+    io::Extensions,
+    utils::{StringValue, StringValueData},
+};
+
+/// Implements [`Arbitrary`] for a type using the given strategy.
+macro_rules! impl_arbitrary {
+    ($ty:ty, $strategy:expr) => {
+        impl Arbitrary for $ty {
+            type Parameters = ();
+            type Strategy = BoxedStrategy<Self>;
+
+            fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+                $strategy.boxed()
+            }
+        }
+    };
+}
+
+/// Strategy for raw values of type `T`, in their canonical representation.
+///
+/// Candidates are generated using the given strategy, and only those that are
+/// valid values of type `T` are used.
+fn raw<T>(candidates: impl Strategy<Value = String>) -> impl Strategy<Value = StringValue<T>>
+where
+    T: StringValueData + Debug,
+{
+    candidates.prop_filter_map("value must be valid", |s| {
+        T::parse_from_str(&s)
+            .ok()
+            .map(|v| StringValue::Raw(v.to_raw_value()))
+    })
+}
+
+/// Strategy for raw values of type `T` matching the given regular expression.
+fn raw_regex<T>(regex: &'static str) -> impl Strategy<Value = StringValue<T>>
+where
+    T: StringValueData + Debug,
+{
+    raw(regex.prop_map(|s: String| s))
+}
+
+/// Strategy for raw values of type `T`, selected from the given list.
+fn raw_select<T>(values: &'static [&'static str]) -> impl Strategy<Value = StringValue<T>>
+where
+    T: StringValueData + Debug,
+{
+    raw(select(values).prop_map(str::to_string))
+}
+
+/// Strategy for non-empty text, including characters that must be escaped.
+fn text() -> impl Strategy<Value = String> {
+    "[a-zA-Z0-9&<>'\"éëï.,-][a-zA-Z0-9 &<>'\"éëï.,-]{0,15}"
+}
+
+/// Strategy for optional non-empty text.
+fn opt_text() -> impl Strategy<Value = Option<String>> {
+    option::of(text())
+}
+
+fn xs_date<T: StringValueData + Debug>() -> impl Strategy<Value = StringValue<T>> {
+    raw_regex(r"20[0-9]{2}-(0[1-9]|1[0-2])-(0[1-9]|1[0-9]|2[0-8])(Z|[+-]0[0-2]:00)?")
+}
+
+fn xs_date_time<T: StringValueData + Debug>() -> impl Strategy<Value = StringValue<T>> {
+    raw_regex(
+        r"20[0-9]{2}-(0[1-9]|1[0-2])-(0[1-9]|1[0-9]|2[0-8])T([01][0-9]|2[0-3]):[0-5][0-9]:[0-5][0-9](Z|[+-]0[0-2]:00)?",
+    )
+}
+
+fn xs_date_or_date_time<T: StringValueData + Debug>() -> impl Strategy<Value = StringValue<T>> {
+    prop_oneof![xs_date(), xs_date_time()]
+}
+
+fn non_zero<T: StringValueData + Debug>() -> impl Strategy<Value = StringValue<T>> {
+    raw_regex("[1-9][0-9]{0,5}")
+}
+
+fn election_id<T: StringValueData + Debug>() -> impl Strategy<Value = StringValue<T>> {
+    raw_regex("(GR|TK|EP|PS|AB)20[0-9]{2}(_[a-zA-Z0-9]{1,8})?")
+}
+
+fn election_category<T: StringValueData + Debug>() -> impl Strategy<Value = StringValue<T>> {
+    raw_select(&["EK", "TK", "EP", "PS", "AB", "GR", "BC", "GC", "ER"])
+}
+
+fn election_subcategory<T: StringValueData + Debug>() -> impl Strategy<Value = StringValue<T>> {
+    raw_select(&["PS1", "PS2", "AB1", "AB2", "GR1", "GR2", "TK", "EK", "EP"])
+}
+
+fn voting_method<T: StringValueData + Debug>() -> impl Strategy<Value = StringValue<T>> {
+    raw_select(&["SPV", "STV", "FPP", "other"])
+}
+
+fn region_category<T: StringValueData + Debug>() -> impl Strategy<Value = StringValue<T>> {
+    raw_select(&[
+        "GEMEENTE",
+        "KIESKRING",
+        "PROVINCIE",
+        "WATERSCHAP",
+        "DEELGEMEENTE",
+    ])
+}
+
+fn xsb<T: StringValueData + Debug>() -> impl Strategy<Value = StringValue<T>> {
+    raw_regex(r"CSB|HSB[0-9]{1,2}|SB[0-9]{1,3}|[0-9]{4}")
+}
+
+fn votes<T: StringValueData + Debug>() -> impl Strategy<Value = StringValue<T>> {
+    raw_regex("0|[1-9][0-9]{0,5}")
+}
+
+fn gender<T: StringValueData + Debug>() -> impl Strategy<Value = StringValue<T>> {
+    raw_select(&["male", "female", "unknown"])
+}
+
+impl_arbitrary!(
+    TransactionId,
+    raw_regex(r"[0-9]{1,6}").prop_map(TransactionId)
+);
+impl_arbitrary!(IssueDate, xs_date_or_date_time().prop_map(IssueDate));
+impl_arbitrary!(CreationDateTime, xs_date_time().prop_map(CreationDateTime));
+
+impl_arbitrary!(
+    ManagingAuthority,
+    (
+        any::<AuthorityIdentifier>(),
+        any::<AuthorityAddress>(),
+        option::of(any::<CreatedByAuthority>()),
+    )
+        .prop_map(
+            |(authority_identifier, authority_address, created_by_authority)| ManagingAuthority {
+                authority_identifier,
+                authority_address,
+                created_by_authority,
+                extensions: Extensions::default(),
+            }
+        )
+);
+
+impl_arbitrary!(
+    AuthorityIdentifier,
+    (xsb(), opt_text()).prop_map(|(id, name)| AuthorityIdentifier { id, name })
+);
+
+impl_arbitrary!(
+    CreatedByAuthority,
+    (xsb(), opt_text()).prop_map(|(id, name)| CreatedByAuthority { id, name })
+);
+
+impl_arbitrary!(
+    AuthorityAddress,
+    option::of(prop_oneof![
+        vec(any::<AddressLine>(), 0..3).prop_map(AuthorityAddressDetails::AddressLines),
+        any::<QualifyingAddressCountry>().prop_map(AuthorityAddressDetails::Country),
+        any::<QualifyingAddressLocality>().prop_map(AuthorityAddressDetails::Locality),
+    ])
+    .prop_map(|details| AuthorityAddress {
+        details,
+        extensions: Extensions::default(),
+    })
+);
+
+impl_arbitrary!(
+    ElectionDomain,
+    (raw_regex(r"[0-9]{4}|[1-9]|1[0-9]|2[0-9]"), text())
+        .prop_map(|(id, name)| ElectionDomain { id, name })
+);
+
+impl_arbitrary!(
+    ContestIdentifier,
+    raw_regex(r"[1-9][0-9]{0,2}|geen|alle|I|IV|XII").prop_map(|id| ContestIdentifier { id })
+);
+
+impl_arbitrary!(
+    ContestIdentifierGeen,
+    raw_select(&["geen"]).prop_map(|id| ContestIdentifierGeen { id })
+);
+
+impl_arbitrary!(
+    ElectionTree,
+    vec(any::<ElectionTreeRegion>(), 0..4).prop_map(|regions| ElectionTree {
+        regions,
+        extensions: Extensions::default(),
+    })
+);
+
+impl_arbitrary!(
+    ElectionTreeRegion,
+    (
+        raw_regex::<String>(r"[0-9]{1,4}"),
+        region_category(),
+        opt_text(),
+        option::of(raw_select(&["true", "false"])),
+        option::of((raw_regex::<String>(r"[0-9]{1,4}"), region_category())),
+        text(),
+        vec(any::<ElectionTreeCommittee>(), 0..3),
+    )
+        .prop_map(
+            |(
+                region_number,
+                region_category,
+                roman_numerals,
+                frysian_export_allowed,
+                superior,
+                region_name,
+                committees,
+            )| {
+                let (superior_region_number, superior_region_category) = superior.unzip();
+                ElectionTreeRegion {
+                    region_number: region_number.raw().into_owned(),
+                    region_category,
+                    roman_numerals,
+                    frysian_export_allowed,
+                    superior_region_number: superior_region_number.map(|n| n.raw().into_owned()),
+                    superior_region_category,
+                    region_name,
+                    committees,
+                    extensions: Extensions::default(),
+                }
+            }
+        )
+);
+
+impl_arbitrary!(
+    ElectionTreeCommittee,
+    (raw_select(&["CSB", "HSB", "GSB"]), opt_text())
+        .prop_map(|(category, name)| ElectionTreeCommittee { category, name })
+);
+
+impl_arbitrary!(
+    ElectionDefinition,
+    (
+        any::<TransactionId>(),
+        option::of(any::<ManagingAuthority>()),
+        option::of(any::<IssueDate>()),
+        any::<CreationDateTime>(),
+        any::<ElectionDefinitionElectionEvent>(),
+    )
+        .prop_map(
+            |(
+                transaction_id,
+                managing_authority,
+                issue_date,
+                creation_date_time,
+                election_event,
+            )| {
+                ElectionDefinition {
+                    transaction_id,
+                    managing_authority,
+                    issue_date,
+                    creation_date_time,
+                    canonicalization_method: None,
+                    election_event,
+                    signature: None,
+                    extensions: Extensions::default(),
+                }
+            }
+        )
+);
+
+impl_arbitrary!(
+    ElectionDefinitionElectionEvent,
+    any::<ElectionDefinitionElection>().prop_map(|election| ElectionDefinitionElectionEvent {
+        id: ElectionDefinitionElectionEventIdentifier,
+        election,
+        extensions: Extensions::default(),
+    })
+);
+
+impl_arbitrary!(
+    ElectionDefinitionElection,
+    (
+        any::<ElectionDefinitionElectionIdentifier>(),
+        any::<ElectionDefinitionContest>(),
+        raw_regex(r"[0-9]{1,3}"),
+        raw_regex(r"[0-9]{1,3}"),
+        any::<ElectionTree>(),
+        vec(any::<ElectionDefinitionRegisteredParty>(), 0..5),
+    )
+        .prop_map(
+            |(
+                identifier,
+                contest,
+                number_of_seats,
+                preference_threshold,
+                election_tree,
+                registered_parties,
+            )| {
+                ElectionDefinitionElection {
+                    identifier,
+                    contest,
+                    number_of_seats,
+                    preference_threshold,
+                    election_tree,
+                    registered_parties,
+                    extensions: Extensions::default(),
+                }
+            }
+        )
+);
+
+impl_arbitrary!(
+    ElectionDefinitionElectionIdentifier,
+    (
+        election_id(),
+        text(),
+        election_category(),
+        election_subcategory(),
+        option::of(any::<ElectionDomain>()),
+        xs_date(),
+        xs_date(),
+    )
+        .prop_map(
+            |(id, name, category, subcategory, domain, election_date, nomination_date)| {
+                ElectionDefinitionElectionIdentifier {
+                    id,
+                    name,
+                    category,
+                    subcategory,
+                    domain,
+                    election_date,
+                    nomination_date,
+                    extensions: Extensions::default(),
+                }
+            }
+        )
+);
+
+impl_arbitrary!(
+    ElectionDefinitionContest,
+    (any::<ContestIdentifier>(), voting_method(), non_zero()).prop_map(
+        |(identifier, voting_method, max_votes)| ElectionDefinitionContest {
+            identifier,
+            voting_method,
+            max_votes,
+            extensions: Extensions::default(),
+        }
+    )
+);
+
+impl_arbitrary!(
+    ElectionDefinitionRegisteredParty,
+    text().prop_map(|registered_appellation| ElectionDefinitionRegisteredParty {
+        registered_appellation,
+        extensions: Extensions::default(),
+    })
+);
+
+impl_arbitrary!(
+    PollingStations,
+    (
+        any::<TransactionId>(),
+        any::<ManagingAuthority>(),
+        option::of(any::<IssueDate>()),
+        any::<CreationDateTime>(),
+        any::<PollingStationsElectionEvent>(),
+    )
+        .prop_map(
+            |(
+                transaction_id,
+                managing_authority,
+                issue_date,
+                creation_date_time,
+                election_event,
+            )| {
+                PollingStations {
+                    transaction_id,
+                    managing_authority,
+                    issue_date,
+                    creation_date_time,
+                    canonicalization_method: None,
+                    election_event,
+                    signature: None,
+                    extensions: Extensions::default(),
+                }
+            }
+        )
+);
+
+impl_arbitrary!(
+    PollingStationsElectionEvent,
+    any::<PollingStationsElection>().prop_map(|election| PollingStationsElectionEvent {
+        id: PollingStationsElectionEventIdentifier,
+        election,
+        extensions: Extensions::default(),
+    })
+);
+
+impl_arbitrary!(
+    PollingStationsElection,
+    (
+        any::<PollingStationsElectionIdentifier>(),
+        any::<PollingStationsContest>(),
+    )
+        .prop_map(|(identifier, contest)| PollingStationsElection {
+            identifier,
+            contest,
+            extensions: Extensions::default(),
+        })
+);
+
+impl_arbitrary!(
+    PollingStationsElectionIdentifier,
+    (
+        election_id(),
+        opt_text(),
+        election_category(),
+        option::of(election_subcategory()),
+        option::of(any::<ElectionDomain>()),
+        xs_date(),
+    )
+        .prop_map(|(id, name, category, subcategory, domain, election_date)| {
+            PollingStationsElectionIdentifier {
+                id,
+                name,
+                category,
+                subcategory,
+                domain,
+                election_date,
+                extensions: Extensions::default(),
+            }
+        })
+);
+
+impl_arbitrary!(
+    PollingStationsContest,
+    (
+        any::<ContestIdentifierGeen>(),
+        any::<PollingStationsReportingUnit>(),
+        voting_method(),
+        non_zero(),
+        vec(any::<PollingPlace>(), 0..5),
+    )
+        .prop_map(
+            |(identifier, reporting_unit, voting_method, max_votes, polling_places)| {
+                PollingStationsContest {
+                    identifier,
+                    reporting_unit,
+                    voting_method,
+                    max_votes,
+                    polling_places,
+                    extensions: Extensions::default(),
+                }
+            }
+        )
+);
+
+impl_arbitrary!(
+    PollingStationsReportingUnit,
+    any::<ReportingUnitIdentifier>().prop_map(|identifier| PollingStationsReportingUnit {
+        identifier,
+        extensions: Extensions::default(),
+    })
+);
+
+impl_arbitrary!(
+    ReportingUnitIdentifier,
+    (
+        raw_regex(r"[0-9]{4}|HSB[0-9]{1,2}|SB[0-9]{1,3}|HSB[0-9]::[0-9]{4}"),
+        text()
+    )
+        .prop_map(|(id, name)| ReportingUnitIdentifier { id, name })
+);
+
+impl_arbitrary!(
+    PollingPlace,
+    (
+        raw_select(&["polling", "postal"]),
+        any::<PhysicalLocation>()
+    )
+        .prop_map(|(channel, physical_location)| PollingPlace {
+            channel,
+            physical_location,
+            extensions: Extensions::default(),
+        })
+);
+
+impl_arbitrary!(
+    PhysicalLocation,
+    (
+        any::<PhysicalLocationLocality>(),
+        any::<PhysicalLocationPollingStation>(),
+    )
+        .prop_map(|(locality, polling_station)| PhysicalLocation {
+            address: PhysicalLocationAddress {
+                locality,
+                extensions: Extensions::default(),
+            },
+            polling_station,
+            extensions: Extensions::default(),
+        })
+);
+
+impl_arbitrary!(
+    PhysicalLocationLocality,
+    (any::<LocalityName>(), option::of(any::<PostalCode>())).prop_map(
+        |(locality_name, postal_code)| PhysicalLocationLocality {
+            locality_name,
+            postal_code,
+            extensions: Extensions::default(),
+        }
+    )
+);
+
+impl_arbitrary!(
+    PhysicalLocationPollingStation,
+    (raw_regex(r"[0-9]{1,4}"), text())
+        .prop_map(|(id, data)| PhysicalLocationPollingStation { id, data })
+);
+
+impl_arbitrary!(
+    LocalityName,
+    (text(), opt_text(), opt_text()).prop_map(|(name, locality_type, code)| LocalityName {
+        name,
+        locality_type,
+        code,
+    })
+);
+
+impl_arbitrary!(
+    PostalCode,
+    (opt_text(), opt_text(), opt_text()).prop_map(|(number_type, code, number)| PostalCode {
+        number: PostalCodeNumber {
+            number_type,
+            code,
+            number,
+        },
+        extensions: Extensions::default(),
+    })
+);
+
+impl_arbitrary!(
+    CandidateLists,
+    (
+        any::<TransactionId>(),
+        any::<ManagingAuthority>(),
+        any::<IssueDate>(),
+        any::<CreationDateTime>(),
+        any::<CandidateListsCandidateList>(),
+    )
+        .prop_map(
+            |(
+                transaction_id,
+                managing_authority,
+                issue_date,
+                creation_date_time,
+                candidate_list,
+            )| {
+                CandidateLists {
+                    transaction_id,
+                    managing_authority,
+                    issue_date,
+                    creation_date_time,
+                    canonicalization_method: None,
+                    candidate_list,
+                    signature: None,
+                    extensions: Extensions::default(),
+                }
+            }
+        )
+);
+
+impl_arbitrary!(
+    CandidateListsCandidateList,
+    (
+        option::of(xs_date_or_date_time().prop_map(CandidateListsListDate)),
+        any::<CandidateListsElection>(),
+    )
+        .prop_map(|(list_date, election)| CandidateListsCandidateList {
+            list_date,
+            election,
+            extensions: Extensions::default(),
+        })
+);
+
+impl_arbitrary!(
+    CandidateListsElection,
+    (
+        any::<CandidateListsElectionIdentifier>(),
+        any::<CandidateListsContest>(),
+    )
+        .prop_map(|(identifier, contest)| CandidateListsElection {
+            identifier,
+            contest,
+            extensions: Extensions::default(),
+        })
+);
+
+impl_arbitrary!(
+    CandidateListsElectionIdentifier,
+    (
+        election_id(),
+        opt_text(),
+        election_category(),
+        option::of(election_subcategory()),
+        option::of(any::<ElectionDomain>()),
+        xs_date(),
+        xs_date(),
+    )
+        .prop_map(
+            |(id, name, category, subcategory, domain, election_date, nomination_date)| {
+                CandidateListsElectionIdentifier {
+                    id,
+                    name,
+                    category,
+                    subcategory,
+                    domain,
+                    election_date,
+                    nomination_date,
+                    extensions: Extensions::default(),
+                }
+            }
+        )
+);
+
+impl_arbitrary!(
+    CandidateListsContest,
+    (
+        any::<ContestIdentifier>(),
+        vec(any::<CandidateListsAffiliation>(), 0..3),
+    )
+        .prop_map(|(identifier, affiliations)| CandidateListsContest {
+            identifier,
+            affiliations,
+            extensions: Extensions::default(),
+        })
+);
+
+impl_arbitrary!(
+    CandidateListsAffiliation,
+    (
+        any::<AffiliationIdentifier>(),
+        raw_select(&[
+            "lijstengroep",
+            "stel gelijkluidende lijsten",
+            "op zichzelf staande lijst",
+        ]),
+        any::<ListData>(),
+        vec(any::<CandidateListsCandidate>(), 0..4),
+    )
+        .prop_map(|(identifier, affiliation_type, list_data, candidates)| {
+            CandidateListsAffiliation {
+                identifier,
+                affiliation_type,
+                list_data,
+                candidates,
+                extensions: Extensions::default(),
+            }
+        })
+);
+
+impl_arbitrary!(
+    AffiliationIdentifier,
+    (option::of(non_zero()), opt_text()).prop_map(|(id, registered_name)| AffiliationIdentifier {
+        id,
+        registered_name,
+        extensions: Extensions::default(),
+    })
+);
+
+impl_arbitrary!(
+    ListData,
+    (
+        raw_select(&["true", "false"]),
+        option::of(raw_select(&["nl", "fy"])),
+        option::of(non_zero()),
+        option::of(raw_regex(r"[A-Z]")),
+        vec(any::<ListDataContest>(), 0..3),
+    )
+        .prop_map(
+            |(
+                publish_gender,
+                publication_language,
+                belongs_to_set,
+                belongs_to_combination,
+                contests,
+            )| {
+                ListData {
+                    publish_gender,
+                    publication_language,
+                    belongs_to_set,
+                    belongs_to_combination,
+                    contests,
+                    extensions: Extensions::default(),
+                }
+            }
+        )
+);
+
+impl_arbitrary!(
+    ListDataContest,
+    (raw_regex(r"[1-9][0-9]{0,2}|I|IV|XII"), opt_text())
+        .prop_map(|(id, name)| ListDataContest { id, name })
+);
+
+impl_arbitrary!(
+    CandidateListsCandidate,
+    (
+        any::<CandidateIdentifier>(),
+        any::<PersonNameStructure>(),
+        option::of(xs_date()),
+        option::of(gender()),
+        any::<QualifyingAddress>(),
+    )
+        .prop_map(
+            |(identifier, full_name, date_of_birth, gender, qualifying_address)| {
+                CandidateListsCandidate {
+                    identifier,
+                    full_name,
+                    date_of_birth,
+                    gender,
+                    qualifying_address,
+                    extensions: Extensions::default(),
+                }
+            }
+        )
+);
+
+impl_arbitrary!(
+    CandidateIdentifier,
+    (
+        non_zero(),
+        option::of(non_zero()),
+        option::of(non_zero()),
+        opt_text(),
+    )
+        .prop_map(
+            |(id, display_order, short_code, expected_confirmation_reference)| {
+                CandidateIdentifier {
+                    id,
+                    display_order,
+                    short_code,
+                    expected_confirmation_reference,
+                    extensions: Extensions::default(),
+                }
+            }
+        )
+);
+
+impl_arbitrary!(
+    PersonNameStructure,
+    (any::<PersonName>(), opt_text(), opt_text()).prop_map(|(person_name, party_type, code)| {
+        PersonNameStructure {
+            person_name,
+            party_type,
+            code,
+            extensions: Extensions::default(),
+        }
+    })
+);
+
+impl_arbitrary!(
+    PersonName,
+    (
+        option::of((text(), opt_text(), opt_text())),
+        option::of((text(), opt_text(), opt_text(), opt_text())),
+        option::of((text(), opt_text(), opt_text(), opt_text())),
+        (text(), opt_text(), opt_text(), opt_text()),
+        (opt_text(), opt_text(), opt_text()),
+    )
+        .prop_map(
+            |(initials, first_name, name_prefix, last_name, (person_name_type, code, key_ref))| {
+                PersonName {
+                    name_line_initials: initials.map(|(value, name_line_type, code)| {
+                        NameLineInitials {
+                            value,
+                            name_line_type,
+                            code,
+                        }
+                    }),
+                    first_name: first_name.map(|(value, first_name_type, name_type, code)| {
+                        FirstName {
+                            value,
+                            first_name_type,
+                            name_type,
+                            code,
+                        }
+                    }),
+                    name_prefix: name_prefix.map(|(value, name_prefix_type, name_type, code)| {
+                        NamePrefix {
+                            value,
+                            name_prefix_type,
+                            name_type,
+                            code,
+                        }
+                    }),
+                    last_name: LastName {
+                        value: last_name.0,
+                        last_name_type: last_name.1,
+                        name_type: last_name.2,
+                        code: last_name.3,
+                    },
+                    person_name_type,
+                    code,
+                    name_details_key_ref: key_ref,
+                    extensions: Extensions::default(),
+                }
+            }
+        )
+);
+
+impl_arbitrary!(
+    QualifyingAddress,
+    prop_oneof![
+        any::<QualifyingAddressLocality>().prop_map(QualifyingAddress::Locality),
+        any::<QualifyingAddressCountry>().prop_map(QualifyingAddress::Country),
+    ]
+);
+
+impl_arbitrary!(
+    QualifyingAddressLocality,
+    (
+        option::of(any::<AddressLine>()),
+        (text(), opt_text(), opt_text()),
+        option::of((text(), opt_text(), opt_text())),
+        (opt_text(), opt_text(), opt_text()),
+    )
+        .prop_map(
+            |(address_line, locality_name, postal_code, (locality_type, usage_type, indicator))| {
+                QualifyingAddressLocality {
+                    address_line,
+                    locality_name: candidate_lists::LocalityName {
+                        value: locality_name.0,
+                        locality_name_type: locality_name.1,
+                        code: locality_name.2,
+                    },
+                    postal_code: postal_code.map(|(value, postal_code_number_type, code)| {
+                        candidate_lists::PostalCode {
+                            postal_code_number: candidate_lists::PostalCodeNumber {
+                                value,
+                                postal_code_number_type,
+                                code,
+                            },
+                            extensions: Extensions::default(),
+                        }
+                    }),
+                    locality_type,
+                    usage_type,
+                    indicator,
+                    extensions: Extensions::default(),
+                }
+            }
+        )
+);
+
+impl_arbitrary!(
+    QualifyingAddressCountry,
+    (
+        option::of((text(), opt_text(), opt_text())),
+        any::<QualifyingAddressLocality>(),
+    )
+        .prop_map(|(country_name_code, locality)| QualifyingAddressCountry {
+            country_name_code: country_name_code.map(|(value, scheme, code)| CountryNameCode {
+                value,
+                scheme,
+                code,
+            }),
+            locality,
+            extensions: Extensions::default(),
+        })
+);
+
+impl_arbitrary!(
+    AddressLine,
+    (text(), opt_text(), opt_text()).prop_map(|(value, address_line_type, code)| AddressLine {
+        value,
+        address_line_type,
+        code,
+    })
+);
+
+impl_arbitrary!(
+    Nomination,
+    (
+        any::<TransactionId>(),
+        option::of(any::<ManagingAuthority>()),
+        option::of(any::<IssueDate>()),
+        any::<CreationDateTime>(),
+        any::<NominationNomination>(),
+    )
+        .prop_map(
+            |(transaction_id, managing_authority, issue_date, creation_date_time, nomination)| {
+                Nomination {
+                    transaction_id,
+                    managing_authority,
+                    issue_date,
+                    creation_date_time,
+                    canonicalization_method: None,
+                    nomination,
+                    signature: None,
+                    extensions: Extensions::default(),
+                }
+            }
+        )
+);
+
+impl_arbitrary!(
+    NominationNomination,
+    (
+        any::<CandidateListsElectionIdentifier>(),
+        option::of(any::<ContestIdentifier>()),
+        any::<CandidateListsAffiliation>(),
+    )
+        .prop_map(|(election_identifier, contest_identifier, affiliation)| {
+            NominationNomination {
+                election_identifier,
+                contest_identifier,
+                affiliation,
+                extensions: Extensions::default(),
+            }
+        })
+);
+
+impl_arbitrary!(
+    CountLevel,
+    select(&[
+        CountLevel::PollingStation,
+        CountLevel::Municipality,
+        CountLevel::PrincipalCommittee,
+        CountLevel::CentralCommittee,
+    ])
+);
+
+impl_arbitrary!(
+    Count,
+    (
+        any::<CountLevel>(),
+        any::<TransactionId>(),
+        any::<ManagingAuthority>(),
+        option::of(any::<IssueDate>()),
+        any::<CreationDateTime>(),
+        any::<CountCount>(),
+    )
+        .prop_map(
+            |(level, transaction_id, managing_authority, issue_date, creation_date_time, count)| {
+                Count {
+                    level,
+                    transaction_id,
+                    managing_authority,
+                    issue_date,
+                    creation_date_time,
+                    canonicalization_method: None,
+                    count,
+                    signature: None,
+                    extensions: Extensions::default(),
+                }
+            }
+        )
+);
+
+impl_arbitrary!(
+    CountCount,
+    any::<CountElection>().prop_map(|election| CountCount {
+        event_identifier: CountEventIdentifier,
+        election,
+        extensions: Extensions::default(),
+    })
+);
+
+impl_arbitrary!(
+    CountElection,
+    (
+        any::<CountElectionIdentifier>(),
+        vec(any::<CountContest>(), 0..3),
+    )
+        .prop_map(|(identifier, contests)| CountElection {
+            identifier,
+            contests,
+            extensions: Extensions::default(),
+        })
+);
+
+impl_arbitrary!(
+    CountElectionIdentifier,
+    (
+        election_id(),
+        opt_text(),
+        election_category(),
+        option::of(election_subcategory()),
+        option::of(any::<ElectionDomain>()),
+        xs_date(),
+    )
+        .prop_map(|(id, name, category, subcategory, domain, election_date)| {
+            CountElectionIdentifier {
+                id,
+                name,
+                category,
+                subcategory,
+                domain,
+                election_date,
+                extensions: Extensions::default(),
+            }
+        })
+);
+
+impl_arbitrary!(
+    CountContest,
+    (
+        any::<ContestIdentifier>(),
+        any::<CountTotalVotes>(),
+        vec(any::<CountReportingUnitVotes>(), 0..3),
+    )
+        .prop_map(
+            |(identifier, total_votes, reporting_unit_votes)| CountContest {
+                identifier,
+                total_votes,
+                reporting_unit_votes,
+                extensions: Extensions::default(),
+            }
+        )
+);
+
+impl_arbitrary!(
+    CountTotalVotes,
+    (
+        vec(any::<CountSelection>(), 0..4),
+        votes(),
+        votes(),
+        vec(any::<CountRejectedVotes>(), 0..3),
+        vec(any::<CountUncountedVotes>(), 0..3),
+    )
+        .prop_map(
+            |(selections, cast, total_counted, rejected_votes, uncounted_votes)| {
+                CountTotalVotes {
+                    selections,
+                    cast,
+                    total_counted,
+                    rejected_votes,
+                    uncounted_votes,
+                    extensions: Extensions::default(),
+                }
+            }
+        )
+);
+
+impl_arbitrary!(
+    CountReportingUnitVotes,
+    (
+        any::<ReportingUnitIdentifier>(),
+        vec(any::<CountInvestigation>(), 0..3),
+        vec(any::<CountSelection>(), 0..4),
+        votes(),
+        votes(),
+        vec(any::<CountRejectedVotes>(), 0..3),
+        vec(any::<CountUncountedVotes>(), 0..3),
+    )
+        .prop_map(
+            |(
+                identifier,
+                investigations,
+                selections,
+                cast,
+                total_counted,
+                rejected_votes,
+                uncounted_votes,
+            )| {
+                CountReportingUnitVotes {
+                    identifier,
+                    investigations,
+                    selections,
+                    cast,
+                    total_counted,
+                    rejected_votes,
+                    uncounted_votes,
+                    extensions: Extensions::default(),
+                }
+            }
+        )
+);
+
+impl_arbitrary!(
+    CountInvestigation,
+    (text(), raw_select(&["true", "false"]))
+        .prop_map(|(reason_code, value)| CountInvestigation { reason_code, value })
+);
+
+impl_arbitrary!(
+    CountSelection,
+    (
+        prop_oneof![
+            any::<AffiliationIdentifier>().prop_map(CountSelected::Affiliation),
+            non_zero().prop_map(|id| CountSelected::Candidate(CountCandidate {
+                identifier: CountCandidateIdentifier { id },
+                extensions: Extensions::default(),
+            })),
+        ],
+        votes(),
+    )
+        .prop_map(|(selected, valid_votes)| CountSelection {
+            selected,
+            valid_votes,
+            extensions: Extensions::default(),
+        })
+);
+
+impl_arbitrary!(
+    CountRejectedVotes,
+    (raw_select(&["ongeldig", "blanco"]), votes())
+        .prop_map(|(reason_code, value)| CountRejectedVotes { reason_code, value })
+);
+
+impl_arbitrary!(
+    CountUncountedVotes,
+    (text(), votes()).prop_map(|(reason_code, value)| CountUncountedVotes { reason_code, value })
+);
+
+impl_arbitrary!(
+    ElectionResult,
+    (
+        any::<TransactionId>(),
+        any::<ManagingAuthority>(),
+        option::of(any::<IssueDate>()),
+        any::<CreationDateTime>(),
+        any::<ElectionResultElection>(),
+    )
+        .prop_map(
+            |(transaction_id, managing_authority, issue_date, creation_date_time, election)| {
+                ElectionResult {
+                    transaction_id,
+                    managing_authority,
+                    issue_date,
+                    creation_date_time,
+                    canonicalization_method: None,
+                    result: ElectionResultResult {
+                        election,
+                        extensions: Extensions::default(),
+                    },
+                    signature: None,
+                    extensions: Extensions::default(),
+                }
+            }
+        )
+);
+
+impl_arbitrary!(
+    ElectionResultElection,
+    (
+        any::<CountElectionIdentifier>(),
+        vec(any::<ElectionResultContest>(), 0..3),
+    )
+        .prop_map(|(identifier, contests)| ElectionResultElection {
+            identifier,
+            contests,
+            extensions: Extensions::default(),
+        })
+);
+
+impl_arbitrary!(
+    ElectionResultContest,
+    (
+        any::<ContestIdentifier>(),
+        vec(any::<ElectionResultSelection>(), 0..4),
+    )
+        .prop_map(|(identifier, selections)| ElectionResultContest {
+            identifier,
+            selections,
+            extensions: Extensions::default(),
+        })
+);
+
+impl_arbitrary!(
+    ElectionResultSelection,
+    (
+        prop_oneof![
+            any::<AffiliationIdentifier>().prop_map(ElectionResultSelected::Affiliation),
+            any::<ElectionResultCandidate>()
+                .prop_map(|candidate| ElectionResultSelected::Candidate(Box::new(candidate))),
+        ],
+        option::of(non_zero()),
+        raw_select(&["yes", "no"]),
+    )
+        .prop_map(|(selected, ranking, elected)| ElectionResultSelection {
+            selected,
+            ranking,
+            elected,
+            extensions: Extensions::default(),
+        })
+);
+
+impl_arbitrary!(
+    ElectionResultCandidate,
+    (
+        any::<CandidateIdentifier>(),
+        any::<PersonNameStructure>(),
+        option::of(xs_date()),
+        option::of(gender()),
+        option::of(any::<QualifyingAddress>()),
+    )
+        .prop_map(
+            |(identifier, full_name, date_of_birth, gender, qualifying_address)| {
+                ElectionResultCandidate {
+                    identifier,
+                    full_name,
+                    date_of_birth,
+                    gender,
+                    qualifying_address,
+                    extensions: Extensions::default(),
+                }
+            }
+        )
+);
+
+#[cfg(test)]
+mod tests {
+    use proptest::{prelude::ProptestConfig, prop_assert, prop_assert_eq, proptest};
+
+    use crate::io::{EMLParsingMode, EMLRead, EMLWrite};
+
+    use super::*;
+
+    /// Check that writing, reading and writing the document again produces
+    /// the same XML, and that reading it results in the same document.
+    fn check_round_trip<T>(doc: &T) -> Result<(), proptest::test_runner::TestCaseError>
+    where
+        T: EMLRead + EMLWrite + PartialEq + Debug,
+    {
+        let written = doc
+            .write_eml_root_str(true, true)
+            .expect("Failed to write EML document");
+
+        let (strict, errors) = T::parse_eml(&written, EMLParsingMode::Strict)
+            .ok_with_errors()
+            .map_err(|e| proptest::test_runner::TestCaseError::fail(format!("{e}\n{written}")))?;
+        prop_assert!(errors.is_empty(), "Unexpected errors: {errors:?}");
+        let rewritten = strict
+            .write_eml_root_str(true, true)
+            .expect("Failed to write EML document");
+        prop_assert_eq!(&written, &rewritten);
+        prop_assert_eq!(&strict, doc);
+
+        let loose = T::parse_eml(&written, EMLParsingMode::Loose)
+            .ok()
+            .expect("Failed to parse EML document");
+        prop_assert_eq!(&loose, doc);
+        Ok(())
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(64))]
+
+        #[test]
+        fn test_election_definition_round_trip(doc in any::<ElectionDefinition>()) {
+            check_round_trip(&doc)?;
+        }
+
+        #[test]
+        fn test_polling_stations_round_trip(doc in any::<PollingStations>()) {
+            check_round_trip(&doc)?;
+        }
+
+        #[test]
+        fn test_nomination_round_trip(doc in any::<Nomination>()) {
+            check_round_trip(&doc)?;
+        }
+
+        #[test]
+        fn test_candidate_lists_round_trip(doc in any::<CandidateLists>()) {
+            check_round_trip(&doc)?;
+        }
+
+        #[test]
+        fn test_count_round_trip(doc in any::<Count>()) {
+            check_round_trip(&doc)?;
+        }
+
+        #[test]
+        fn test_election_result_round_trip(doc in any::<ElectionResult>()) {
+            check_round_trip(&doc)?;
+        }
+    }
+}
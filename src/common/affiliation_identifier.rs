@@ -1,16 +1,19 @@
 use crate::{
     NS_EML,
-    io::{EMLElement, collect_struct},
+    io::{EMLElement, Extensions, collect_struct},
     utils::{AffiliationIdType, StringValue},
 };
 
 /// An affiliation identifier consisting of an id and a registered name.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AffiliationIdentifier {
     /// The affiliation id.
     pub id: Option<StringValue<AffiliationIdType>>,
     /// The registered name of the affiliation.
     pub registered_name: Option<String>,
+    /// Unknown attributes and child elements, see [`Extensions`].
+    pub extensions: Extensions,
 }
 
 impl AffiliationIdentifier {
@@ -19,6 +22,7 @@ impl AffiliationIdentifier {
         Self {
             id: id.map(StringValue::Parsed),
             registered_name: registered_name.map(|name| name.into()),
+            extensions: Extensions::default(),
         }
     }
 }
@@ -33,12 +37,14 @@ impl EMLElement for AffiliationIdentifier {
             AffiliationIdentifier {
                 id: elem.string_value_attr_opt("Id")?,
                 registered_name: ("RegisteredName", NS_EML) => |elem| elem.text_without_children_opt()?,
+                extensions as Extensions,
             }
         ))
     }
 
     fn write_eml(&self, writer: crate::io::EMLElementWriter) -> Result<(), crate::EMLError> {
         writer
+            .extensions(&self.extensions)?
             .attr_opt("Id", self.id.as_ref().map(|id| id.raw()))?
             .child(("RegisteredName", NS_EML), |w| {
                 if let Some(name) = &self.registered_name {
@@ -2,12 +2,13 @@ use std::num::NonZeroU64;
 
 use crate::{
     NS_EML,
-    io::{EMLElement, collect_struct},
+    io::{EMLElement, Extensions, collect_struct},
     utils::{CandidateIdType, NameShortCodeType, StringValue},
 };
 
 /// Candidate identifier, but not for 510 document types.\
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CandidateIdentifier {
     /// The candidate id.
     pub id: StringValue<CandidateIdType>,
@@ -17,6 +18,8 @@ pub struct CandidateIdentifier {
     pub short_code: Option<StringValue<NameShortCodeType>>,
     /// The expected confirmation reference of the candidate.
     pub expected_confirmation_reference: Option<String>,
+    /// Unknown attributes and child elements, see [`Extensions`].
+    pub extensions: Extensions,
 }
 
 impl CandidateIdentifier {
@@ -27,6 +30,7 @@ impl CandidateIdentifier {
             display_order: None,
             short_code: None,
             expected_confirmation_reference: None,
+            extensions: Extensions::default(),
         }
     }
 }
@@ -45,12 +49,14 @@ impl EMLElement for CandidateIdentifier {
 
         struct CandidateIdentifierTmp {
             short_code: Option<StringValue<NameShortCodeType>>,
+            extensions: Extensions,
         }
 
         let elem = collect_struct!(
             elem,
             CandidateIdentifierTmp {
                 short_code as Option: ("ShortCode", NS_EML) => |elem| elem.string_value()?,
+                extensions as Extensions,
             }
         );
 
@@ -59,11 +65,13 @@ impl EMLElement for CandidateIdentifier {
             display_order,
             short_code: short_code.or(elem.short_code), // attribute takes precedence
             expected_confirmation_reference,
+            extensions: elem.extensions,
         })
     }
 
     fn write_eml(&self, writer: crate::io::EMLElementWriter) -> Result<(), crate::EMLError> {
         writer
+            .extensions(&self.extensions)?
             .attr("Id", &self.id.raw())?
             .attr_opt("DisplayOrder", self.display_order.as_ref().map(|v| v.raw()))?
             .attr_opt("ShortCode", self.short_code.as_ref().map(|v| v.raw()))?
@@ -1,14 +1,34 @@
 use crate::{
     NS_DS,
-    io::{EMLElement, collect_struct},
+    io::{CanonicalizationAlgorithm, EMLElement, collect_struct},
 };
 
 /// XML CanonicalizationMethod element
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CanonicalizationMethod {
     algorithm: String,
 }
 
+impl CanonicalizationMethod {
+    /// Create a CanonicalizationMethod element for the given algorithm.
+    pub fn new(algorithm: CanonicalizationAlgorithm) -> Self {
+        CanonicalizationMethod {
+            algorithm: algorithm.uri().to_string(),
+        }
+    }
+
+    /// Returns the algorithm, if it is a supported canonicalization algorithm.
+    pub fn algorithm(&self) -> Option<CanonicalizationAlgorithm> {
+        CanonicalizationAlgorithm::from_uri(&self.algorithm)
+    }
+
+    /// Returns the URI of the algorithm as it appears in the document.
+    pub fn algorithm_uri(&self) -> &str {
+        &self.algorithm
+    }
+}
+
 impl EMLElement for CanonicalizationMethod {
     const EML_NAME: crate::io::QualifiedName<'_, '_> =
         crate::io::QualifiedName::from_static("CanonicalizationMethod", Some(NS_DS));
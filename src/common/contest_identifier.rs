@@ -5,7 +5,8 @@ use crate::{
 };
 
 /// Identifier for the contest.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ContestIdentifier {
     /// Id of the contest.
     pub id: StringValue<ContestIdType>,
@@ -26,7 +27,8 @@ impl EMLElement for ContestIdentifier {
 }
 
 /// Identifier for the contest with 'geen' type.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ContestIdentifierGeen {
     /// Id of the contest.
     pub id: StringValue<ContestIdTypeGeen>,
@@ -7,7 +7,8 @@ use crate::{
 };
 
 /// Document creation date time.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CreationDateTime(pub StringValue<XsDateTime>);
 
 impl CreationDateTime {
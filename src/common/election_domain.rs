@@ -9,7 +9,8 @@ use crate::{
 /// ElectionDomain is part of the election name, e.g. election of the council of
 /// a municipality or province. Not needed e.g. for Tweede Kamer or European
 /// Parliament.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ElectionDomain {
     /// Identifier of the election domain
     pub id: StringValue<ElectionDomainIdType>,
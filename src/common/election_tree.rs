@@ -1,26 +1,221 @@
 use crate::{
-    NS_KR,
-    io::{EMLElement, QualifiedName},
+    EMLError, NS_KR,
+    io::{
+        EMLElement, EMLElementReader, EMLElementWriter, Extensions, QualifiedName, collect_struct,
+    },
+    utils::{CommitteeCategory, RegionCategory, StringValue},
 };
 
 /// Election tree as defined in EML_NL.
-#[derive(Debug, Clone)]
-pub struct ElectionTree {}
+///
+/// The election tree is stored as a flat list of regions, in document order.
+/// Each region refers to its parent region through its superior region number
+/// and category. Use [`ElectionTree::parent`], [`ElectionTree::children`] and
+/// [`ElectionTree::path_to_root`] to traverse the tree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ElectionTree {
+    /// The regions in this election tree.
+    pub regions: Vec<ElectionTreeRegion>,
+    /// Unknown attributes and child elements, see [`Extensions`].
+    pub extensions: Extensions,
+}
+
+impl ElectionTree {
+    /// Find the region with the given number.
+    ///
+    /// Region numbers are only unique within a region category, if multiple
+    /// regions share the number the first one is returned. Use
+    /// [`ElectionTree::find_region_in_category`] to disambiguate.
+    pub fn find_region(&self, number: &str) -> Option<&ElectionTreeRegion> {
+        self.regions.iter().find(|r| r.region_number == number)
+    }
+
+    /// Find the region with the given category and number.
+    pub fn find_region_in_category(
+        &self,
+        category: RegionCategory,
+        number: &str,
+    ) -> Option<&ElectionTreeRegion> {
+        self.regions
+            .iter()
+            .find(|r| r.region_number == number && r.is_category(category))
+    }
+
+    /// Get the regions that do not have a superior region.
+    pub fn roots(&self) -> impl Iterator<Item = &ElectionTreeRegion> {
+        self.regions
+            .iter()
+            .filter(|r| r.superior_region_number.is_none())
+    }
+
+    /// Get the parent of the given region, if it has one and it is present
+    /// in this tree.
+    pub fn parent(&self, region: &ElectionTreeRegion) -> Option<&ElectionTreeRegion> {
+        let number = region.superior_region_number.as_deref()?;
+        self.regions.iter().find(|r| {
+            r.region_number == number
+                && region
+                    .superior_region_category
+                    .as_ref()
+                    .is_none_or(|c| c.raw() == r.region_category.raw())
+        })
+    }
+
+    /// Get the direct children of the given region.
+    pub fn children<'a>(
+        &'a self,
+        region: &'a ElectionTreeRegion,
+    ) -> impl Iterator<Item = &'a ElectionTreeRegion> {
+        self.regions.iter().filter(move |r| {
+            r.superior_region_number.as_deref() == Some(region.region_number.as_str())
+                && r.superior_region_category
+                    .as_ref()
+                    .is_none_or(|c| c.raw() == region.region_category.raw())
+        })
+    }
+
+    /// Get the path from the given region up to the root of the tree.
+    ///
+    /// The first element is the given region itself, the last element is the
+    /// topmost region that could be found. Cycles in the superior region
+    /// references are not followed.
+    pub fn path_to_root<'a>(
+        &'a self,
+        region: &'a ElectionTreeRegion,
+    ) -> Vec<&'a ElectionTreeRegion> {
+        let mut path = vec![region];
+        let mut current = region;
+        while let Some(parent) = self.parent(current) {
+            if path.iter().any(|r| std::ptr::eq(*r, parent)) {
+                break;
+            }
+            path.push(parent);
+            current = parent;
+        }
+        path
+    }
+}
 
 impl EMLElement for ElectionTree {
     const EML_NAME: QualifiedName<'_, '_> = QualifiedName::from_static("ElectionTree", Some(NS_KR));
 
-    fn read_eml(elem: &mut crate::io::EMLElementReader<'_, '_>) -> Result<Self, crate::EMLError>
-    where
-        Self: Sized,
-    {
-        // TODO: complete election tree parsing
+    fn read_eml(elem: &mut EMLElementReader<'_, '_>) -> Result<Self, EMLError> {
+        Ok(collect_struct!(elem, ElectionTree {
+            regions as Vec: ElectionTreeRegion::EML_NAME => |elem| ElectionTreeRegion::read_eml(elem)?,
+            extensions as Extensions,
+        }))
+    }
+
+    fn write_eml(&self, writer: EMLElementWriter) -> Result<(), EMLError> {
+        let mut content = writer.extensions(&self.extensions)?.content()?;
+        for region in &self.regions {
+            content = content.child_elem(ElectionTreeRegion::EML_NAME, region)?;
+        }
+        content.finish()
+    }
+}
+
+/// A region in the election tree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ElectionTreeRegion {
+    /// Number of the region, unique within its category.
+    pub region_number: String,
+    /// Category of the region.
+    pub region_category: StringValue<RegionCategory>,
+    /// Roman numerals of the region, used for electoral districts.
+    pub roman_numerals: Option<String>,
+    /// Whether exporting Frisian names is allowed for this region.
+    pub frysian_export_allowed: Option<StringValue<bool>>,
+    /// Number of the superior (parent) region, if any.
+    pub superior_region_number: Option<String>,
+    /// Category of the superior (parent) region, if any.
+    pub superior_region_category: Option<StringValue<RegionCategory>>,
+    /// Name of the region.
+    pub region_name: String,
+    /// Committees responsible for this region.
+    pub committees: Vec<ElectionTreeCommittee>,
+    /// Unknown attributes and child elements, see [`Extensions`].
+    pub extensions: Extensions,
+}
+
+impl ElectionTreeRegion {
+    /// Returns true if this region is of the given category.
+    pub fn is_category(&self, category: RegionCategory) -> bool {
+        self.region_category.raw() == category.to_str_value()
+    }
+}
+
+impl EMLElement for ElectionTreeRegion {
+    const EML_NAME: QualifiedName<'_, '_> = QualifiedName::from_static("Region", Some(NS_KR));
+
+    fn read_eml(elem: &mut EMLElementReader<'_, '_>) -> Result<Self, EMLError> {
+        Ok(collect_struct!(elem, ElectionTreeRegion {
+            region_number: elem.attribute_value_req("RegionNumber")?.into_owned(),
+            region_category: elem.string_value_attr("RegionCategory", None)?,
+            roman_numerals: elem.attribute_value("RomanNumerals")?.map(|v| v.into_owned()),
+            frysian_export_allowed: elem.string_value_attr_opt("FrysianExportAllowed")?,
+            superior_region_number: elem.attribute_value("SuperiorRegionNumber")?.map(|v| v.into_owned()),
+            superior_region_category: elem.string_value_attr_opt("SuperiorRegionCategory")?,
+            region_name: ("RegionName", NS_KR) => |elem| elem.text_without_children()?,
+            committees as Vec: ElectionTreeCommittee::EML_NAME => |elem| ElectionTreeCommittee::read_eml(elem)?,
+            extensions as Extensions,
+        }))
+    }
+
+    fn write_eml(&self, writer: EMLElementWriter) -> Result<(), EMLError> {
+        let mut content = writer
+            .extensions(&self.extensions)?
+            .attr("RegionNumber", &self.region_number)?
+            .attr("RegionCategory", &self.region_category.raw())?
+            .attr_opt("RomanNumerals", self.roman_numerals.as_ref())?
+            .attr_opt(
+                "FrysianExportAllowed",
+                self.frysian_export_allowed.as_ref().map(|v| v.raw()),
+            )?
+            .attr_opt("SuperiorRegionNumber", self.superior_region_number.as_ref())?
+            .attr_opt(
+                "SuperiorRegionCategory",
+                self.superior_region_category.as_ref().map(|v| v.raw()),
+            )?
+            .child(("RegionName", NS_KR), |elem| {
+                elem.text(self.region_name.as_ref())?.finish()
+            })?;
+        for committee in &self.committees {
+            content = content.child_elem(ElectionTreeCommittee::EML_NAME, committee)?;
+        }
+        content.finish()
+    }
+}
+
+/// A committee responsible for a region in the election tree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ElectionTreeCommittee {
+    /// Category of the committee.
+    pub category: StringValue<CommitteeCategory>,
+    /// Name of the committee, if present.
+    pub name: Option<String>,
+}
+
+impl EMLElement for ElectionTreeCommittee {
+    const EML_NAME: QualifiedName<'_, '_> = QualifiedName::from_static("Committee", Some(NS_KR));
+
+    fn read_eml(elem: &mut EMLElementReader<'_, '_>) -> Result<Self, EMLError> {
+        let category = elem.string_value_attr("CommitteeCategory", None)?;
+        let name = elem
+            .attribute_value("CommitteeName")?
+            .map(|v| v.into_owned());
         elem.skip()?;
-        Ok(ElectionTree {})
+
+        Ok(ElectionTreeCommittee { category, name })
     }
 
-    fn write_eml(&self, writer: crate::io::EMLElementWriter) -> Result<(), crate::EMLError> {
-        // TODO: complete election tree writing
-        writer.empty()
+    fn write_eml(&self, writer: EMLElementWriter) -> Result<(), EMLError> {
+        writer
+            .attr("CommitteeCategory", &self.category.raw())?
+            .attr_opt("CommitteeName", self.name.as_ref())?
+            .empty()
     }
 }
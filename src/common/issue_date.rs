@@ -10,7 +10,8 @@ use crate::{
 /// Document issue date.
 ///
 /// Can be either a date or a date with time.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct IssueDate(pub StringValue<XsDateOrDateTime>);
 
 impl IssueDate {
@@ -4,12 +4,13 @@ use thiserror::Error;
 
 use crate::{
     NS_KR,
-    io::{EMLElement, QualifiedName, collect_struct},
+    io::{EMLElement, Extensions, QualifiedName, collect_struct},
     utils::{ContestIdType, PublicationLanguageType, StringValue, StringValueData},
 };
 
 /// Additional data for affiliation lists.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ListData {
     /// Whether to publish the genders for this list.
     pub publish_gender: StringValue<bool>,
@@ -27,6 +28,9 @@ pub struct ListData {
 
     /// An optional list of contests this list is associated with.
     pub contests: Vec<ListDataContest>,
+
+    /// Unknown attributes and child elements, see [`Extensions`].
+    pub extensions: Extensions,
 }
 
 impl ListData {
@@ -38,6 +42,7 @@ impl ListData {
             belongs_to_set: None,
             belongs_to_combination: None,
             contests: Vec::new(),
+            extensions: Extensions::default(),
         }
     }
 
@@ -67,6 +72,7 @@ impl EMLElement for ListData {
         // temporary struct to collect optional contests element
         struct ListDataContests {
             contests: Option<Vec<ListDataContest>>,
+            extensions: Extensions,
         }
 
         let tmp = collect_struct!(elem, ListDataContests {
@@ -82,6 +88,7 @@ impl EMLElement for ListData {
 
                 res.contests
             },
+            extensions as Extensions,
         });
 
         Ok(ListData {
@@ -90,11 +97,13 @@ impl EMLElement for ListData {
             belongs_to_set,
             belongs_to_combination,
             contests: tmp.contests.unwrap_or_default(),
+            extensions: tmp.extensions,
         })
     }
 
     fn write_eml(&self, writer: crate::io::EMLElementWriter) -> Result<(), crate::EMLError> {
         let writer = writer
+            .extensions(&self.extensions)?
             .attr("PublishGender", &self.publish_gender.raw())?
             .attr_opt(
                 "PublicationLanguage",
@@ -129,7 +138,8 @@ impl EMLElement for ListData {
 }
 
 /// Data for a contest associated with a list.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ListDataContest {
     /// The contest ID.
     pub id: StringValue<ContestIdType>,
@@ -145,13 +155,9 @@ impl EMLElement for ListDataContest {
     where
         Self: Sized,
     {
-        Ok(collect_struct!(
-            elem,
-            ListDataContest {
-                id: elem.string_value_attr("Id", None)?,
-                name: elem.text_without_children_opt()?,
-            }
-        ))
+        let name = elem.text_without_children_opt()?;
+        let id = elem.string_value_attr("Id", None)?;
+        Ok(ListDataContest { id, name })
     }
 
     fn write_eml(&self, writer: crate::io::EMLElementWriter) -> Result<(), crate::EMLError> {
@@ -166,7 +172,7 @@ impl EMLElement for ListDataContest {
 }
 
 /// Type representing the combination a list belongs to.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ListDataBelongsToCombinationType(String);
 
 /// Error returned when an invalid list data belongs to combination type string is encountered.
@@ -197,3 +203,28 @@ impl StringValueData for ListDataBelongsToCombinationType {
         self.0.clone()
     }
 }
+
+crate::utils::impl_serde_raw!(ListDataBelongsToCombinationType);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::{EMLParsingMode, EMLRead as _};
+
+    #[test]
+    fn test_list_data_contest_name() {
+        let xml = r#"<kr:Contest xmlns:kr="http://www.kiesraad.nl/extensions" Id="1">Kieskring 1</kr:Contest>"#;
+        let contest = ListDataContest::parse_eml(xml, EMLParsingMode::Strict)
+            .ok()
+            .expect("Failed to parse contest");
+        assert_eq!(contest.id.raw(), "1");
+        assert_eq!(contest.name.as_deref(), Some("Kieskring 1"));
+
+        let xml = r#"<kr:Contest xmlns:kr="http://www.kiesraad.nl/extensions" Id="2"/>"#;
+        let contest = ListDataContest::parse_eml(xml, EMLParsingMode::Strict)
+            .ok()
+            .expect("Failed to parse contest");
+        assert_eq!(contest.id.raw(), "2");
+        assert_eq!(contest.name, None);
+    }
+}
@@ -4,7 +4,8 @@ use crate::{
 };
 
 /// Name of a locality
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LocalityName {
     /// Name of the locality
     pub name: String,
@@ -1,11 +1,18 @@
 use crate::{
-    EMLError, NS_EML, NS_KR,
-    io::{EMLElement, EMLElementReader, EMLElementWriter, QualifiedName, collect_struct},
+    EMLError, NS_EML, NS_KR, NS_XAL,
+    documents::candidate_lists::{
+        AddressLine, QualifyingAddressCountry, QualifyingAddressLocality,
+    },
+    error::EMLErrorKind,
+    io::{
+        EMLElement, EMLElementReader, EMLElementWriter, Extensions, QualifiedName, collect_struct,
+    },
     utils::{StringValue, XSBType},
 };
 
 /// Managing authority of an election.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ManagingAuthority {
     /// Identifier of the managing authority
     pub authority_identifier: AuthorityIdentifier,
@@ -13,6 +20,8 @@ pub struct ManagingAuthority {
     pub authority_address: AuthorityAddress,
     /// Instance which created a data set on behalf of another (only if different!)
     pub created_by_authority: Option<CreatedByAuthority>,
+    /// Unknown attributes and child elements, see [`Extensions`].
+    pub extensions: Extensions,
 }
 
 impl EMLElement for ManagingAuthority {
@@ -24,11 +33,13 @@ impl EMLElement for ManagingAuthority {
             authority_identifier: AuthorityIdentifier::EML_NAME => |elem| AuthorityIdentifier::read_eml(elem)?,
             authority_address: AuthorityAddress::EML_NAME => |elem| AuthorityAddress::read_eml(elem)?,
             created_by_authority as Option: CreatedByAuthority::EML_NAME => |elem| CreatedByAuthority::read_eml(elem)?,
+            extensions as Extensions,
         }))
     }
 
     fn write_eml(&self, writer: EMLElementWriter) -> Result<(), EMLError> {
         writer
+            .extensions(&self.extensions)?
             .child_elem(AuthorityIdentifier::EML_NAME, &self.authority_identifier)?
             .child_elem(AuthorityAddress::EML_NAME, &self.authority_address)?
             .child_elem_option(
@@ -42,7 +53,8 @@ impl EMLElement for ManagingAuthority {
 }
 
 /// Identifier of a managing authority.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AuthorityIdentifier {
     /// Identifier of the managing authority
     pub id: StringValue<XSBType>,
@@ -76,24 +88,137 @@ impl EMLElement for AuthorityIdentifier {
 }
 
 /// Address of a managing authority.
-#[derive(Debug, Clone)]
-pub struct AuthorityAddress {}
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AuthorityAddress {
+    /// The xAL address details, if present.
+    pub details: Option<AuthorityAddressDetails>,
+    /// Unknown attributes and child elements, see [`Extensions`].
+    pub extensions: Extensions,
+}
 
 impl EMLElement for AuthorityAddress {
     const EML_NAME: QualifiedName<'_, '_> =
         QualifiedName::from_static("AuthorityAddress", Some(NS_EML));
 
     fn read_eml(elem: &mut EMLElementReader<'_, '_>) -> Result<Self, EMLError> {
-        Ok(collect_struct!(elem, AuthorityAddress {}))
+        let parent_name = elem.name()?.as_owned();
+        let mut details = None;
+        let mut extensions = Extensions::default();
+        while let Some(mut next_child) = elem.next_child()? {
+            if next_child.preserve_comments() {
+                let annotations = next_child
+                    .take_annotations(usize::from(details.is_some()), extensions.elements.len());
+                extensions.annotations.extend(annotations);
+            }
+            let name = next_child.name()?;
+            let value = if details.is_some() {
+                None
+            } else if name == EML_NAME_ADDRESS_LINES {
+                Some(AuthorityAddressDetails::AddressLines(read_address_lines(
+                    &mut next_child,
+                )?))
+            } else if name == QualifyingAddressCountry::EML_NAME {
+                Some(AuthorityAddressDetails::Country(
+                    QualifyingAddressCountry::read_eml(&mut next_child)?,
+                ))
+            } else if name == QualifyingAddressLocality::EML_NAME {
+                Some(AuthorityAddressDetails::Locality(
+                    QualifyingAddressLocality::read_eml(&mut next_child)?,
+                ))
+            } else {
+                None
+            };
+
+            if value.is_some() {
+                details = value;
+            } else {
+                let err = EMLErrorKind::UnexpectedElement(
+                    next_child.name()?.as_owned(),
+                    parent_name.clone(),
+                )
+                .add_span(next_child.span());
+                next_child.push_err(err);
+                if next_child.preserve_unknown() {
+                    let position = usize::from(details.is_some());
+                    extensions
+                        .elements
+                        .push(next_child.read_unknown_element(position)?);
+                } else {
+                    next_child.skip()?;
+                }
+            }
+        }
+        if elem.preserve_comments() {
+            let annotations =
+                elem.take_annotations(usize::from(details.is_some()), extensions.elements.len());
+            extensions.annotations.extend(annotations);
+        }
+        if elem.preserve_unknown() {
+            extensions.attributes = elem.unknown_attributes()?;
+        }
+        Ok(AuthorityAddress {
+            details,
+            extensions,
+        })
     }
 
     fn write_eml(&self, writer: EMLElementWriter) -> Result<(), EMLError> {
-        writer.empty()
+        let writer = writer.extensions(&self.extensions)?;
+        match &self.details {
+            None => writer.empty(),
+            Some(AuthorityAddressDetails::AddressLines(lines)) => writer
+                .child(EML_NAME_ADDRESS_LINES, |elem| {
+                    let mut content = elem.content()?;
+                    for line in lines {
+                        content = content.child_elem(AddressLine::EML_NAME, line)?;
+                    }
+                    content.finish()
+                })?
+                .finish(),
+            Some(AuthorityAddressDetails::Country(country)) => writer
+                .child_elem(QualifyingAddressCountry::EML_NAME, country)?
+                .finish(),
+            Some(AuthorityAddressDetails::Locality(locality)) => writer
+                .child_elem(QualifyingAddressLocality::EML_NAME, locality)?
+                .finish(),
+        }
     }
 }
 
+/// Name for the xAL address lines element
+const EML_NAME_ADDRESS_LINES: QualifiedName<'_, '_> =
+    QualifiedName::from_static("AddressLines", Some(NS_XAL));
+
+/// Read the address lines contained in an `xal:AddressLines` element.
+fn read_address_lines(elem: &mut EMLElementReader<'_, '_>) -> Result<Vec<AddressLine>, EMLError> {
+    // Temporary struct to collect the address lines
+    struct AddressLines {
+        lines: Vec<AddressLine>,
+    }
+
+    let res = collect_struct!(elem, AddressLines {
+        lines as Vec: AddressLine::EML_NAME => |elem| AddressLine::read_eml(elem)?,
+    });
+
+    Ok(res.lines)
+}
+
+/// The xAL address details of a managing authority.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AuthorityAddressDetails {
+    /// Free-form address lines.
+    AddressLines(Vec<AddressLine>),
+    /// A locality in a specific country.
+    Country(QualifyingAddressCountry),
+    /// A locality only.
+    Locality(QualifyingAddressLocality),
+}
+
 /// Address of a managing authority.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CreatedByAuthority {
     /// Identifier of the managing authority
     pub id: StringValue<XSBType>,
@@ -126,3 +251,81 @@ impl EMLElement for CreatedByAuthority {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        EMLErrorKind,
+        documents::polling_stations::PollingStations,
+        io::{EMLParsingMode, EMLRead as _, EMLReadResult, EMLWrite as _},
+    };
+
+    #[test]
+    fn test_authority_address_round_trip() {
+        let doc = include_str!("../../test-emls/polling_stations/eml110b_test.eml.xml");
+        let doc = doc.replace(
+            "<AuthorityAddress/>",
+            r#"<AuthorityAddress>
+                <xal:Locality>
+                    <xal:AddressLine>Stadhuisplein 1</xal:AddressLine>
+                    <xal:LocalityName>Juinen</xal:LocalityName>
+                    <xal:PostalCode>
+                        <xal:PostalCodeNumber>1234 AB</xal:PostalCodeNumber>
+                    </xal:PostalCode>
+                </xal:Locality>
+            </AuthorityAddress>"#,
+        );
+        let polling_stations = PollingStations::parse_eml(&doc, EMLParsingMode::Strict)
+            .ok()
+            .expect("Failed to parse EML document");
+
+        let written = polling_stations
+            .write_eml_root_str(true, true)
+            .expect("Failed to write EML document");
+        let reparsed = PollingStations::parse_eml(&written, EMLParsingMode::Strict)
+            .ok()
+            .expect("Failed to parse written EML document");
+        let address = &reparsed.managing_authority.authority_address;
+        let Some(AuthorityAddressDetails::Locality(locality)) = &address.details else {
+            panic!("Expected a locality address");
+        };
+        assert_eq!(
+            locality.address_line.as_ref().map(|l| l.value.as_str()),
+            Some("Stadhuisplein 1")
+        );
+        assert_eq!(locality.locality_name.value, "Juinen");
+        assert_eq!(
+            locality
+                .postal_code
+                .as_ref()
+                .map(|p| p.postal_code_number.value.as_str()),
+            Some("1234 AB")
+        );
+    }
+
+    #[test]
+    fn test_authority_address_unknown_child() {
+        let doc = include_str!("../../test-emls/polling_stations/eml110b_test.eml.xml");
+        let doc = doc.replace(
+            "<AuthorityAddress/>",
+            "<AuthorityAddress><Unknown/></AuthorityAddress>",
+        );
+        let EMLReadResult::Ok(polling_stations, errors) =
+            PollingStations::parse_eml(&doc, EMLParsingMode::Strict)
+        else {
+            panic!("Expected the unknown element to be a non-fatal error");
+        };
+        assert!(
+            polling_stations
+                .managing_authority
+                .authority_address
+                .details
+                .is_none()
+        );
+        assert!(errors.iter().any(|e| matches!(
+            e.kind(),
+            EMLErrorKind::UnexpectedElement(name, _) if name.local_name == "Unknown"
+        )));
+    }
+}
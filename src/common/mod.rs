@@ -14,6 +14,7 @@ mod managing_authority;
 mod person_name;
 mod postal_code;
 mod reporting_unit_identifier;
+mod signature;
 mod transaction_id;
 
 pub use affiliation_identifier::*;
@@ -30,4 +31,5 @@ pub use managing_authority::*;
 pub use person_name::*;
 pub use postal_code::*;
 pub use reporting_unit_identifier::*;
+pub use signature::*;
 pub use transaction_id::*;
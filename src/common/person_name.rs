@@ -2,11 +2,12 @@ use thiserror::Error;
 
 use crate::{
     EMLError, NS_XNL,
-    io::{EMLElement, EMLReadElement, EMLWriteElement, QualifiedName, collect_struct},
+    io::{EMLElement, EMLReadElement, EMLWriteElement, Extensions, QualifiedName, collect_struct},
 };
 
 /// Container for details of the name of a person.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PersonNameStructure {
     /// The person's name details.
     pub person_name: PersonName,
@@ -14,6 +15,8 @@ pub struct PersonNameStructure {
     pub party_type: Option<String>,
     /// The Code attribute of the PersonNameStructure
     pub code: Option<String>,
+    /// Unknown attributes and child elements, see [`Extensions`].
+    pub extensions: Extensions,
 }
 
 impl EMLReadElement for PersonNameStructure {
@@ -26,6 +29,7 @@ impl EMLReadElement for PersonNameStructure {
                 person_name: PersonName::EML_NAME => |elem| PersonName::read_eml(elem)?,
                 party_type: elem.attribute_value("PartyType")?.map(|s| s.into_owned()),
                 code: elem.attribute_value("Code")?.map(|s| s.into_owned()),
+                extensions as Extensions,
             }
         ))
     }
@@ -34,6 +38,7 @@ impl EMLReadElement for PersonNameStructure {
 impl EMLWriteElement for PersonNameStructure {
     fn write_eml_element(&self, writer: crate::io::EMLElementWriter) -> Result<(), EMLError> {
         writer
+            .extensions(&self.extensions)?
             .attr_opt("PartyType", self.party_type.as_ref())?
             .attr_opt("Code", self.code.as_ref())?
             .child_elem(PersonName::EML_NAME, &self.person_name)?
@@ -42,7 +47,8 @@ impl EMLWriteElement for PersonNameStructure {
 }
 
 /// Details of the name of a person.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PersonName {
     /// The initials of the person.
     pub name_line_initials: Option<NameLineInitials>,
@@ -58,6 +64,8 @@ pub struct PersonName {
     pub code: Option<String>,
     /// The NameDetailsKeyRef attribute of the PersonName
     pub name_details_key_ref: Option<String>,
+    /// Unknown attributes and child elements, see [`Extensions`].
+    pub extensions: Extensions,
 }
 
 impl PersonName {
@@ -71,6 +79,7 @@ impl PersonName {
             person_name_type: None,
             code: None,
             name_details_key_ref: None,
+            extensions: Extensions::default(),
         }
     }
 
@@ -111,12 +120,14 @@ impl EMLElement for PersonName {
                 name_details_key_ref: elem
                     .attribute_value("NameDetailsKeyRef")?
                     .map(|s| s.into_owned()),
+                    extensions as Extensions,
             }
         ))
     }
 
     fn write_eml(&self, writer: crate::io::EMLElementWriter) -> Result<(), EMLError> {
         writer
+            .extensions(&self.extensions)?
             .attr_opt("Type", self.person_name_type.as_ref())?
             .attr_opt("Code", self.code.as_ref())?
             .attr_opt("NameDetailsKeyRef", self.name_details_key_ref.as_ref())?
@@ -129,7 +140,8 @@ impl EMLElement for PersonName {
 }
 
 /// Details of the initials line of a person's name.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct NameLineInitials {
     /// The initials value.
     pub value: String,
@@ -159,12 +171,12 @@ impl EMLElement for NameLineInitials {
     const EML_NAME: QualifiedName<'_, '_> = QualifiedName::from_static("NameLine", Some(NS_XNL));
 
     fn read_eml(elem: &mut crate::io::EMLElementReader<'_, '_>) -> Result<Self, crate::EMLError> {
-        let name_type = elem.attribute_value_req("NameType")?;
+        let (name_type, name_type_span) = elem.attribute_value_req_with_span("NameType")?;
         if name_type.as_ref() != "Initials" {
             let err = EMLError::invalid_value(
                 elem.name()?.as_owned(),
                 NameTypeInitialsError,
-                Some(elem.span()),
+                Some(name_type_span),
             );
             if elem.parsing_mode().is_strict() {
                 return Err(err);
@@ -182,6 +194,7 @@ impl EMLElement for NameLineInitials {
 
     fn write_eml(&self, writer: crate::io::EMLElementWriter) -> Result<(), crate::EMLError> {
         writer
+            .attr("NameType", "Initials")?
             .attr_opt("Type", self.name_line_type.as_ref())?
             .attr_opt("Code", self.code.as_ref())?
             .text(&self.value)?
@@ -191,7 +204,8 @@ impl EMLElement for NameLineInitials {
 }
 
 /// Details of the first name of a person.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FirstName {
     /// The first name value.
     pub value: String,
@@ -239,7 +253,8 @@ impl EMLElement for FirstName {
 }
 
 /// Details of the prefix of a person's last name.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct NamePrefix {
     /// The prefix value.
     pub value: String,
@@ -287,7 +302,8 @@ impl EMLElement for NamePrefix {
 }
 
 /// Details of the last name of a person.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LastName {
     /// The last name value.
     pub value: String,
@@ -333,3 +349,28 @@ impl EMLElement for LastName {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        documents::candidate_lists::CandidateLists,
+        io::{EMLParsingMode, EMLRead as _, EMLWrite as _},
+    };
+
+    #[test]
+    fn test_initials_round_trip() {
+        let doc = include_str!("../../test-emls/candidate_list/eml230b_test.eml.xml");
+        let candidate_lists = CandidateLists::parse_eml(doc, EMLParsingMode::Strict)
+            .ok()
+            .expect("Failed to parse EML document");
+        let written = candidate_lists
+            .write_eml_root_str(true, true)
+            .expect("Failed to write EML document");
+        assert!(written.contains("NameType=\"Initials\""));
+
+        // The written document must be readable again
+        CandidateLists::parse_eml(&written, EMLParsingMode::Strict)
+            .ok()
+            .expect("Failed to parse written EML document");
+    }
+}
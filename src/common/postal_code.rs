@@ -1,13 +1,18 @@
 use crate::{
     NS_XAL,
-    io::{EMLElement, EMLElementReader, EMLElementWriter, QualifiedName, collect_struct},
+    io::{
+        EMLElement, EMLElementReader, EMLElementWriter, Extensions, QualifiedName, collect_struct,
+    },
 };
 
 /// Postal code element
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PostalCode {
     /// Postal code number
     pub number: PostalCodeNumber,
+    /// Unknown attributes and child elements, see [`Extensions`].
+    pub extensions: Extensions,
 }
 
 impl EMLElement for PostalCode {
@@ -16,18 +21,21 @@ impl EMLElement for PostalCode {
     fn read_eml(elem: &mut EMLElementReader<'_, '_>) -> Result<Self, crate::EMLError> {
         Ok(collect_struct!(elem, PostalCode {
             number: PostalCodeNumber::EML_NAME => |elem| PostalCodeNumber::read_eml(elem)?,
+            extensions as Extensions,
         }))
     }
 
     fn write_eml(&self, writer: EMLElementWriter) -> Result<(), crate::EMLError> {
         writer
+            .extensions(&self.extensions)?
             .child_elem(PostalCodeNumber::EML_NAME, &self.number)?
             .finish()
     }
 }
 
 /// Postal code number element
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PostalCodeNumber {
     /// Type attribute of the postal code number
     pub number_type: Option<String>,
@@ -4,7 +4,8 @@ use crate::{
 };
 
 /// Identifier for the reporting unit.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ReportingUnitIdentifier {
     /// Id of the reporting unit.
     pub id: StringValue<ReportingUnitIdentifierId>,
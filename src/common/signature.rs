@@ -0,0 +1,357 @@
+use std::borrow::Cow;
+
+use crate::{
+    EMLError, NS_DS,
+    common::CanonicalizationMethod,
+    io::{
+        EMLElement, EMLElementReader, EMLElementWriter, Extensions, QualifiedName, collect_struct,
+    },
+    utils::{Base64Binary, StringValue},
+};
+
+/// XML Signature element, as defined by
+/// [XML Signature Syntax and Processing](https://www.w3.org/TR/xmldsig-core1/).
+///
+/// EML_NL documents can contain an enveloped signature as the last child of
+/// the root element. This only describes the structure of the signature,
+/// signatures can be verified and created with the `xmldsig` feature enabled.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Signature {
+    /// Identifier of the signature
+    pub id: Option<String>,
+    /// The information that is signed
+    pub signed_info: SignedInfo,
+    /// Value of the signature over the canonical form of the signed info
+    pub signature_value: StringValue<Base64Binary>,
+    /// Information about the key that created the signature
+    pub key_info: Option<KeyInfo>,
+    /// Unknown attributes and child elements, see [`Extensions`].
+    pub extensions: Extensions,
+}
+
+impl EMLElement for Signature {
+    const EML_NAME: QualifiedName<'_, '_> = QualifiedName::from_static("Signature", Some(NS_DS));
+
+    fn read_eml(elem: &mut EMLElementReader<'_, '_>) -> Result<Self, EMLError> {
+        Ok(collect_struct!(elem, Signature {
+            id: elem.attribute_value("Id")?.map(Cow::into_owned),
+            signed_info: SignedInfo::EML_NAME => |elem| SignedInfo::read_eml(elem)?,
+            signature_value: ("SignatureValue", NS_DS) => |elem| elem.string_value()?,
+            key_info as Option: KeyInfo::EML_NAME => |elem| KeyInfo::read_eml(elem)?,
+            extensions as Extensions,
+        }))
+    }
+
+    fn write_eml(&self, writer: EMLElementWriter) -> Result<(), EMLError> {
+        writer
+            .extensions(&self.extensions)?
+            .attr_opt("Id", self.id.as_ref())?
+            .child_elem(SignedInfo::EML_NAME, &self.signed_info)?
+            .child(("SignatureValue", NS_DS), |w| {
+                w.text(&self.signature_value.raw())?.finish()
+            })?
+            .child_elem_option(KeyInfo::EML_NAME, self.key_info.as_ref())?
+            .finish()
+    }
+}
+
+/// The information that is signed by a [`Signature`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SignedInfo {
+    /// Algorithm used to canonicalize the signed info before signing it
+    pub canonicalization_method: CanonicalizationMethod,
+    /// Algorithm used to create the signature value
+    pub signature_method: SignatureMethod,
+    /// References to the signed content
+    pub references: Vec<Reference>,
+    /// Unknown attributes and child elements, see [`Extensions`].
+    pub extensions: Extensions,
+}
+
+impl EMLElement for SignedInfo {
+    const EML_NAME: QualifiedName<'_, '_> = QualifiedName::from_static("SignedInfo", Some(NS_DS));
+
+    fn read_eml(elem: &mut EMLElementReader<'_, '_>) -> Result<Self, EMLError> {
+        Ok(collect_struct!(elem, SignedInfo {
+            canonicalization_method: CanonicalizationMethod::EML_NAME => |elem| CanonicalizationMethod::read_eml(elem)?,
+            signature_method: SignatureMethod::EML_NAME => |elem| SignatureMethod::read_eml(elem)?,
+            references as Vec: Reference::EML_NAME => |elem| Reference::read_eml(elem)?,
+            extensions as Extensions,
+        }))
+    }
+
+    fn write_eml(&self, writer: EMLElementWriter) -> Result<(), EMLError> {
+        let mut content = writer
+            .extensions(&self.extensions)?
+            .child_elem(
+                CanonicalizationMethod::EML_NAME,
+                &self.canonicalization_method,
+            )?
+            .child_elem(SignatureMethod::EML_NAME, &self.signature_method)?;
+        for reference in &self.references {
+            content = content.child_elem(Reference::EML_NAME, reference)?;
+        }
+        content.finish()
+    }
+}
+
+/// Algorithm used to create a signature value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SignatureMethod {
+    /// URI of the signature algorithm
+    pub algorithm: String,
+}
+
+impl SignatureMethod {
+    /// Create a SignatureMethod element for the given algorithm URI.
+    pub fn new(algorithm: impl Into<String>) -> Self {
+        SignatureMethod {
+            algorithm: algorithm.into(),
+        }
+    }
+}
+
+impl EMLElement for SignatureMethod {
+    const EML_NAME: QualifiedName<'_, '_> =
+        QualifiedName::from_static("SignatureMethod", Some(NS_DS));
+
+    fn read_eml(elem: &mut EMLElementReader<'_, '_>) -> Result<Self, EMLError> {
+        Ok(collect_struct!(
+            elem,
+            SignatureMethod {
+                algorithm: elem.attribute_value_req("Algorithm")?.into_owned(),
+            }
+        ))
+    }
+
+    fn write_eml(&self, writer: EMLElementWriter) -> Result<(), EMLError> {
+        writer.attr("Algorithm", &self.algorithm)?.empty()
+    }
+}
+
+/// Reference to content that is signed, together with the digest of that
+/// content.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Reference {
+    /// Identifier of the reference
+    pub id: Option<String>,
+    /// URI of the referenced content, where an empty URI refers to the entire
+    /// document containing the signature
+    pub uri: Option<String>,
+    /// Transforms applied to the referenced content before computing the digest
+    pub transforms: Option<Transforms>,
+    /// Algorithm used to compute the digest
+    pub digest_method: DigestMethod,
+    /// Digest of the transformed content
+    pub digest_value: StringValue<Base64Binary>,
+    /// Unknown attributes and child elements, see [`Extensions`].
+    pub extensions: Extensions,
+}
+
+impl EMLElement for Reference {
+    const EML_NAME: QualifiedName<'_, '_> = QualifiedName::from_static("Reference", Some(NS_DS));
+
+    fn read_eml(elem: &mut EMLElementReader<'_, '_>) -> Result<Self, EMLError> {
+        Ok(collect_struct!(elem, Reference {
+            id: elem.attribute_value("Id")?.map(Cow::into_owned),
+            uri: elem.attribute_value("URI")?.map(Cow::into_owned),
+            transforms as Option: Transforms::EML_NAME => |elem| Transforms::read_eml(elem)?,
+            digest_method: DigestMethod::EML_NAME => |elem| DigestMethod::read_eml(elem)?,
+            digest_value: ("DigestValue", NS_DS) => |elem| elem.string_value()?,
+            extensions as Extensions,
+        }))
+    }
+
+    fn write_eml(&self, writer: EMLElementWriter) -> Result<(), EMLError> {
+        writer
+            .extensions(&self.extensions)?
+            .attr_opt("Id", self.id.as_ref())?
+            .attr_opt("URI", self.uri.as_ref())?
+            .child_elem_option(Transforms::EML_NAME, self.transforms.as_ref())?
+            .child_elem(DigestMethod::EML_NAME, &self.digest_method)?
+            .child(("DigestValue", NS_DS), |w| {
+                w.text(&self.digest_value.raw())?.finish()
+            })?
+            .finish()
+    }
+}
+
+/// Transforms applied to referenced content, in order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Transforms {
+    /// The transforms, in the order they are applied
+    pub transforms: Vec<Transform>,
+    /// Unknown attributes and child elements, see [`Extensions`].
+    pub extensions: Extensions,
+}
+
+impl EMLElement for Transforms {
+    const EML_NAME: QualifiedName<'_, '_> = QualifiedName::from_static("Transforms", Some(NS_DS));
+
+    fn read_eml(elem: &mut EMLElementReader<'_, '_>) -> Result<Self, EMLError> {
+        Ok(collect_struct!(elem, Transforms {
+            transforms as Vec: Transform::EML_NAME => |elem| Transform::read_eml(elem)?,
+            extensions as Extensions,
+        }))
+    }
+
+    fn write_eml(&self, writer: EMLElementWriter) -> Result<(), EMLError> {
+        let mut content = writer.extensions(&self.extensions)?.content()?;
+        for transform in &self.transforms {
+            content = content.child_elem(Transform::EML_NAME, transform)?;
+        }
+        content.finish()
+    }
+}
+
+/// A transform applied to referenced content.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Transform {
+    /// URI of the transform algorithm
+    pub algorithm: String,
+}
+
+impl Transform {
+    /// Create a Transform element for the given algorithm URI.
+    pub fn new(algorithm: impl Into<String>) -> Self {
+        Transform {
+            algorithm: algorithm.into(),
+        }
+    }
+}
+
+impl EMLElement for Transform {
+    const EML_NAME: QualifiedName<'_, '_> = QualifiedName::from_static("Transform", Some(NS_DS));
+
+    fn read_eml(elem: &mut EMLElementReader<'_, '_>) -> Result<Self, EMLError> {
+        Ok(collect_struct!(
+            elem,
+            Transform {
+                algorithm: elem.attribute_value_req("Algorithm")?.into_owned(),
+            }
+        ))
+    }
+
+    fn write_eml(&self, writer: EMLElementWriter) -> Result<(), EMLError> {
+        writer.attr("Algorithm", &self.algorithm)?.empty()
+    }
+}
+
+/// Algorithm used to compute a digest.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DigestMethod {
+    /// URI of the digest algorithm
+    pub algorithm: String,
+}
+
+impl DigestMethod {
+    /// Create a DigestMethod element for the given algorithm URI.
+    pub fn new(algorithm: impl Into<String>) -> Self {
+        DigestMethod {
+            algorithm: algorithm.into(),
+        }
+    }
+}
+
+impl EMLElement for DigestMethod {
+    const EML_NAME: QualifiedName<'_, '_> = QualifiedName::from_static("DigestMethod", Some(NS_DS));
+
+    fn read_eml(elem: &mut EMLElementReader<'_, '_>) -> Result<Self, EMLError> {
+        Ok(collect_struct!(
+            elem,
+            DigestMethod {
+                algorithm: elem.attribute_value_req("Algorithm")?.into_owned(),
+            }
+        ))
+    }
+
+    fn write_eml(&self, writer: EMLElementWriter) -> Result<(), EMLError> {
+        writer.attr("Algorithm", &self.algorithm)?.empty()
+    }
+}
+
+/// Information about the key that created a [`Signature`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct KeyInfo {
+    /// Identifier of the key info
+    pub id: Option<String>,
+    /// Names identifying the key
+    pub key_names: Vec<String>,
+    /// X.509 certificates of the key
+    pub x509_data: Vec<X509Data>,
+    /// Unknown attributes and child elements, see [`Extensions`].
+    pub extensions: Extensions,
+}
+
+impl KeyInfo {
+    /// Returns all DER encoded certificates contained in this key info.
+    pub fn certificates(&self) -> impl Iterator<Item = &StringValue<Base64Binary>> {
+        self.x509_data.iter().flat_map(|data| &data.certificates)
+    }
+}
+
+impl EMLElement for KeyInfo {
+    const EML_NAME: QualifiedName<'_, '_> = QualifiedName::from_static("KeyInfo", Some(NS_DS));
+
+    fn read_eml(elem: &mut EMLElementReader<'_, '_>) -> Result<Self, EMLError> {
+        Ok(collect_struct!(elem, KeyInfo {
+            id: elem.attribute_value("Id")?.map(Cow::into_owned),
+            key_names as Vec: ("KeyName", NS_DS) => |elem| elem.text_without_children()?,
+            x509_data as Vec: X509Data::EML_NAME => |elem| X509Data::read_eml(elem)?,
+            extensions as Extensions,
+        }))
+    }
+
+    fn write_eml(&self, writer: EMLElementWriter) -> Result<(), EMLError> {
+        let mut content = writer
+            .extensions(&self.extensions)?
+            .attr_opt("Id", self.id.as_ref())?
+            .content()?;
+        for key_name in &self.key_names {
+            content = content.child(("KeyName", NS_DS), |w| w.text(key_name)?.finish())?;
+        }
+        for data in &self.x509_data {
+            content = content.child_elem(X509Data::EML_NAME, data)?;
+        }
+        content.finish()
+    }
+}
+
+/// X.509 certificates in a [`KeyInfo`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct X509Data {
+    /// DER encoded certificates
+    pub certificates: Vec<StringValue<Base64Binary>>,
+    /// Unknown attributes and child elements, see [`Extensions`].
+    pub extensions: Extensions,
+}
+
+impl EMLElement for X509Data {
+    const EML_NAME: QualifiedName<'_, '_> = QualifiedName::from_static("X509Data", Some(NS_DS));
+
+    fn read_eml(elem: &mut EMLElementReader<'_, '_>) -> Result<Self, EMLError> {
+        Ok(collect_struct!(elem, X509Data {
+            certificates as Vec: ("X509Certificate", NS_DS) => |elem| elem.string_value()?,
+            extensions as Extensions,
+        }))
+    }
+
+    fn write_eml(&self, writer: EMLElementWriter) -> Result<(), EMLError> {
+        let mut content = writer.extensions(&self.extensions)?.content()?;
+        for certificate in &self.certificates {
+            content = content.child(("X509Certificate", NS_DS), |w| {
+                w.text(&certificate.raw())?.finish()
+            })?;
+        }
+        content.finish()
+    }
+}
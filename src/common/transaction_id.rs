@@ -10,7 +10,8 @@ use crate::{
 ///
 /// EML_NL documents contain a transaction id, but this is generally not used
 /// and set to `1` as a default.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TransactionId(pub StringValue<u64>);
 
 impl TransactionId {
@@ -0,0 +1,273 @@
+//! Human readable rendering of errors, with line and column information and
+//! an annotated snippet of the source document.
+//!
+//! A [`Span`] only contains byte offsets in the source document. Given the
+//! source text, a [`Diagnostic`] renders an [`EMLError`] in a style similar to
+//! the Rust compiler:
+//!
+//! ```text
+//! warning: Unexpected element: {http://www.kiesraad.nl/extensions}NominationDate inside of ...
+//!   --> eml110b_test.eml.xml:23:13
+//!    |
+//! 23 |             <kr:NominationDate>2022-03-02</kr:NominationDate>
+//!    |             ^^^^^^^^^^^^^^^^^^^
+//! ```
+//!
+//! Errors consisting of [multiple errors](crate::MultipleEMLErrors) are
+//! rendered as one diagnostic per error.
+
+use std::fmt;
+
+use crate::{EMLError, io::Span};
+
+/// A position in a source document, as a line and column number.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SourceLocation {
+    /// Line number, starting at 1.
+    pub line: usize,
+    /// Column number in characters, starting at 1.
+    pub column: usize,
+}
+
+impl SourceLocation {
+    /// Determine the location of the given byte offset in the source text.
+    ///
+    /// Offsets beyond the end of the source are clamped to the end, offsets
+    /// inside a multi-byte character refer to the start of that character.
+    pub fn from_offset(source: &str, offset: u64) -> SourceLocation {
+        let offset = floor_char_boundary(source, offset);
+        let before = &source[..offset];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        SourceLocation {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+        }
+    }
+}
+
+impl fmt::Display for SourceLocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
+
+/// Clamp the offset to the source length and move it back to a character boundary.
+fn floor_char_boundary(source: &str, offset: u64) -> usize {
+    let mut offset = usize::try_from(offset)
+        .unwrap_or(usize::MAX)
+        .min(source.len());
+    while !source.is_char_boundary(offset) {
+        offset -= 1;
+    }
+    offset
+}
+
+/// An [`EMLError`] together with the source text it refers to, which can be
+/// rendered using its [`Display`](fmt::Display) implementation.
+#[derive(Debug, Clone, Copy)]
+pub struct Diagnostic<'a> {
+    error: &'a EMLError,
+    source: &'a str,
+    file_name: Option<&'a str>,
+    fatal: bool,
+}
+
+impl<'a> Diagnostic<'a> {
+    /// Create a diagnostic for an error in the given source text.
+    pub fn new(error: &'a EMLError, source: &'a str) -> Diagnostic<'a> {
+        Diagnostic {
+            error,
+            source,
+            file_name: None,
+            fatal: false,
+        }
+    }
+
+    /// Render the error as the error that stopped processing the document.
+    ///
+    /// By default only errors of a fatal [kind](EMLError::is_fatal) are
+    /// rendered as errors, and other errors as warnings. Use this for the error
+    /// returned when parsing fails, as in strict mode any error is fatal. When
+    /// the error consists of multiple errors, only the last one is considered
+    /// fatal.
+    pub fn as_fatal(mut self) -> Diagnostic<'a> {
+        self.fatal = true;
+        self
+    }
+
+    /// Set the file name that is shown in front of the location.
+    pub fn with_file_name(mut self, file_name: &'a str) -> Diagnostic<'a> {
+        self.file_name = Some(file_name);
+        self
+    }
+
+    fn render_single(&self, f: &mut fmt::Formatter<'_>, error: &EMLError) -> fmt::Result {
+        let severity = if self.fatal || error.is_fatal() {
+            "error"
+        } else {
+            "warning"
+        };
+        writeln!(f, "{severity}: {}", error.kind())?;
+
+        let Some(span) = error.span() else {
+            if let Some(file_name) = self.file_name {
+                writeln!(f, "  --> {file_name}")?;
+            }
+            return Ok(());
+        };
+
+        let start = floor_char_boundary(self.source, span.start);
+        // Some spans end before they start, those are rendered as a single position
+        let end = floor_char_boundary(self.source, span.end).max(start);
+        let location = SourceLocation::from_offset(self.source, span.start);
+
+        let line_start = self.source[..start].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = self.source[start..]
+            .find('\n')
+            .map_or(self.source.len(), |i| start + i);
+        let line = self.source[line_start..line_end].trim_end_matches('\r');
+
+        // Keep tabs in the padding so the carets line up with the source line
+        let padding: String = self.source[line_start..start]
+            .chars()
+            .map(|c| if c == '\t' { '\t' } else { ' ' })
+            .collect();
+        let underlined = self.source[start..end.min(line_end)].chars().count();
+        let carets = "^".repeat(underlined.max(1));
+
+        let gutter = " ".repeat(location.line.to_string().len());
+        match self.file_name {
+            Some(file_name) => writeln!(f, "{gutter}--> {file_name}:{location}")?,
+            None => writeln!(f, "{gutter}--> {location}")?,
+        }
+        writeln!(f, "{gutter} |")?;
+        writeln!(f, "{} | {line}", location.line)?;
+        writeln!(f, "{gutter} | {padding}{carets}")
+    }
+}
+
+impl fmt::Display for Diagnostic<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.error {
+            EMLError::Multiple(multiple) => {
+                let last = multiple.errors.len() - 1;
+                for (i, error) in multiple.errors.iter().enumerate() {
+                    if i > 0 {
+                        writeln!(f)?;
+                    }
+                    let fatal = self.fatal && i == last;
+                    Diagnostic {
+                        error,
+                        fatal,
+                        ..*self
+                    }
+                    .fmt(f)?;
+                }
+                Ok(())
+            }
+            error => self.render_single(f, error),
+        }
+    }
+}
+
+/// Render all given errors for the given source text, separated by empty lines.
+pub fn render_errors(errors: &[EMLError], source: &str, file_name: Option<&str>) -> String {
+    errors
+        .iter()
+        .map(|error| {
+            let diagnostic = Diagnostic::new(error, source);
+            match file_name {
+                Some(file_name) => diagnostic.with_file_name(file_name).to_string(),
+                None => diagnostic.to_string(),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+impl Span {
+    /// Determine the location of the start of this span in the given source text.
+    pub fn start_location(&self, source: &str) -> SourceLocation {
+        SourceLocation::from_offset(source, self.start)
+    }
+
+    /// Determine the location of the end of this span in the given source text.
+    pub fn end_location(&self, source: &str) -> SourceLocation {
+        SourceLocation::from_offset(source, self.end)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        EMLErrorKind,
+        documents::EML,
+        io::{EMLParsingMode, EMLRead as _},
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_source_location() {
+        let source = "ab\ncdé\r\nf";
+        assert_eq!(
+            SourceLocation::from_offset(source, 0),
+            SourceLocation { line: 1, column: 1 }
+        );
+        assert_eq!(
+            SourceLocation::from_offset(source, 4),
+            SourceLocation { line: 2, column: 2 }
+        );
+        // Inside the two byte é
+        assert_eq!(
+            SourceLocation::from_offset(source, 6),
+            SourceLocation { line: 2, column: 3 }
+        );
+        assert_eq!(
+            SourceLocation::from_offset(source, 100),
+            SourceLocation { line: 3, column: 2 }
+        );
+    }
+
+    #[test]
+    fn test_render_positioned_error() {
+        let source = "<a>\n\t<b>x</b>\n</a>";
+        let error = EMLErrorKind::MissingElement(
+            crate::io::QualifiedName::from_static("c", None).as_owned(),
+        )
+        .add_span(Span::new(5, 8));
+        let rendered = Diagnostic::new(&error, source)
+            .with_file_name("test.xml")
+            .to_string();
+        assert_eq!(
+            rendered,
+            format!(
+                "error: {}\n --> test.xml:2:2\n  |\n2 | \t<b>x</b>\n  | \t^^^\n",
+                error.kind()
+            )
+        );
+    }
+
+    #[test]
+    fn test_render_multiple_errors() {
+        let source = include_str!("../test-emls/polling_stations/eml110b_test.eml.xml")
+            .replacen("<kr:ElectionDate>", "<Unknown/><kr:ElectionDate>", 1)
+            .replacen("</EML>", "", 1);
+        let error = EML::parse_eml(&source, EMLParsingMode::Strict)
+            .ok()
+            .expect_err("Document should not parse");
+        let EMLError::Multiple(multiple) = &error else {
+            panic!("Expected multiple errors, got {error:?}");
+        };
+
+        let rendered = Diagnostic::new(&error, &source).as_fatal().to_string();
+        assert_eq!(
+            rendered.matches("warning: ").count(),
+            multiple.errors.len() - 1
+        );
+        assert_eq!(rendered.matches("error: ").count(), 1);
+        assert!(rendered.contains("<Unknown/>"));
+        assert_eq!(render_errors(&multiple.errors, &source, None), rendered);
+    }
+}
@@ -0,0 +1,1124 @@
+//! Structural comparison of two EML_NL documents of the same type.
+//!
+//! Use [`diff`] to find out what changed between two versions of a document,
+//! for example when a corrected polling stations (`110b`) document is received
+//! or when a candidate list (`230b`) is amended. The result is a list of
+//! [`Change`]s, each with a path describing where in the document the change
+//! was found.
+//!
+//! Detailed changes are reported for election definitions (`110a`), polling
+//! stations (`110b`), nominations (`210`) and candidate lists (`230b`). For the
+//! other documents only the document header is compared in detail, other
+//! differences are reported as a single [`ChangeKind::ElementChanged`].
+
+use std::{borrow::Cow, fmt};
+
+use crate::{
+    common::{
+        CreationDateTime, ElectionDomain, IssueDate, ManagingAuthority, PersonName, TransactionId,
+    },
+    documents::{
+        EML,
+        candidate_lists::{
+            CandidateLists, CandidateListsAffiliation, CandidateListsCandidate,
+            CandidateListsElectionIdentifier,
+        },
+        count::Count,
+        election_definition::{ElectionDefinition, ElectionDefinitionElectionIdentifier},
+        election_result::ElectionResult,
+        nomination::Nomination,
+        polling_stations::{PollingPlace, PollingStations, PollingStationsElectionIdentifier},
+    },
+    utils::{StringValue, StringValueData},
+};
+
+/// Error returned when two documents cannot be compared.
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DiffError {
+    /// The documents are not of the same type
+    #[error("Cannot compare a {0} document with a {1} document")]
+    DocumentTypeMismatch(String, String),
+}
+
+/// A single change between two documents.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Change {
+    /// Path of the changed element or attribute, e.g.
+    /// `EML/CandidateList/Election/Contest/Affiliation[Id=1]/Candidate[Id=2]`.
+    pub path: String,
+
+    /// What changed.
+    pub kind: ChangeKind,
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.path, self.kind)
+    }
+}
+
+/// The kind of change found between two documents.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ChangeKind {
+    /// The value of an attribute or element changed, was added or was removed.
+    ValueChanged {
+        /// The old value, if present.
+        old: Option<String>,
+        /// The new value, if present.
+        new: Option<String>,
+    },
+    /// An element changed in a way that is not described in more detail.
+    ElementChanged,
+    /// A polling station with the given id was added.
+    PollingStationAdded(String),
+    /// A polling station with the given id was removed.
+    PollingStationRemoved(String),
+    /// A polling station with the given id was modified, the details are
+    /// reported as separate changes.
+    PollingStationModified(String),
+    /// A registered party with the given appellation was added.
+    RegisteredPartyAdded(String),
+    /// A registered party with the given appellation was removed.
+    RegisteredPartyRemoved(String),
+    /// An affiliation (list) with the given name or id was added.
+    AffiliationAdded(String),
+    /// An affiliation (list) with the given name or id was removed.
+    AffiliationRemoved(String),
+    /// A candidate with the given name was added.
+    CandidateAdded(String),
+    /// A candidate with the given name was removed.
+    CandidateRemoved(String),
+    /// A candidate changed position relative to the other candidates on the list.
+    CandidateMoved {
+        /// Name of the candidate.
+        name: String,
+        /// Old position on the list, starting at 1.
+        old_position: usize,
+        /// New position on the list, starting at 1.
+        new_position: usize,
+    },
+    /// The name of a candidate changed.
+    NameChanged {
+        /// The old name.
+        old: String,
+        /// The new name.
+        new: String,
+    },
+}
+
+impl fmt::Display for ChangeKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ChangeKind::ValueChanged { old, new } => match (old, new) {
+                (Some(old), Some(new)) => write!(f, "changed from '{old}' to '{new}'"),
+                (None, Some(new)) => write!(f, "added '{new}'"),
+                (Some(old), None) => write!(f, "removed '{old}'"),
+                (None, None) => write!(f, "changed"),
+            },
+            ChangeKind::ElementChanged => write!(f, "changed"),
+            ChangeKind::PollingStationAdded(id) => write!(f, "polling station {id} added"),
+            ChangeKind::PollingStationRemoved(id) => write!(f, "polling station {id} removed"),
+            ChangeKind::PollingStationModified(id) => write!(f, "polling station {id} modified"),
+            ChangeKind::RegisteredPartyAdded(name) => write!(f, "registered party '{name}' added"),
+            ChangeKind::RegisteredPartyRemoved(name) => {
+                write!(f, "registered party '{name}' removed")
+            }
+            ChangeKind::AffiliationAdded(name) => write!(f, "affiliation '{name}' added"),
+            ChangeKind::AffiliationRemoved(name) => write!(f, "affiliation '{name}' removed"),
+            ChangeKind::CandidateAdded(name) => write!(f, "candidate '{name}' added"),
+            ChangeKind::CandidateRemoved(name) => write!(f, "candidate '{name}' removed"),
+            ChangeKind::CandidateMoved {
+                name,
+                old_position,
+                new_position,
+            } => write!(
+                f,
+                "candidate '{name}' moved from position {old_position} to {new_position}"
+            ),
+            ChangeKind::NameChanged { old, new } => {
+                write!(f, "name changed from '{old}' to '{new}'")
+            }
+        }
+    }
+}
+
+/// Compare two documents of the same type.
+///
+/// Returns an empty list if the documents are equal, or an error if the
+/// documents are not of the same type.
+pub fn diff(old: &EML, new: &EML) -> Result<Vec<Change>, DiffError> {
+    let mut differ = Differ::default();
+    match (old, new) {
+        (EML::ElectionDefinition(old), EML::ElectionDefinition(new)) => {
+            differ.election_definition(old, new)
+        }
+        (EML::PollingStations(old), EML::PollingStations(new)) => differ.polling_stations(old, new),
+        (EML::Nomination(old), EML::Nomination(new)) => differ.nomination(old, new),
+        (EML::CandidateLists(old), EML::CandidateLists(new)) => differ.candidate_lists(old, new),
+        (EML::PollingStationCount(old), EML::PollingStationCount(new))
+        | (EML::Count(old), EML::Count(new))
+        | (EML::PrincipalCommitteeCount(old), EML::PrincipalCommitteeCount(new))
+        | (EML::Totals(old), EML::Totals(new)) => {
+            differ.header(ROOT, Header::from(&**old), Header::from(&**new));
+            differ.element(&child(ROOT, "Count"), &old.count, &new.count);
+        }
+        (EML::ElectionResult(old), EML::ElectionResult(new)) => {
+            differ.header(ROOT, Header::from(&**old), Header::from(&**new));
+            differ.element(&child(ROOT, "Result"), &old.result, &new.result);
+        }
+        _ => {
+            return Err(DiffError::DocumentTypeMismatch(
+                old.to_eml_id().to_string(),
+                new.to_eml_id().to_string(),
+            ));
+        }
+    }
+    Ok(differ.changes)
+}
+
+/// Compare two election definition (`110a`) documents.
+pub fn diff_election_definitions(
+    old: &ElectionDefinition,
+    new: &ElectionDefinition,
+) -> Vec<Change> {
+    let mut differ = Differ::default();
+    differ.election_definition(old, new);
+    differ.changes
+}
+
+/// Compare two polling stations (`110b`) documents.
+pub fn diff_polling_stations(old: &PollingStations, new: &PollingStations) -> Vec<Change> {
+    let mut differ = Differ::default();
+    differ.polling_stations(old, new);
+    differ.changes
+}
+
+/// Compare two candidate lists (`230b`) documents.
+pub fn diff_candidate_lists(old: &CandidateLists, new: &CandidateLists) -> Vec<Change> {
+    let mut differ = Differ::default();
+    differ.candidate_lists(old, new);
+    differ.changes
+}
+
+const ROOT: &str = "EML";
+
+fn child(path: &str, name: &str) -> String {
+    format!("{path}/{name}")
+}
+
+fn keyed_child(path: &str, name: &str, key: &str, value: &str) -> String {
+    format!("{path}/{name}[{key}={value}]")
+}
+
+/// Format a person name for display, e.g. `A.B. (Anna) de Vries`.
+fn format_person_name(name: &PersonName) -> String {
+    let first_name = name.first_name.as_ref().map(|n| format!("({})", n.value));
+    [
+        name.name_line_initials.as_ref().map(|n| n.value.as_str()),
+        first_name.as_deref(),
+        name.name_prefix.as_ref().map(|n| n.value.as_str()),
+        Some(name.last_name.value.as_str()),
+    ]
+    .into_iter()
+    .flatten()
+    .collect::<Vec<_>>()
+    .join(" ")
+}
+
+/// The header elements shared by all documents.
+struct Header<'a> {
+    transaction_id: &'a TransactionId,
+    managing_authority: Option<&'a ManagingAuthority>,
+    issue_date: Option<&'a IssueDate>,
+    creation_date_time: &'a CreationDateTime,
+}
+
+macro_rules! impl_header_from {
+    ($ty:ty, |$doc:ident| $managing_authority:expr, $issue_date:expr) => {
+        impl<'a> From<&'a $ty> for Header<'a> {
+            fn from($doc: &'a $ty) -> Self {
+                Header {
+                    transaction_id: &$doc.transaction_id,
+                    managing_authority: $managing_authority,
+                    issue_date: $issue_date,
+                    creation_date_time: &$doc.creation_date_time,
+                }
+            }
+        }
+    };
+}
+
+impl_header_from!(
+    ElectionDefinition,
+    |doc| doc.managing_authority.as_ref(),
+    doc.issue_date.as_ref()
+);
+impl_header_from!(
+    PollingStations,
+    |doc| Some(&doc.managing_authority),
+    doc.issue_date.as_ref()
+);
+impl_header_from!(
+    Nomination,
+    |doc| doc.managing_authority.as_ref(),
+    doc.issue_date.as_ref()
+);
+impl_header_from!(
+    CandidateLists,
+    |doc| Some(&doc.managing_authority),
+    Some(&doc.issue_date)
+);
+impl_header_from!(
+    Count,
+    |doc| Some(&doc.managing_authority),
+    doc.issue_date.as_ref()
+);
+impl_header_from!(
+    ElectionResult,
+    |doc| Some(&doc.managing_authority),
+    doc.issue_date.as_ref()
+);
+
+/// The election identifying values shared by the documents.
+struct ElectionIdentifier<'a> {
+    id: String,
+    name: Option<&'a str>,
+    category: String,
+    subcategory: Option<String>,
+    domain: Option<&'a ElectionDomain>,
+    election_date: String,
+    nomination_date: Option<String>,
+}
+
+impl<'a> From<&'a ElectionDefinitionElectionIdentifier> for ElectionIdentifier<'a> {
+    fn from(identifier: &'a ElectionDefinitionElectionIdentifier) -> Self {
+        ElectionIdentifier {
+            id: identifier.id.raw().into_owned(),
+            name: Some(&identifier.name),
+            category: identifier.category.raw().into_owned(),
+            subcategory: Some(identifier.subcategory.raw().into_owned()),
+            domain: identifier.domain.as_ref(),
+            election_date: identifier.election_date.raw().into_owned(),
+            nomination_date: Some(identifier.nomination_date.raw().into_owned()),
+        }
+    }
+}
+
+impl<'a> From<&'a PollingStationsElectionIdentifier> for ElectionIdentifier<'a> {
+    fn from(identifier: &'a PollingStationsElectionIdentifier) -> Self {
+        ElectionIdentifier {
+            id: identifier.id.raw().into_owned(),
+            name: identifier.name.as_deref(),
+            category: identifier.category.raw().into_owned(),
+            subcategory: identifier
+                .subcategory
+                .as_ref()
+                .map(|s| s.raw().into_owned()),
+            domain: identifier.domain.as_ref(),
+            election_date: identifier.election_date.raw().into_owned(),
+            nomination_date: None,
+        }
+    }
+}
+
+impl<'a> From<&'a CandidateListsElectionIdentifier> for ElectionIdentifier<'a> {
+    fn from(identifier: &'a CandidateListsElectionIdentifier) -> Self {
+        ElectionIdentifier {
+            id: identifier.id.raw().into_owned(),
+            name: identifier.name.as_deref(),
+            category: identifier.category.raw().into_owned(),
+            subcategory: identifier
+                .subcategory
+                .as_ref()
+                .map(|s| s.raw().into_owned()),
+            domain: identifier.domain.as_ref(),
+            election_date: identifier.election_date.raw().into_owned(),
+            nomination_date: Some(identifier.nomination_date.raw().into_owned()),
+        }
+    }
+}
+
+/// Collects the changes between two documents.
+#[derive(Default)]
+struct Differ {
+    changes: Vec<Change>,
+}
+
+impl Differ {
+    fn push(&mut self, path: String, kind: ChangeKind) {
+        self.changes.push(Change { path, kind });
+    }
+
+    /// Report a change if the given text values differ.
+    fn text(&mut self, path: String, old: Option<&str>, new: Option<&str>) {
+        if old != new {
+            self.push(
+                path,
+                ChangeKind::ValueChanged {
+                    old: old.map(str::to_string),
+                    new: new.map(str::to_string),
+                },
+            );
+        }
+    }
+
+    /// Report a change if the given values differ, compared by their canonical raw value.
+    fn value<T: StringValueData>(
+        &mut self,
+        path: String,
+        old: Option<&StringValue<T>>,
+        new: Option<&StringValue<T>>,
+    ) {
+        if old != new {
+            self.push(
+                path,
+                ChangeKind::ValueChanged {
+                    old: old.map(|v| v.raw().into_owned()),
+                    new: new.map(|v| v.raw().into_owned()),
+                },
+            );
+        }
+    }
+
+    /// Report a change without further details if the given elements differ.
+    fn element<T: PartialEq>(&mut self, path: &str, old: &T, new: &T) {
+        if old != new {
+            self.push(path.to_string(), ChangeKind::ElementChanged);
+        }
+    }
+
+    /// Report a change without further details if the given elements differ,
+    /// but no detailed changes were reported since `start`.
+    fn fallback<T: PartialEq>(&mut self, start: usize, path: &str, old: &T, new: &T) {
+        if self.changes.len() == start {
+            self.element(path, old, new);
+        }
+    }
+
+    fn header(&mut self, path: &str, old: Header<'_>, new: Header<'_>) {
+        self.value(
+            child(path, "TransactionId"),
+            Some(&old.transaction_id.0),
+            Some(&new.transaction_id.0),
+        );
+        match (old.managing_authority, new.managing_authority) {
+            (Some(old), Some(new)) => {
+                self.managing_authority(&child(path, "ManagingAuthority"), old, new)
+            }
+            (old, new) => self.element(&child(path, "ManagingAuthority"), &old, &new),
+        }
+        self.value(
+            child(path, "IssueDate"),
+            old.issue_date.map(|d| &d.0),
+            new.issue_date.map(|d| &d.0),
+        );
+        self.value(
+            child(path, "CreationDateTime"),
+            Some(&old.creation_date_time.0),
+            Some(&new.creation_date_time.0),
+        );
+    }
+
+    fn managing_authority(&mut self, path: &str, old: &ManagingAuthority, new: &ManagingAuthority) {
+        let start = self.changes.len();
+        let identifier = child(path, "AuthorityIdentifier");
+        self.value(
+            child(&identifier, "@Id"),
+            Some(&old.authority_identifier.id),
+            Some(&new.authority_identifier.id),
+        );
+        self.text(
+            identifier,
+            old.authority_identifier.name.as_deref(),
+            new.authority_identifier.name.as_deref(),
+        );
+        self.element(
+            &child(path, "AuthorityAddress"),
+            &old.authority_address,
+            &new.authority_address,
+        );
+        self.fallback(start, path, old, new);
+    }
+
+    fn election_identifier(
+        &mut self,
+        path: &str,
+        old: ElectionIdentifier<'_>,
+        new: ElectionIdentifier<'_>,
+    ) {
+        self.text(child(path, "@Id"), Some(&old.id), Some(&new.id));
+        self.text(child(path, "ElectionName"), old.name, new.name);
+        self.text(
+            child(path, "ElectionCategory"),
+            Some(&old.category),
+            Some(&new.category),
+        );
+        self.text(
+            child(path, "ElectionSubcategory"),
+            old.subcategory.as_deref(),
+            new.subcategory.as_deref(),
+        );
+        let domain = child(path, "ElectionDomain");
+        self.value(
+            child(&domain, "@Id"),
+            old.domain.map(|d| &d.id),
+            new.domain.map(|d| &d.id),
+        );
+        self.text(
+            domain,
+            old.domain.map(|d| d.name.as_str()),
+            new.domain.map(|d| d.name.as_str()),
+        );
+        self.text(
+            child(path, "ElectionDate"),
+            Some(&old.election_date),
+            Some(&new.election_date),
+        );
+        self.text(
+            child(path, "NominationDate"),
+            old.nomination_date.as_deref(),
+            new.nomination_date.as_deref(),
+        );
+    }
+
+    fn election_definition(&mut self, old: &ElectionDefinition, new: &ElectionDefinition) {
+        self.header(ROOT, Header::from(old), Header::from(new));
+
+        let path = child(ROOT, "ElectionEvent/Election");
+        let (old, new) = (&old.election_event.election, &new.election_event.election);
+        self.election_identifier(
+            &child(&path, "ElectionIdentifier"),
+            (&old.identifier).into(),
+            (&new.identifier).into(),
+        );
+
+        let contest = child(&path, "Contest");
+        self.value(
+            child(&contest, "ContestIdentifier/@Id"),
+            Some(&old.contest.identifier.id),
+            Some(&new.contest.identifier.id),
+        );
+        self.value(
+            child(&contest, "VotingMethod"),
+            Some(&old.contest.voting_method),
+            Some(&new.contest.voting_method),
+        );
+        self.value(
+            child(&contest, "MaxVotes"),
+            Some(&old.contest.max_votes),
+            Some(&new.contest.max_votes),
+        );
+        self.value(
+            child(&path, "NumberOfSeats"),
+            Some(&old.number_of_seats),
+            Some(&new.number_of_seats),
+        );
+        self.value(
+            child(&path, "PreferenceThreshold"),
+            Some(&old.preference_threshold),
+            Some(&new.preference_threshold),
+        );
+        self.element(
+            &child(&path, "ElectionTree"),
+            &old.election_tree,
+            &new.election_tree,
+        );
+
+        let parties = child(&path, "RegisteredParties");
+        for party in &old.registered_parties {
+            if !new.registered_parties.contains(party) {
+                self.push(
+                    parties.clone(),
+                    ChangeKind::RegisteredPartyRemoved(party.registered_appellation.clone()),
+                );
+            }
+        }
+        for party in &new.registered_parties {
+            if !old.registered_parties.contains(party) {
+                self.push(
+                    parties.clone(),
+                    ChangeKind::RegisteredPartyAdded(party.registered_appellation.clone()),
+                );
+            }
+        }
+    }
+
+    fn polling_stations(&mut self, old: &PollingStations, new: &PollingStations) {
+        self.header(ROOT, Header::from(old), Header::from(new));
+
+        let path = child(ROOT, "ElectionEvent/Election");
+        let (old, new) = (&old.election_event.election, &new.election_event.election);
+        self.election_identifier(
+            &child(&path, "ElectionIdentifier"),
+            (&old.identifier).into(),
+            (&new.identifier).into(),
+        );
+
+        let contest = child(&path, "Contest");
+        let (old, new) = (&old.contest, &new.contest);
+        let reporting_unit = child(&contest, "ReportingUnit/ReportingUnitIdentifier");
+        self.value(
+            child(&reporting_unit, "@Id"),
+            Some(&old.reporting_unit.identifier.id),
+            Some(&new.reporting_unit.identifier.id),
+        );
+        self.text(
+            reporting_unit,
+            Some(&old.reporting_unit.identifier.name),
+            Some(&new.reporting_unit.identifier.name),
+        );
+        self.value(
+            child(&contest, "VotingMethod"),
+            Some(&old.voting_method),
+            Some(&new.voting_method),
+        );
+        self.value(
+            child(&contest, "MaxVotes"),
+            Some(&old.max_votes),
+            Some(&new.max_votes),
+        );
+
+        let id = polling_station_id;
+        for old_place in &old.polling_places {
+            let old_id = id(old_place);
+            let place_path = keyed_child(&contest, "PollingPlace", "Id", &old_id);
+            match new.polling_places.iter().find(|p| id(p) == old_id) {
+                Some(new_place) => self.polling_place(place_path, &old_id, old_place, new_place),
+                None => self.push(
+                    place_path,
+                    ChangeKind::PollingStationRemoved(old_id.into_owned()),
+                ),
+            }
+        }
+        for new_place in &new.polling_places {
+            let new_id = id(new_place);
+            if !old.polling_places.iter().any(|p| id(p) == new_id) {
+                self.push(
+                    keyed_child(&contest, "PollingPlace", "Id", &new_id),
+                    ChangeKind::PollingStationAdded(new_id.into_owned()),
+                );
+            }
+        }
+    }
+
+    fn polling_place(&mut self, path: String, id: &str, old: &PollingPlace, new: &PollingPlace) {
+        if old == new {
+            return;
+        }
+        self.push(
+            path.clone(),
+            ChangeKind::PollingStationModified(id.to_string()),
+        );
+
+        let start = self.changes.len();
+        self.value(
+            child(&path, "@Channel"),
+            Some(&old.channel),
+            Some(&new.channel),
+        );
+        let (old, new) = (&old.physical_location, &new.physical_location);
+        self.text(
+            child(&path, "PhysicalLocation/PollingStation"),
+            Some(&old.polling_station.data),
+            Some(&new.polling_station.data),
+        );
+        let locality = child(&path, "PhysicalLocation/Address/Locality");
+        let (old, new) = (&old.address.locality, &new.address.locality);
+        self.text(
+            child(&locality, "LocalityName"),
+            Some(&old.locality_name.name),
+            Some(&new.locality_name.name),
+        );
+        self.text(
+            child(&locality, "PostalCode/PostalCodeNumber"),
+            old.postal_code
+                .as_ref()
+                .and_then(|p| p.number.number.as_deref()),
+            new.postal_code
+                .as_ref()
+                .and_then(|p| p.number.number.as_deref()),
+        );
+        self.fallback(start, &path, old, new);
+    }
+
+    fn nomination(&mut self, old: &Nomination, new: &Nomination) {
+        self.header(ROOT, Header::from(old), Header::from(new));
+
+        let path = child(ROOT, "Nomination");
+        let (old, new) = (&old.nomination, &new.nomination);
+        self.election_identifier(
+            &child(&path, "ElectionIdentifier"),
+            (&old.election_identifier).into(),
+            (&new.election_identifier).into(),
+        );
+        self.element(
+            &child(&path, "ContestIdentifier"),
+            &old.contest_identifier,
+            &new.contest_identifier,
+        );
+        self.affiliation(
+            &child(&path, "Affiliation"),
+            &old.affiliation,
+            &new.affiliation,
+        );
+    }
+
+    fn candidate_lists(&mut self, old: &CandidateLists, new: &CandidateLists) {
+        self.header(ROOT, Header::from(old), Header::from(new));
+
+        let path = child(ROOT, "CandidateList");
+        let (old, new) = (&old.candidate_list, &new.candidate_list);
+        self.value(
+            child(&path, "@ListDate"),
+            old.list_date.as_ref().map(|d| &d.0),
+            new.list_date.as_ref().map(|d| &d.0),
+        );
+
+        let path = child(&path, "Election");
+        let (old, new) = (&old.election, &new.election);
+        self.election_identifier(
+            &child(&path, "ElectionIdentifier"),
+            (&old.identifier).into(),
+            (&new.identifier).into(),
+        );
+
+        let contest = child(&path, "Contest");
+        let (old, new) = (&old.contest, &new.contest);
+        self.value(
+            child(&contest, "ContestIdentifier/@Id"),
+            Some(&old.identifier.id),
+            Some(&new.identifier.id),
+        );
+
+        for old_affiliation in &old.affiliations {
+            let (key, value) = affiliation_key(old_affiliation);
+            let affiliation_path = keyed_child(&contest, "Affiliation", key, &value);
+            match new
+                .affiliations
+                .iter()
+                .find(|a| affiliation_key(a) == (key, value.clone()))
+            {
+                Some(new_affiliation) => {
+                    self.affiliation(&affiliation_path, old_affiliation, new_affiliation)
+                }
+                None => self.push(
+                    affiliation_path,
+                    ChangeKind::AffiliationRemoved(affiliation_name(old_affiliation)),
+                ),
+            }
+        }
+        for new_affiliation in &new.affiliations {
+            let key = affiliation_key(new_affiliation);
+            if !old.affiliations.iter().any(|a| affiliation_key(a) == key) {
+                self.push(
+                    keyed_child(&contest, "Affiliation", key.0, &key.1),
+                    ChangeKind::AffiliationAdded(affiliation_name(new_affiliation)),
+                );
+            }
+        }
+    }
+
+    fn affiliation(
+        &mut self,
+        path: &str,
+        old: &CandidateListsAffiliation,
+        new: &CandidateListsAffiliation,
+    ) {
+        let identifier = child(path, "AffiliationIdentifier");
+        self.value(
+            child(&identifier, "@Id"),
+            old.identifier.id.as_ref(),
+            new.identifier.id.as_ref(),
+        );
+        self.text(
+            child(&identifier, "RegisteredName"),
+            old.identifier.registered_name.as_deref(),
+            new.identifier.registered_name.as_deref(),
+        );
+        self.value(
+            child(path, "Type"),
+            Some(&old.affiliation_type),
+            Some(&new.affiliation_type),
+        );
+
+        let list_data = child(path, "ListData");
+        let start = self.changes.len();
+        let (old_data, new_data) = (&old.list_data, &new.list_data);
+        self.value(
+            child(&list_data, "@PublishGender"),
+            Some(&old_data.publish_gender),
+            Some(&new_data.publish_gender),
+        );
+        self.value(
+            child(&list_data, "@PublicationLanguage"),
+            old_data.publication_language.as_ref(),
+            new_data.publication_language.as_ref(),
+        );
+        self.value(
+            child(&list_data, "@BelongsToSet"),
+            old_data.belongs_to_set.as_ref(),
+            new_data.belongs_to_set.as_ref(),
+        );
+        self.value(
+            child(&list_data, "@BelongsToCombination"),
+            old_data.belongs_to_combination.as_ref(),
+            new_data.belongs_to_combination.as_ref(),
+        );
+        self.fallback(start, &list_data, old_data, new_data);
+
+        self.candidates(path, &old.candidates, &new.candidates);
+    }
+
+    fn candidates(
+        &mut self,
+        path: &str,
+        old: &[CandidateListsCandidate],
+        new: &[CandidateListsCandidate],
+    ) {
+        // Candidates are matched by their name and date of birth first, so
+        // that candidates moving to a different position can be detected.
+        // Remaining candidates are matched by their id, which means their name
+        // or date of birth changed.
+        let mut pairs = Vec::new();
+        let mut old_matched = vec![false; old.len()];
+        let mut new_matched = vec![false; new.len()];
+        for (i, old_candidate) in old.iter().enumerate() {
+            let key = candidate_key(old_candidate);
+            if let Some(j) =
+                (0..new.len()).find(|&j| !new_matched[j] && candidate_key(&new[j]) == key)
+            {
+                old_matched[i] = true;
+                new_matched[j] = true;
+                pairs.push((i, j));
+            }
+        }
+        for (i, old_candidate) in old.iter().enumerate() {
+            if old_matched[i] {
+                continue;
+            }
+            if let Some(j) = (0..new.len())
+                .find(|&j| !new_matched[j] && new[j].identifier.id == old_candidate.identifier.id)
+            {
+                old_matched[i] = true;
+                new_matched[j] = true;
+                pairs.push((i, j));
+            }
+        }
+        pairs.sort_by_key(|&(_, j)| j);
+        let in_order = longest_increasing(&pairs.iter().map(|&(i, _)| i).collect::<Vec<_>>());
+
+        for (i, old_candidate) in old.iter().enumerate() {
+            if !old_matched[i] {
+                self.push(
+                    candidate_path(path, old_candidate),
+                    ChangeKind::CandidateRemoved(format_person_name(
+                        &old_candidate.full_name.person_name,
+                    )),
+                );
+            }
+        }
+        for ((i, j), in_order) in pairs.into_iter().zip(in_order) {
+            self.candidate(path, (i, &old[i]), (j, &new[j]), !in_order);
+        }
+        for (j, new_candidate) in new.iter().enumerate() {
+            if !new_matched[j] {
+                self.push(
+                    candidate_path(path, new_candidate),
+                    ChangeKind::CandidateAdded(format_person_name(
+                        &new_candidate.full_name.person_name,
+                    )),
+                );
+            }
+        }
+    }
+
+    fn candidate(
+        &mut self,
+        path: &str,
+        (old_index, old): (usize, &CandidateListsCandidate),
+        (new_index, new): (usize, &CandidateListsCandidate),
+        moved: bool,
+    ) {
+        let path = candidate_path(path, new);
+        let old_name = format_person_name(&old.full_name.person_name);
+        let new_name = format_person_name(&new.full_name.person_name);
+        if moved {
+            self.push(
+                path.clone(),
+                ChangeKind::CandidateMoved {
+                    name: new_name.clone(),
+                    old_position: old_index + 1,
+                    new_position: new_index + 1,
+                },
+            );
+        }
+        if old_name != new_name {
+            self.push(
+                child(&path, "CandidateFullName"),
+                ChangeKind::NameChanged {
+                    old: old_name,
+                    new: new_name,
+                },
+            );
+        } else {
+            self.element(
+                &child(&path, "CandidateFullName"),
+                &old.full_name,
+                &new.full_name,
+            );
+        }
+
+        let identifier = child(&path, "CandidateIdentifier");
+        self.value(
+            child(&identifier, "@Id"),
+            Some(&old.identifier.id),
+            Some(&new.identifier.id),
+        );
+        self.value(
+            child(&identifier, "@DisplayOrder"),
+            old.identifier.display_order.as_ref(),
+            new.identifier.display_order.as_ref(),
+        );
+        self.value(
+            child(&identifier, "@ShortCode"),
+            old.identifier.short_code.as_ref(),
+            new.identifier.short_code.as_ref(),
+        );
+        self.text(
+            child(&identifier, "@ExpectedConfirmationReference"),
+            old.identifier.expected_confirmation_reference.as_deref(),
+            new.identifier.expected_confirmation_reference.as_deref(),
+        );
+        self.value(
+            child(&path, "DateOfBirth"),
+            old.date_of_birth.as_ref(),
+            new.date_of_birth.as_ref(),
+        );
+        self.value(
+            child(&path, "Gender"),
+            old.gender.as_ref(),
+            new.gender.as_ref(),
+        );
+        self.element(
+            &child(&path, "QualifyingAddress"),
+            &old.qualifying_address,
+            &new.qualifying_address,
+        );
+    }
+}
+
+/// Mark the values that are part of a longest strictly increasing subsequence.
+///
+/// Candidates whose old positions form this subsequence kept their relative
+/// order, so only the other candidates are reported as moved. This way
+/// adding or removing a candidate does not move every candidate after it.
+fn longest_increasing(values: &[usize]) -> Vec<bool> {
+    // `tails[k]` is the index of the smallest value ending an increasing
+    // subsequence of length `k + 1`, `previous` links each value to the value
+    // before it in its subsequence.
+    let mut tails: Vec<usize> = Vec::new();
+    let mut previous = vec![None; values.len()];
+    for (index, &value) in values.iter().enumerate() {
+        let length = tails.partition_point(|&t| values[t] < value);
+        previous[index] = length.checked_sub(1).map(|k| tails[k]);
+        if length == tails.len() {
+            tails.push(index);
+        } else {
+            tails[length] = index;
+        }
+    }
+
+    let mut result = vec![false; values.len()];
+    let mut current = tails.last().copied();
+    while let Some(index) = current {
+        result[index] = true;
+        current = previous[index];
+    }
+    result
+}
+
+/// Affiliations are identified by their id, or by their name if they have no id.
+fn affiliation_key(affiliation: &CandidateListsAffiliation) -> (&'static str, String) {
+    match (
+        &affiliation.identifier.id,
+        &affiliation.identifier.registered_name,
+    ) {
+        (Some(id), _) => ("Id", id.raw().into_owned()),
+        (None, Some(name)) => ("RegisteredName", name.clone()),
+        (None, None) => ("Id", String::new()),
+    }
+}
+
+fn affiliation_name(affiliation: &CandidateListsAffiliation) -> String {
+    affiliation
+        .identifier
+        .registered_name
+        .clone()
+        .unwrap_or_else(|| affiliation_key(affiliation).1)
+}
+
+fn polling_station_id(place: &PollingPlace) -> Cow<'_, str> {
+    place.physical_location.polling_station.id.raw()
+}
+
+fn candidate_key(candidate: &CandidateListsCandidate) -> (&PersonName, Option<String>) {
+    (
+        &candidate.full_name.person_name,
+        candidate
+            .date_of_birth
+            .as_ref()
+            .map(|d| d.raw().into_owned()),
+    )
+}
+
+fn candidate_path(path: &str, candidate: &CandidateListsCandidate) -> String {
+    keyed_child(path, "Candidate", "Id", &candidate.identifier.id.raw())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::io::{EMLParsingMode, EMLRead as _};
+
+    use super::*;
+
+    fn parse(doc: &str) -> EML {
+        EML::parse_eml(doc, EMLParsingMode::Loose)
+            .ok()
+            .expect("Failed to parse EML document")
+    }
+
+    #[test]
+    fn test_diff_equal_documents() {
+        let doc = parse(include_str!(
+            "../test-emls/polling_stations/eml110b_test.eml.xml"
+        ));
+        assert_eq!(diff(&doc, &doc), Ok(vec![]));
+    }
+
+    #[test]
+    fn test_diff_document_type_mismatch() {
+        let old = parse(include_str!(
+            "../test-emls/polling_stations/eml110b_test.eml.xml"
+        ));
+        let new = parse(include_str!(
+            "../test-emls/candidate_list/eml230b_test.eml.xml"
+        ));
+        assert_eq!(
+            diff(&old, &new),
+            Err(DiffError::DocumentTypeMismatch(
+                "110b".to_string(),
+                "230b".to_string()
+            ))
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_diff_serde() {
+        let change = Change {
+            path: "EML/ManagingAuthority".to_string(),
+            kind: ChangeKind::ValueChanged {
+                old: Some("0000".to_string()),
+                new: None,
+            },
+        };
+        let json = serde_json::to_string(&change).expect("Failed to serialize");
+        let deserialized: Change = serde_json::from_str(&json).expect("Failed to deserialize");
+        assert_eq!(deserialized, change);
+
+        let error = DiffError::DocumentTypeMismatch("110b".to_string(), "230b".to_string());
+        let json = serde_json::to_string(&error).expect("Failed to serialize");
+        let deserialized: DiffError = serde_json::from_str(&json).expect("Failed to deserialize");
+        assert_eq!(deserialized, error);
+    }
+
+    #[test]
+    fn test_diff_polling_stations() {
+        let doc = include_str!("../test-emls/polling_stations/eml110b_test.eml.xml");
+        let old = PollingStations::parse_eml(doc, EMLParsingMode::Loose)
+            .ok()
+            .expect("Failed to parse EML document");
+        let mut new = old.clone();
+        let places = &mut new.election_event.election.contest.polling_places;
+        let removed = places.remove(0);
+        places[0]
+            .physical_location
+            .address
+            .locality
+            .locality_name
+            .name = "Elders".to_string();
+        let mut added = removed.clone();
+        added.physical_location.polling_station.id = StringValue::from_raw("999");
+        places.push(added);
+
+        let removed_id = removed
+            .physical_location
+            .polling_station
+            .id
+            .raw()
+            .into_owned();
+        let modified_id = places[0]
+            .physical_location
+            .polling_station
+            .id
+            .raw()
+            .into_owned();
+        let changes = diff_polling_stations(&old, &new);
+        let kinds: Vec<_> = changes.iter().map(|c| &c.kind).collect();
+        assert!(kinds.contains(&&ChangeKind::PollingStationRemoved(removed_id.to_string())));
+        assert!(kinds.contains(&&ChangeKind::PollingStationModified(
+            modified_id.to_string()
+        )));
+        assert!(kinds.contains(&&ChangeKind::PollingStationAdded("999".to_string())));
+        assert!(changes.iter().any(|c| c.path.ends_with("Locality/LocalityName")
+            && matches!(&c.kind, ChangeKind::ValueChanged { new: Some(n), .. } if n == "Elders")));
+    }
+
+    #[test]
+    fn test_diff_candidate_lists() {
+        let doc = include_str!("../test-emls/candidate_list/eml230b_test.eml.xml");
+        let old = CandidateLists::parse_eml(doc, EMLParsingMode::Loose)
+            .ok()
+            .expect("Failed to parse EML document");
+        let mut new = old.clone();
+        let affiliation = &mut new.candidate_list.election.contest.affiliations[0];
+        affiliation.candidates.swap(0, 1);
+        affiliation.candidates[1]
+            .full_name
+            .person_name
+            .last_name
+            .value = "Anders".to_string();
+        let name = format_person_name(&affiliation.candidates[0].full_name.person_name);
+
+        let changes = diff_candidate_lists(&old, &new);
+        assert!(changes.iter().any(|c| c.kind
+            == ChangeKind::CandidateMoved {
+                name: name.clone(),
+                old_position: 2,
+                new_position: 1,
+            }));
+        assert!(changes.iter().any(|c| matches!(
+            &c.kind,
+            ChangeKind::NameChanged { new, .. } if new.ends_with("Anders")
+        )));
+
+        let mut removed = old.clone();
+        let affiliation = &mut removed.candidate_list.election.contest.affiliations[0];
+        let name = format_person_name(&affiliation.candidates[0].full_name.person_name);
+        affiliation.candidates.remove(0);
+        let changes = diff_candidate_lists(&old, &removed);
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].kind, ChangeKind::CandidateRemoved(name));
+
+        new.candidate_list.election.contest.affiliations.remove(0);
+        let changes = diff_candidate_lists(&old, &new);
+        assert!(
+            changes
+                .iter()
+                .any(|c| matches!(c.kind, ChangeKind::AffiliationRemoved(_)))
+        );
+    }
+}
@@ -1,30 +1,32 @@
 //! Document variant for the EML_NL Candidate List (`230b`) document.
 
-use std::borrow::Cow;
+use std::{borrow::Cow, collections::HashSet};
 
 use crate::{
     EML_SCHEMA_VERSION, EMLError, NS_EML, NS_KR, NS_XAL,
     common::{
         AffiliationIdentifier, CandidateIdentifier, CanonicalizationMethod, ContestIdentifier,
         CreationDateTime, ElectionDomain, IssueDate, ListData, ManagingAuthority,
-        PersonNameStructure, TransactionId,
+        PersonNameStructure, Signature, TransactionId,
     },
     documents::accepted_root,
     error::{EMLErrorKind, EMLResultExt},
     io::{
-        EMLElement, EMLElementReader, EMLElementWriter, EMLReadElement as _, QualifiedName,
-        collect_struct, write_eml_element,
+        EMLElement, EMLElementReader, EMLElementWriter, EMLReadElement as _, Extensions,
+        QualifiedName, collect_struct, write_eml_element,
     },
     utils::{
         AffiliationType, ElectionCategory, ElectionIdType, ElectionSubcategory, GenderType,
         StringValue, XsDate, XsDateOrDateTime,
     },
+    validation::{ValidationFinding, parsed_value},
 };
 
 pub(crate) const EML_CANDIDATE_LISTS_ID: &str = "230b";
 
 /// Representing a `230b` document, containing the candidate lists.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CandidateLists {
     /// Transaction id of the document.
     pub transaction_id: TransactionId,
@@ -43,6 +45,126 @@ pub struct CandidateLists {
 
     /// The candidate lists contained in this document.
     pub candidate_list: CandidateListsCandidateList,
+
+    /// Enveloped signature of this document, if present.
+    pub signature: Option<Signature>,
+
+    /// Unknown attributes and child elements, see [`Extensions`].
+    pub extensions: Extensions,
+}
+
+impl CandidateLists {
+    /// Validate these candidate lists against the rules of the Kiesraad.
+    ///
+    /// The following rules are checked:
+    ///
+    /// * There must be at least one affiliation, and affiliation ids must be unique.
+    /// * Every affiliation must contain at least one candidate, and no more
+    ///   than allowed for the election category.
+    /// * Candidate ids must be unique within an affiliation and numbered
+    ///   sequentially starting at 1.
+    /// * `BelongsToSet` may only be used for a set of equal lists, and
+    ///   `BelongsToCombination` only for a group of lists.
+    pub fn validate(&self) -> Vec<ValidationFinding> {
+        let mut findings = Vec::new();
+        let election = &self.candidate_list.election;
+        let affiliations = &election.contest.affiliations;
+        let max_candidates = parsed_value(
+            &election.identifier.category,
+            "ElectionCategory",
+            &mut findings,
+        )
+        .map(max_candidates_per_list);
+
+        if affiliations.is_empty() {
+            findings.push(ValidationFinding::NoAffiliations);
+        }
+
+        let mut affiliation_ids = HashSet::new();
+        for affiliation in affiliations {
+            let label = affiliation.label();
+            if let Some(id) = &affiliation.identifier.id
+                && !affiliation_ids.insert(id.raw())
+            {
+                findings.push(ValidationFinding::DuplicateAffiliationId(
+                    id.raw().into_owned(),
+                ));
+            }
+
+            if affiliation.candidates.is_empty() {
+                findings.push(ValidationFinding::AffiliationWithoutCandidates(
+                    label.clone(),
+                ));
+            }
+            if let Some(max_candidates) = max_candidates
+                && affiliation.candidates.len() > max_candidates
+            {
+                findings.push(ValidationFinding::TooManyCandidates(
+                    label.clone(),
+                    affiliation.candidates.len(),
+                    max_candidates,
+                ));
+            }
+
+            let mut candidate_ids = HashSet::new();
+            for (expected, candidate) in (1u64..).zip(&affiliation.candidates) {
+                let id = candidate.identifier.id.raw();
+                if !candidate_ids.insert(id.clone()) {
+                    findings.push(ValidationFinding::DuplicateCandidateId(
+                        label.clone(),
+                        id.clone().into_owned(),
+                    ));
+                }
+                if id.parse::<u64>().ok() != Some(expected) {
+                    findings.push(ValidationFinding::CandidateIdOutOfSequence(
+                        label.clone(),
+                        expected,
+                        id.into_owned(),
+                    ));
+                }
+            }
+
+            let affiliation_type =
+                parsed_value(&affiliation.affiliation_type, "Type", &mut findings);
+            if let Some(affiliation_type) = affiliation_type {
+                let list_data = &affiliation.list_data;
+                if list_data.belongs_to_set.is_some()
+                    && affiliation_type != AffiliationType::SetOfEqualLists
+                {
+                    findings.push(ValidationFinding::ListDataTypeMismatch(
+                        label.clone(),
+                        "BelongsToSet".to_string(),
+                        affiliation_type.to_str_value().to_string(),
+                    ));
+                }
+                if list_data.belongs_to_combination.is_some()
+                    && affiliation_type != AffiliationType::GroupOfLists
+                {
+                    findings.push(ValidationFinding::ListDataTypeMismatch(
+                        label,
+                        "BelongsToCombination".to_string(),
+                        affiliation_type.to_str_value().to_string(),
+                    ));
+                }
+            }
+        }
+
+        findings
+    }
+}
+
+/// Maximum number of candidates on a single list for an election category.
+///
+/// For council elections the maximum depends on the number of seats. Candidate
+/// lists do not contain the number of seats, so the maximum for the largest
+/// council of the category is used instead.
+fn max_candidates_per_list(category: ElectionCategory) -> usize {
+    match category {
+        ElectionCategory::TK | ElectionCategory::EP => 80,
+        ElectionCategory::PS => 65,
+        ElectionCategory::GR => 55,
+        _ => 50,
+    }
 }
 
 impl EMLElement for CandidateLists {
@@ -51,13 +173,13 @@ impl EMLElement for CandidateLists {
     fn read_eml(elem: &mut EMLElementReader<'_, '_>) -> Result<Self, EMLError> {
         accepted_root(elem)?;
 
-        let document_id = elem.attribute_value_req(("Id", None))?;
+        let (document_id, id_span) = elem.attribute_value_req_with_span(("Id", None))?;
         if document_id != EML_CANDIDATE_LISTS_ID {
             return Err(EMLErrorKind::InvalidDocumentType(
                 EML_CANDIDATE_LISTS_ID,
                 document_id.to_string(),
             ))
-            .with_span(elem.span());
+            .with_span(id_span);
         }
 
         Ok(collect_struct!(elem, CandidateLists {
@@ -67,23 +189,23 @@ impl EMLElement for CandidateLists {
             creation_date_time: CreationDateTime::EML_NAME => |elem| CreationDateTime::read_eml(elem)?,
             canonicalization_method as Option: CanonicalizationMethod::EML_NAME => |elem| CanonicalizationMethod::read_eml(elem)?,
             candidate_list: CandidateListsCandidateList::EML_NAME => |elem| CandidateListsCandidateList::read_eml(elem)?,
+            signature as Option: Signature::EML_NAME => |elem| Signature::read_eml(elem)?,
+            extensions as Extensions,
         }))
     }
 
     fn write_eml(&self, writer: EMLElementWriter) -> Result<(), EMLError> {
         writer
+            .extensions(&self.extensions)?
             .attr(("Id", None), EML_CANDIDATE_LISTS_ID)?
             .attr(("SchemaVersion", None), EML_SCHEMA_VERSION)?
             .child_elem(TransactionId::EML_NAME, &self.transaction_id)?
             .child_elem(ManagingAuthority::EML_NAME, &self.managing_authority)?
             .child_elem(IssueDate::EML_NAME, &self.issue_date)?
             .child_elem(CreationDateTime::EML_NAME, &self.creation_date_time)?
-            // Note: we don't output the CanonicalizationMethod because we aren't canonicalizing our output
-            // .child_elem_option(
-            //     CanonicalizationMethod::EML_NAME,
-            //     self.canonicalization_method.as_ref(),
-            // )?
+            .canonicalization_method(self.canonicalization_method.is_some())?
             .child_elem(CandidateListsCandidateList::EML_NAME, &self.candidate_list)?
+            .child_elem_option(Signature::EML_NAME, self.signature.as_ref())?
             .finish()?;
 
         Ok(())
@@ -91,12 +213,15 @@ impl EMLElement for CandidateLists {
 }
 
 /// The root candidate list element.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CandidateListsCandidateList {
     /// The date of the candidate list, if present.
     pub list_date: Option<CandidateListsListDate>,
     /// The election information.
     pub election: CandidateListsElection,
+    /// Unknown attributes and child elements, see [`Extensions`].
+    pub extensions: Extensions,
 }
 
 impl EMLElement for CandidateListsCandidateList {
@@ -107,11 +232,13 @@ impl EMLElement for CandidateListsCandidateList {
         Ok(collect_struct!(elem, CandidateListsCandidateList {
             list_date as Option: CandidateListsListDate::EML_NAME => |elem| CandidateListsListDate::read_eml(elem)?,
             election: CandidateListsElection::EML_NAME => |elem| CandidateListsElection::read_eml(elem)?,
+            extensions as Extensions,
         }))
     }
 
     fn write_eml(&self, writer: EMLElementWriter) -> Result<(), EMLError> {
         writer
+            .extensions(&self.extensions)?
             .child_elem_option(CandidateListsListDate::EML_NAME, self.list_date.as_ref())?
             .child_elem(CandidateListsElection::EML_NAME, &self.election)?
             .finish()
@@ -119,7 +246,8 @@ impl EMLElement for CandidateListsCandidateList {
 }
 
 /// The date of the candidate list.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CandidateListsListDate(pub StringValue<XsDateOrDateTime>);
 
 impl EMLElement for CandidateListsListDate {
@@ -136,12 +264,15 @@ impl EMLElement for CandidateListsListDate {
 }
 
 /// The election information in the candidate lists.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CandidateListsElection {
     /// Identifier for the election.
     pub identifier: CandidateListsElectionIdentifier,
     /// Election contest details.
     pub contest: CandidateListsContest,
+    /// Unknown attributes and child elements, see [`Extensions`].
+    pub extensions: Extensions,
 }
 
 impl EMLElement for CandidateListsElection {
@@ -151,11 +282,13 @@ impl EMLElement for CandidateListsElection {
         Ok(collect_struct!(elem, CandidateListsElection {
             identifier: CandidateListsElectionIdentifier::EML_NAME => |elem| CandidateListsElectionIdentifier::read_eml(elem)?,
             contest: CandidateListsContest::EML_NAME => |elem| CandidateListsContest::read_eml(elem)?,
+            extensions as Extensions,
         }))
     }
 
     fn write_eml(&self, writer: EMLElementWriter) -> Result<(), EMLError> {
         writer
+            .extensions(&self.extensions)?
             .child_elem(CandidateListsElectionIdentifier::EML_NAME, &self.identifier)?
             .child_elem(CandidateListsContest::EML_NAME, &self.contest)?
             .finish()
@@ -163,7 +296,8 @@ impl EMLElement for CandidateListsElection {
 }
 
 /// Identifier for the election.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CandidateListsElectionIdentifier {
     /// Id of the election
     pub id: StringValue<ElectionIdType>,
@@ -179,6 +313,8 @@ pub struct CandidateListsElectionIdentifier {
     pub election_date: StringValue<XsDate>,
     /// Nomination date for the election
     pub nomination_date: StringValue<XsDate>,
+    /// Unknown attributes and child elements, see [`Extensions`].
+    pub extensions: Extensions,
 }
 
 impl EMLElement for CandidateListsElectionIdentifier {
@@ -196,12 +332,14 @@ impl EMLElement for CandidateListsElectionIdentifier {
                 domain as Option: ElectionDomain::EML_NAME => |elem| ElectionDomain::read_eml(elem)?,
                 election_date: ("ElectionDate", NS_KR) => |elem| elem.string_value()?,
                 nomination_date: ("NominationDate", NS_KR) => |elem| elem.string_value()?,
+                extensions as Extensions,
             }
         ))
     }
 
     fn write_eml(&self, writer: EMLElementWriter) -> Result<(), EMLError> {
         writer
+            .extensions(&self.extensions)?
             .attr("Id", self.id.raw().as_ref())?
             .child_option(
                 ("ElectionName", NS_EML),
@@ -228,12 +366,15 @@ impl EMLElement for CandidateListsElectionIdentifier {
 }
 
 /// Election contest details.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CandidateListsContest {
     /// Identifier for the contest.
     pub identifier: ContestIdentifier,
     /// Affiliations participating in the contest.
     pub affiliations: Vec<CandidateListsAffiliation>,
+    /// Unknown attributes and child elements, see [`Extensions`].
+    pub extensions: Extensions,
 }
 
 impl EMLElement for CandidateListsContest {
@@ -243,11 +384,14 @@ impl EMLElement for CandidateListsContest {
         Ok(collect_struct!(elem, CandidateListsContest {
             identifier: ContestIdentifier::EML_NAME => |elem| ContestIdentifier::read_eml(elem)?,
             affiliations as Vec: CandidateListsAffiliation::EML_NAME => |elem| CandidateListsAffiliation::read_eml(elem)?,
+            extensions as Extensions,
         }))
     }
 
     fn write_eml(&self, writer: EMLElementWriter) -> Result<(), EMLError> {
-        let mut writer = writer.child_elem(ContestIdentifier::EML_NAME, &self.identifier)?;
+        let mut writer = writer
+            .extensions(&self.extensions)?
+            .child_elem(ContestIdentifier::EML_NAME, &self.identifier)?;
         for affiliation in &self.affiliations {
             writer = writer.child_elem(CandidateListsAffiliation::EML_NAME, affiliation)?;
         }
@@ -256,7 +400,8 @@ impl EMLElement for CandidateListsContest {
 }
 
 /// An affiliation participating in the contest.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CandidateListsAffiliation {
     /// The affiliation identifier.
     pub identifier: AffiliationIdentifier,
@@ -269,6 +414,21 @@ pub struct CandidateListsAffiliation {
 
     /// The candidates of the affiliation.
     pub candidates: Vec<CandidateListsCandidate>,
+
+    /// Unknown attributes and child elements, see [`Extensions`].
+    pub extensions: Extensions,
+}
+
+impl CandidateListsAffiliation {
+    /// Label used to refer to this affiliation, which is its id if present
+    /// and otherwise its registered name.
+    fn label(&self) -> String {
+        match (&self.identifier.id, &self.identifier.registered_name) {
+            (Some(id), _) => id.raw().into_owned(),
+            (None, Some(name)) => name.clone(),
+            (None, None) => String::new(),
+        }
+    }
 }
 
 impl EMLElement for CandidateListsAffiliation {
@@ -280,11 +440,13 @@ impl EMLElement for CandidateListsAffiliation {
             affiliation_type: ("Type", NS_EML) => |elem| elem.string_value()?,
             list_data: ListData::EML_NAME => |elem| ListData::read_eml(elem)?,
             candidates as Vec: CandidateListsCandidate::EML_NAME => |elem| CandidateListsCandidate::read_eml(elem)?,
+            extensions as Extensions,
         }))
     }
 
     fn write_eml(&self, writer: EMLElementWriter) -> Result<(), EMLError> {
         let mut writer = writer
+            .extensions(&self.extensions)?
             .child_elem(AffiliationIdentifier::EML_NAME, &self.identifier)?
             .child(("Type", NS_EML), |elem| {
                 elem.text(self.affiliation_type.raw().as_ref())?.finish()
@@ -299,7 +461,8 @@ impl EMLElement for CandidateListsAffiliation {
 }
 
 /// A candidate in an affiliation.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CandidateListsCandidate {
     /// The candidate identifier.
     pub identifier: CandidateIdentifier,
@@ -315,6 +478,9 @@ pub struct CandidateListsCandidate {
 
     /// The qualifying address of the candidate.
     pub qualifying_address: QualifyingAddress,
+
+    /// Unknown attributes and child elements, see [`Extensions`].
+    pub extensions: Extensions,
 }
 
 impl EMLElement for CandidateListsCandidate {
@@ -327,11 +493,13 @@ impl EMLElement for CandidateListsCandidate {
             date_of_birth as Option: ("DateOfBirth", NS_EML) => |elem| elem.string_value()?,
             gender as Option: ("Gender", NS_EML) => |elem| elem.string_value()?,
             qualifying_address: QualifyingAddress::EML_NAME => |elem| QualifyingAddress::read_eml(elem)?,
+            extensions as Extensions,
         }))
     }
 
     fn write_eml(&self, writer: EMLElementWriter) -> Result<(), EMLError> {
         writer
+            .extensions(&self.extensions)?
             .child_elem(CandidateIdentifier::EML_NAME, &self.identifier)?
             .child(
                 ("CandidateFullName", NS_EML),
@@ -351,7 +519,8 @@ impl EMLElement for CandidateListsCandidate {
 }
 
 /// The qualifying address of a candidate.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum QualifyingAddress {
     /// Qualifying address is a locality only.
     Locality(QualifyingAddressLocality),
@@ -367,6 +536,7 @@ impl EMLElement for QualifyingAddress {
         let parent_name = elem.name()?.as_owned();
         let mut found_value = None;
         while let Some(mut next_child) = elem.next_child()? {
+            next_child.drop_annotations(&parent_name);
             let name = next_child.name()?;
             if found_value.is_some()
                 || name != QualifyingAddressLocality::EML_NAME
@@ -394,6 +564,7 @@ impl EMLElement for QualifyingAddress {
                 }
             }
         }
+        elem.drop_annotations(&parent_name);
         let Some(value) = found_value else {
             return Err(EMLErrorKind::MissingChoiceElements(vec![
                 QualifyingAddressLocality::EML_NAME.as_owned(),
@@ -418,7 +589,8 @@ impl EMLElement for QualifyingAddress {
 }
 
 /// Qualifying address locality.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct QualifyingAddressLocality {
     /// The address line, if present.
     pub address_line: Option<AddressLine>,
@@ -432,6 +604,8 @@ pub struct QualifyingAddressLocality {
     pub usage_type: Option<String>,
     /// The Indicator attribute, if present.
     pub indicator: Option<String>,
+    /// Unknown attributes and child elements, see [`Extensions`].
+    pub extensions: Extensions,
 }
 
 impl QualifyingAddressLocality {
@@ -444,6 +618,7 @@ impl QualifyingAddressLocality {
             locality_type: None,
             usage_type: None,
             indicator: None,
+            extensions: Extensions::default(),
         }
     }
 }
@@ -459,11 +634,13 @@ impl EMLElement for QualifyingAddressLocality {
             locality_type: elem.attribute_value("Type")?.map(Cow::into_owned),
             usage_type: elem.attribute_value("UsageType")?.map(Cow::into_owned),
             indicator: elem.attribute_value("Indicator")?.map(Cow::into_owned),
+            extensions as Extensions,
         }))
     }
 
     fn write_eml(&self, writer: EMLElementWriter) -> Result<(), EMLError> {
         writer
+            .extensions(&self.extensions)?
             .attr_opt("Type", self.locality_type.as_ref())?
             .attr_opt("UsageType", self.usage_type.as_ref())?
             .attr_opt("Indicator", self.indicator.as_ref())?
@@ -475,7 +652,8 @@ impl EMLElement for QualifyingAddressLocality {
 }
 
 /// Address line information.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AddressLine {
     /// The address line value.
     pub value: String,
@@ -517,7 +695,8 @@ impl EMLElement for AddressLine {
 }
 
 /// The locality name.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LocalityName {
     /// The locality name.
     pub value: String,
@@ -560,10 +739,13 @@ impl EMLElement for LocalityName {
 }
 
 /// Postal code information.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PostalCode {
     /// Number of the postal code.
     pub postal_code_number: PostalCodeNumber,
+    /// Unknown attributes and child elements, see [`Extensions`].
+    pub extensions: Extensions,
 }
 
 impl PostalCode {
@@ -571,6 +753,7 @@ impl PostalCode {
     pub fn new(postal_code_number: impl Into<String>) -> Self {
         PostalCode {
             postal_code_number: PostalCodeNumber::new(postal_code_number),
+            extensions: Extensions::default(),
         }
     }
 }
@@ -581,18 +764,21 @@ impl EMLElement for PostalCode {
     fn read_eml(elem: &mut EMLElementReader<'_, '_>) -> Result<Self, EMLError> {
         Ok(collect_struct!(elem, PostalCode {
             postal_code_number: PostalCodeNumber::EML_NAME => |elem| PostalCodeNumber::read_eml(elem)?,
+            extensions as Extensions,
         }))
     }
 
     fn write_eml(&self, writer: EMLElementWriter) -> Result<(), EMLError> {
         writer
+            .extensions(&self.extensions)?
             .child_elem(PostalCodeNumber::EML_NAME, &self.postal_code_number)?
             .finish()
     }
 }
 
 /// The postal code number.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PostalCodeNumber {
     /// The postal code number value.
     pub value: String,
@@ -635,12 +821,15 @@ impl PostalCodeNumber {
 }
 
 /// Qualifying address country.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct QualifyingAddressCountry {
     /// The country name code, if present.
     pub country_name_code: Option<CountryNameCode>,
     /// The locality within the country.
     pub locality: QualifyingAddressLocality,
+    /// Unknown attributes and child elements, see [`Extensions`].
+    pub extensions: Extensions,
 }
 
 impl QualifyingAddressCountry {
@@ -652,6 +841,7 @@ impl QualifyingAddressCountry {
         Self {
             country_name_code: country_code.map(|code| CountryNameCode::new(code)),
             locality,
+            extensions: Extensions::default(),
         }
     }
 }
@@ -663,11 +853,13 @@ impl EMLElement for QualifyingAddressCountry {
         Ok(collect_struct!(elem, QualifyingAddressCountry {
             country_name_code as Option: CountryNameCode::EML_NAME => |elem| CountryNameCode::read_eml(elem)?,
             locality: QualifyingAddressLocality::EML_NAME => |elem| QualifyingAddressLocality::read_eml(elem)?,
+            extensions as Extensions,
         }))
     }
 
     fn write_eml(&self, writer: EMLElementWriter) -> Result<(), EMLError> {
         writer
+            .extensions(&self.extensions)?
             .child_elem_option(CountryNameCode::EML_NAME, self.country_name_code.as_ref())?
             .child_elem(QualifyingAddressLocality::EML_NAME, &self.locality)?
             .finish()
@@ -675,7 +867,8 @@ impl EMLElement for QualifyingAddressCountry {
 }
 
 /// Country name code information.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CountryNameCode {
     /// The country name code value.
     pub value: String,
@@ -716,3 +909,119 @@ impl EMLElement for CountryNameCode {
             .finish()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::io::{EMLParsingMode, EMLRead as _};
+
+    use super::*;
+
+    fn parse(doc: &str) -> CandidateLists {
+        CandidateLists::parse_eml(doc, EMLParsingMode::Strict)
+            .ok()
+            .expect("Failed to parse EML document")
+    }
+
+    #[test]
+    fn test_validate_candidate_lists() {
+        let doc = include_str!("../../test-emls/candidate_list/eml230b_test.eml.xml");
+        assert_eq!(parse(doc).validate(), vec![]);
+
+        let doc =
+            include_str!("../../test-emls/candidate_list/eml230b_invalid_empty_affiliates.eml.xml");
+        assert_eq!(
+            parse(doc).validate(),
+            vec![ValidationFinding::NoAffiliations]
+        );
+
+        let doc =
+            include_str!("../../test-emls/candidate_list/eml230b_invalid_empty_candidates.eml.xml");
+        assert!(
+            parse(doc)
+                .validate()
+                .contains(&ValidationFinding::AffiliationWithoutCandidates(
+                    "3".to_string()
+                ))
+        );
+    }
+
+    #[test]
+    fn test_validate_candidate_numbering() {
+        let doc = include_str!("../../test-emls/candidate_list/eml230b_test.eml.xml")
+            .replacen(
+                r#"<AffiliationIdentifier Id="2">"#,
+                r#"<AffiliationIdentifier Id="1">"#,
+                1,
+            )
+            .replacen(
+                r#"<CandidateIdentifier Id="2"/>"#,
+                r#"<CandidateIdentifier Id="1"/>"#,
+                1,
+            )
+            .replacen(
+                r#"<kr:ListData PublicationLanguage="nl" PublishGender="true"/>"#,
+                r#"<kr:ListData PublicationLanguage="nl" PublishGender="true" BelongsToSet="1"/>"#,
+                1,
+            );
+        let findings = parse(&doc).validate();
+        assert!(findings.contains(&ValidationFinding::DuplicateAffiliationId("1".to_string())));
+        assert!(findings.contains(&ValidationFinding::DuplicateCandidateId(
+            "1".to_string(),
+            "1".to_string()
+        )));
+        assert!(
+            findings.contains(&ValidationFinding::CandidateIdOutOfSequence(
+                "1".to_string(),
+                2,
+                "1".to_string()
+            ))
+        );
+        assert!(findings.contains(&ValidationFinding::ListDataTypeMismatch(
+            "1".to_string(),
+            "BelongsToSet".to_string(),
+            "op zichzelf staande lijst".to_string()
+        )));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_candidate_lists_serde() {
+        let doc = include_str!("../../test-emls/candidate_list/eml230b_test.eml.xml");
+        let candidate_lists = parse(doc);
+
+        let json = serde_json::to_value(&candidate_lists).expect("Failed to serialize");
+        let election = &json["candidate_list"]["election"];
+        assert_eq!(election["identifier"]["category"], "GR");
+        assert_eq!(
+            election["contest"]["affiliations"][0]["affiliation_type"],
+            "op zichzelf staande lijst"
+        );
+
+        let deserialized: CandidateLists =
+            serde_json::from_value(json).expect("Failed to deserialize");
+        assert_eq!(deserialized, candidate_lists);
+
+        assert_eq!(
+            serde_json::to_string(&AffiliationType::GroupOfLists).expect("Failed to serialize"),
+            r#""lijstengroep""#
+        );
+        assert!(serde_json::from_str::<ElectionCategory>(r#""XX""#).is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_validation_findings_serde() {
+        let doc = include_str!("../../test-emls/candidate_list/eml230b_test.eml.xml").replacen(
+            r#"<kr:ListData PublicationLanguage="nl" PublishGender="true"/>"#,
+            r#"<kr:ListData PublicationLanguage="nl" PublishGender="true" BelongsToSet="1"/>"#,
+            1,
+        );
+        let findings = parse(&doc).validate();
+        assert!(!findings.is_empty());
+
+        let json = serde_json::to_string(&findings).expect("Failed to serialize");
+        let deserialized: Vec<ValidationFinding> =
+            serde_json::from_str(&json).expect("Failed to deserialize");
+        assert_eq!(deserialized, findings);
+    }
+}
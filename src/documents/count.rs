@@ -0,0 +1,844 @@
+//! Document variants for the EML_NL Count (`510a`, `510b`, `510c` and `510d`) documents.
+
+use crate::{
+    EML_SCHEMA_VERSION, EMLError, NS_EML, NS_KR,
+    common::{
+        AffiliationIdentifier, CanonicalizationMethod, ContestIdentifier, CreationDateTime,
+        ElectionDomain, IssueDate, ManagingAuthority, ReportingUnitIdentifier, Signature,
+        TransactionId,
+    },
+    documents::{accepted_root, totals::EML_TOTALS_ID},
+    error::{EMLErrorKind, EMLResultExt},
+    io::{
+        EMLElement, EMLElementReader, EMLElementWriter, Extensions, QualifiedName, collect_struct,
+    },
+    utils::{
+        CandidateIdType, ElectionCategory, ElectionIdType, ElectionSubcategory,
+        RejectedVotesReason, StringValue, XsDate,
+    },
+};
+
+pub(crate) const EML_POLLING_STATION_COUNT_ID: &str = "510a";
+pub(crate) const EML_COUNT_ID: &str = "510b";
+pub(crate) const EML_PRINCIPAL_COMMITTEE_COUNT_ID: &str = "510c";
+
+/// The level at which a count was performed.
+///
+/// All four `510` document variants share the same content model, they only
+/// differ in which reporting units are present.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CountLevel {
+    /// Count of a single polling station (`510a`)
+    PollingStation,
+    /// Count of a municipality (`510b`)
+    Municipality,
+    /// Count of a principal electoral committee, or hoofdstembureau (`510c`)
+    PrincipalCommittee,
+    /// Count of the central electoral committee, or centraal stembureau (`510d`)
+    CentralCommittee,
+}
+
+impl CountLevel {
+    /// Create a CountLevel from an EML document id, if possible.
+    pub fn from_eml_id(s: &str) -> Option<Self> {
+        match s {
+            EML_POLLING_STATION_COUNT_ID => Some(CountLevel::PollingStation),
+            EML_COUNT_ID => Some(CountLevel::Municipality),
+            EML_PRINCIPAL_COMMITTEE_COUNT_ID => Some(CountLevel::PrincipalCommittee),
+            EML_TOTALS_ID => Some(CountLevel::CentralCommittee),
+            _ => None,
+        }
+    }
+
+    /// Get the EML document id for this CountLevel.
+    pub fn to_eml_id(&self) -> &'static str {
+        match self {
+            CountLevel::PollingStation => EML_POLLING_STATION_COUNT_ID,
+            CountLevel::Municipality => EML_COUNT_ID,
+            CountLevel::PrincipalCommittee => EML_PRINCIPAL_COMMITTEE_COUNT_ID,
+            CountLevel::CentralCommittee => EML_TOTALS_ID,
+        }
+    }
+}
+
+/// Representing a `510a`, `510b`, `510c` or `510d` document, containing a count.
+///
+/// The [`level`](Count::level) determines which of the document variants is
+/// read and written.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Count {
+    /// The level of the count, determining the document id.
+    pub level: CountLevel,
+
+    /// Transaction id of the document.
+    pub transaction_id: TransactionId,
+
+    /// Managing authority of the document.
+    pub managing_authority: ManagingAuthority,
+
+    /// Issue date of the document, if present.
+    pub issue_date: Option<IssueDate>,
+
+    /// Creation date and time of the document.
+    pub creation_date_time: CreationDateTime,
+
+    /// Canonicalization method used in this document, if present.
+    pub canonicalization_method: Option<CanonicalizationMethod>,
+
+    /// The count contained in this document.
+    pub count: CountCount,
+
+    /// Enveloped signature of this document, if present.
+    pub signature: Option<Signature>,
+
+    /// Unknown attributes and child elements, see [`Extensions`].
+    pub extensions: Extensions,
+}
+
+impl EMLElement for Count {
+    const EML_NAME: QualifiedName<'_, '_> = QualifiedName::from_static("EML", Some(NS_EML));
+
+    fn read_eml(elem: &mut EMLElementReader<'_, '_>) -> Result<Self, EMLError> {
+        accepted_root(elem)?;
+
+        let (document_id, id_span) = elem.attribute_value_req_with_span(("Id", None))?;
+        let Some(level) = CountLevel::from_eml_id(document_id.as_ref()) else {
+            return Err(EMLErrorKind::InvalidDocumentType(
+                EML_COUNT_ID,
+                document_id.to_string(),
+            ))
+            .with_span(id_span);
+        };
+
+        Ok(collect_struct!(elem, Count {
+            level: level,
+            transaction_id: TransactionId::EML_NAME => |elem| TransactionId::read_eml(elem)?,
+            managing_authority: ManagingAuthority::EML_NAME => |elem| ManagingAuthority::read_eml(elem)?,
+            issue_date as Option: IssueDate::EML_NAME => |elem| IssueDate::read_eml(elem)?,
+            creation_date_time: CreationDateTime::EML_NAME => |elem| CreationDateTime::read_eml(elem)?,
+            canonicalization_method as Option: CanonicalizationMethod::EML_NAME => |elem| CanonicalizationMethod::read_eml(elem)?,
+            count: CountCount::EML_NAME => |elem| CountCount::read_eml(elem)?,
+            signature as Option: Signature::EML_NAME => |elem| Signature::read_eml(elem)?,
+            extensions as Extensions,
+        }))
+    }
+
+    fn write_eml(&self, writer: EMLElementWriter) -> Result<(), EMLError> {
+        self.write_eml_at_level(self.level, writer)
+    }
+}
+
+impl Count {
+    /// Writes the count as the document variant of the given level, which is
+    /// determined by the [`EML`](crate::documents::EML) variant when the count
+    /// is written as part of it.
+    pub(crate) fn write_eml_at_level(
+        &self,
+        level: CountLevel,
+        writer: EMLElementWriter,
+    ) -> Result<(), EMLError> {
+        writer
+            .extensions(&self.extensions)?
+            .attr(("Id", None), level.to_eml_id())?
+            .attr(("SchemaVersion", None), EML_SCHEMA_VERSION)?
+            .child_elem(TransactionId::EML_NAME, &self.transaction_id)?
+            .child_elem(ManagingAuthority::EML_NAME, &self.managing_authority)?
+            .child_elem_option(IssueDate::EML_NAME, self.issue_date.as_ref())?
+            .child_elem(CreationDateTime::EML_NAME, &self.creation_date_time)?
+            .canonicalization_method(self.canonicalization_method.is_some())?
+            .child_elem(CountCount::EML_NAME, &self.count)?
+            .child_elem_option(Signature::EML_NAME, self.signature.as_ref())?
+            .finish()?;
+
+        Ok(())
+    }
+}
+
+/// The count element, containing the votes for an election.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CountCount {
+    /// Identifier of the event the count belongs to.
+    pub event_identifier: CountEventIdentifier,
+
+    /// The election that was counted.
+    pub election: CountElection,
+
+    /// Unknown attributes and child elements, see [`Extensions`].
+    pub extensions: Extensions,
+}
+
+impl EMLElement for CountCount {
+    const EML_NAME: QualifiedName<'_, '_> = QualifiedName::from_static("Count", Some(NS_EML));
+
+    fn read_eml(elem: &mut EMLElementReader<'_, '_>) -> Result<Self, EMLError> {
+        Ok(collect_struct!(elem, CountCount {
+            event_identifier: CountEventIdentifier::EML_NAME => |elem| CountEventIdentifier::read_eml(elem)?,
+            election: CountElection::EML_NAME => |elem| CountElection::read_eml(elem)?,
+            extensions as Extensions,
+        }))
+    }
+
+    fn write_eml(&self, writer: EMLElementWriter) -> Result<(), EMLError> {
+        writer
+            .extensions(&self.extensions)?
+            .child_elem(CountEventIdentifier::EML_NAME, &self.event_identifier)?
+            .child_elem(CountElection::EML_NAME, &self.election)?
+            .finish()
+    }
+}
+
+/// Identifier for the event of a count.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CountEventIdentifier;
+
+impl EMLElement for CountEventIdentifier {
+    const EML_NAME: QualifiedName<'_, '_> =
+        QualifiedName::from_static("EventIdentifier", Some(NS_EML));
+
+    fn read_eml(elem: &mut EMLElementReader<'_, '_>) -> Result<Self, EMLError> {
+        elem.skip()?;
+        Ok(CountEventIdentifier)
+    }
+
+    fn write_eml(&self, writer: EMLElementWriter) -> Result<(), EMLError> {
+        writer.empty()
+    }
+}
+
+/// The election that was counted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CountElection {
+    /// Identifier of the election.
+    pub identifier: CountElectionIdentifier,
+
+    /// The contests in the election.
+    pub contests: Vec<CountContest>,
+
+    /// Unknown attributes and child elements, see [`Extensions`].
+    pub extensions: Extensions,
+}
+
+impl EMLElement for CountElection {
+    const EML_NAME: QualifiedName<'_, '_> = QualifiedName::from_static("Election", Some(NS_EML));
+
+    fn read_eml(elem: &mut EMLElementReader<'_, '_>) -> Result<Self, EMLError> {
+        Ok(collect_struct!(elem, CountElection {
+            identifier: CountElectionIdentifier::EML_NAME => |elem| CountElectionIdentifier::read_eml(elem)?,
+            contests: ("Contests", NS_EML) => |elem| {
+                // Temporary struct to collect contest elements
+                struct Contests {
+                    contests: Vec<CountContest>,
+                }
+
+                let res = collect_struct!(elem, Contests {
+                    contests as Vec: CountContest::EML_NAME => |elem| CountContest::read_eml(elem)?,
+                });
+
+                res.contests
+            },
+            extensions as Extensions,
+        }))
+    }
+
+    fn write_eml(&self, writer: EMLElementWriter) -> Result<(), EMLError> {
+        writer
+            .extensions(&self.extensions)?
+            .child_elem(CountElectionIdentifier::EML_NAME, &self.identifier)?
+            .child(("Contests", NS_EML), |writer| {
+                let mut writer = writer.content()?;
+                for contest in &self.contests {
+                    writer = writer.child_elem(CountContest::EML_NAME, contest)?;
+                }
+                writer.finish()
+            })?
+            .finish()
+    }
+}
+
+/// Identifier of the election that was counted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CountElectionIdentifier {
+    /// Election id.
+    pub id: StringValue<ElectionIdType>,
+
+    /// Election name, if present.
+    pub name: Option<String>,
+
+    /// Election category.
+    pub category: StringValue<ElectionCategory>,
+
+    /// Election subcategory, if present.
+    pub subcategory: Option<StringValue<ElectionSubcategory>>,
+
+    /// The (top level) region where the election takes place.
+    pub domain: Option<ElectionDomain>,
+
+    /// Date of the election
+    pub election_date: StringValue<XsDate>,
+
+    /// Unknown attributes and child elements, see [`Extensions`].
+    pub extensions: Extensions,
+}
+
+impl EMLElement for CountElectionIdentifier {
+    const EML_NAME: QualifiedName<'_, '_> =
+        QualifiedName::from_static("ElectionIdentifier", Some(NS_EML));
+
+    fn read_eml(elem: &mut EMLElementReader<'_, '_>) -> Result<Self, EMLError> {
+        Ok(collect_struct!(
+            elem,
+            CountElectionIdentifier {
+                id: elem.string_value_attr("Id", None)?,
+                name as Option: ("ElectionName", NS_EML) => |elem| elem.text_without_children()?,
+                category: ("ElectionCategory", NS_EML) => |elem| elem.string_value()?,
+                subcategory as Option: ("ElectionSubcategory", NS_KR) => |elem| elem.string_value()?,
+                domain as Option: ElectionDomain::EML_NAME => |elem| ElectionDomain::read_eml(elem)?,
+                election_date: ("ElectionDate", NS_KR) => |elem| elem.string_value()?,
+                extensions as Extensions,
+            }
+        ))
+    }
+
+    fn write_eml(&self, writer: EMLElementWriter) -> Result<(), EMLError> {
+        writer
+            .extensions(&self.extensions)?
+            .attr("Id", self.id.raw().as_ref())?
+            .child_option(
+                ("ElectionName", NS_EML),
+                self.name.as_ref(),
+                |elem, value| elem.text(value)?.finish(),
+            )?
+            .child(("ElectionCategory", NS_EML), |elem| {
+                elem.text(self.category.raw().as_ref())?.finish()
+            })?
+            .child_option(
+                ("ElectionSubcategory", NS_KR),
+                self.subcategory.as_ref(),
+                |elem, value| elem.text(value.raw().as_ref())?.finish(),
+            )?
+            .child_elem_option(ElectionDomain::EML_NAME, self.domain.as_ref())?
+            .child(("ElectionDate", NS_KR), |elem| {
+                elem.text(self.election_date.raw().as_ref())?.finish()
+            })?
+            .finish()
+    }
+}
+
+/// A contest that was counted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CountContest {
+    /// Identifier of the contest.
+    pub identifier: ContestIdentifier,
+
+    /// The total votes of the contest.
+    pub total_votes: CountTotalVotes,
+
+    /// The votes per reporting unit.
+    pub reporting_unit_votes: Vec<CountReportingUnitVotes>,
+
+    /// Unknown attributes and child elements, see [`Extensions`].
+    pub extensions: Extensions,
+}
+
+impl EMLElement for CountContest {
+    const EML_NAME: QualifiedName<'_, '_> = QualifiedName::from_static("Contest", Some(NS_EML));
+
+    fn read_eml(elem: &mut EMLElementReader<'_, '_>) -> Result<Self, EMLError> {
+        Ok(collect_struct!(elem, CountContest {
+            identifier: ContestIdentifier::EML_NAME => |elem| ContestIdentifier::read_eml(elem)?,
+            total_votes: CountTotalVotes::EML_NAME => |elem| CountTotalVotes::read_eml(elem)?,
+            reporting_unit_votes as Vec: CountReportingUnitVotes::EML_NAME => |elem| CountReportingUnitVotes::read_eml(elem)?,
+            extensions as Extensions,
+        }))
+    }
+
+    fn write_eml(&self, writer: EMLElementWriter) -> Result<(), EMLError> {
+        let mut writer = writer
+            .extensions(&self.extensions)?
+            .child_elem(ContestIdentifier::EML_NAME, &self.identifier)?
+            .child_elem(CountTotalVotes::EML_NAME, &self.total_votes)?;
+        for votes in &self.reporting_unit_votes {
+            writer = writer.child_elem(CountReportingUnitVotes::EML_NAME, votes)?;
+        }
+        writer.finish()
+    }
+}
+
+/// The total votes of a contest.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CountTotalVotes {
+    /// The votes per affiliation and candidate.
+    pub selections: Vec<CountSelection>,
+
+    /// The number of cast votes.
+    pub cast: StringValue<u64>,
+
+    /// The number of counted votes.
+    pub total_counted: StringValue<u64>,
+
+    /// The rejected votes, per reason.
+    pub rejected_votes: Vec<CountRejectedVotes>,
+
+    /// The uncounted votes, per reason.
+    pub uncounted_votes: Vec<CountUncountedVotes>,
+
+    /// Unknown attributes and child elements, see [`Extensions`].
+    pub extensions: Extensions,
+}
+
+impl EMLElement for CountTotalVotes {
+    const EML_NAME: QualifiedName<'_, '_> = QualifiedName::from_static("TotalVotes", Some(NS_EML));
+
+    fn read_eml(elem: &mut EMLElementReader<'_, '_>) -> Result<Self, EMLError> {
+        Ok(collect_struct!(elem, CountTotalVotes {
+            selections as Vec: CountSelection::EML_NAME => |elem| CountSelection::read_eml(elem)?,
+            cast: ("Cast", NS_EML) => |elem| elem.string_value()?,
+            total_counted: ("TotalCounted", NS_EML) => |elem| elem.string_value()?,
+            rejected_votes as Vec: CountRejectedVotes::EML_NAME => |elem| CountRejectedVotes::read_eml(elem)?,
+            uncounted_votes as Vec: CountUncountedVotes::EML_NAME => |elem| CountUncountedVotes::read_eml(elem)?,
+            extensions as Extensions,
+        }))
+    }
+
+    fn write_eml(&self, writer: EMLElementWriter) -> Result<(), EMLError> {
+        let mut writer = writer.extensions(&self.extensions)?.content()?;
+        for selection in &self.selections {
+            writer = writer.child_elem(CountSelection::EML_NAME, selection)?;
+        }
+
+        let mut writer = writer
+            .child(("Cast", NS_EML), |elem| {
+                elem.text(self.cast.raw().as_ref())?.finish()
+            })?
+            .child(("TotalCounted", NS_EML), |elem| {
+                elem.text(self.total_counted.raw().as_ref())?.finish()
+            })?;
+
+        for rejected in &self.rejected_votes {
+            writer = writer.child_elem(CountRejectedVotes::EML_NAME, rejected)?;
+        }
+        for uncounted in &self.uncounted_votes {
+            writer = writer.child_elem(CountUncountedVotes::EML_NAME, uncounted)?;
+        }
+        writer.finish()
+    }
+}
+
+/// The votes of a single reporting unit in a contest.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CountReportingUnitVotes {
+    /// Identifier of the reporting unit.
+    pub identifier: ReportingUnitIdentifier,
+
+    /// Investigations performed for this reporting unit.
+    pub investigations: Vec<CountInvestigation>,
+
+    /// The votes per affiliation and candidate.
+    pub selections: Vec<CountSelection>,
+
+    /// The number of cast votes.
+    pub cast: StringValue<u64>,
+
+    /// The number of counted votes.
+    pub total_counted: StringValue<u64>,
+
+    /// The rejected votes, per reason.
+    pub rejected_votes: Vec<CountRejectedVotes>,
+
+    /// The uncounted votes, per reason.
+    pub uncounted_votes: Vec<CountUncountedVotes>,
+
+    /// Unknown attributes and child elements, see [`Extensions`].
+    pub extensions: Extensions,
+}
+
+struct CountReportingUnitVotesInternal {
+    identifier: ReportingUnitIdentifier,
+    investigations: Option<Vec<CountInvestigation>>,
+    selections: Vec<CountSelection>,
+    cast: StringValue<u64>,
+    total_counted: StringValue<u64>,
+    rejected_votes: Vec<CountRejectedVotes>,
+    uncounted_votes: Vec<CountUncountedVotes>,
+    /// Unknown attributes and child elements, see [`Extensions`].
+    extensions: Extensions,
+}
+
+impl EMLElement for CountReportingUnitVotes {
+    const EML_NAME: QualifiedName<'_, '_> =
+        QualifiedName::from_static("ReportingUnitVotes", Some(NS_EML));
+
+    fn read_eml(elem: &mut EMLElementReader<'_, '_>) -> Result<Self, EMLError> {
+        let data = collect_struct!(elem, CountReportingUnitVotesInternal {
+            identifier: ReportingUnitIdentifier::EML_NAME => |elem| ReportingUnitIdentifier::read_eml(elem)?,
+            investigations as Option: ("ReportingUnitInvestigations", NS_KR) => |elem| {
+                // Temporary struct to collect investigation elements
+                struct Investigations {
+                    investigations: Vec<CountInvestigation>,
+                }
+
+                let res = collect_struct!(elem, Investigations {
+                    investigations as Vec: CountInvestigation::EML_NAME => |elem| CountInvestigation::read_eml(elem)?,
+                });
+
+                res.investigations
+            },
+            selections as Vec: CountSelection::EML_NAME => |elem| CountSelection::read_eml(elem)?,
+            cast: ("Cast", NS_EML) => |elem| elem.string_value()?,
+            total_counted: ("TotalCounted", NS_EML) => |elem| elem.string_value()?,
+            rejected_votes as Vec: CountRejectedVotes::EML_NAME => |elem| CountRejectedVotes::read_eml(elem)?,
+            uncounted_votes as Vec: CountUncountedVotes::EML_NAME => |elem| CountUncountedVotes::read_eml(elem)?,
+            extensions as Extensions,
+        });
+
+        Ok(CountReportingUnitVotes {
+            identifier: data.identifier,
+            investigations: data.investigations.unwrap_or_default(),
+            selections: data.selections,
+            cast: data.cast,
+            total_counted: data.total_counted,
+            rejected_votes: data.rejected_votes,
+            uncounted_votes: data.uncounted_votes,
+            extensions: data.extensions,
+        })
+    }
+
+    fn write_eml(&self, writer: EMLElementWriter) -> Result<(), EMLError> {
+        let mut writer = writer
+            .extensions(&self.extensions)?
+            .child_elem(ReportingUnitIdentifier::EML_NAME, &self.identifier)?;
+
+        if !self.investigations.is_empty() {
+            writer = writer.child(("ReportingUnitInvestigations", NS_KR), |writer| {
+                let mut writer = writer.content()?;
+                for investigation in &self.investigations {
+                    writer = writer.child_elem(CountInvestigation::EML_NAME, investigation)?;
+                }
+                writer.finish()
+            })?;
+        }
+
+        for selection in &self.selections {
+            writer = writer.child_elem(CountSelection::EML_NAME, selection)?;
+        }
+
+        let mut writer = writer
+            .child(("Cast", NS_EML), |elem| {
+                elem.text(self.cast.raw().as_ref())?.finish()
+            })?
+            .child(("TotalCounted", NS_EML), |elem| {
+                elem.text(self.total_counted.raw().as_ref())?.finish()
+            })?;
+
+        for rejected in &self.rejected_votes {
+            writer = writer.child_elem(CountRejectedVotes::EML_NAME, rejected)?;
+        }
+        for uncounted in &self.uncounted_votes {
+            writer = writer.child_elem(CountUncountedVotes::EML_NAME, uncounted)?;
+        }
+        writer.finish()
+    }
+}
+
+/// An investigation performed for a reporting unit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CountInvestigation {
+    /// The reason for the investigation.
+    pub reason_code: String,
+
+    /// Whether the investigation applies.
+    pub value: StringValue<bool>,
+}
+
+impl EMLElement for CountInvestigation {
+    const EML_NAME: QualifiedName<'_, '_> =
+        QualifiedName::from_static("Investigation", Some(NS_KR));
+
+    fn read_eml(elem: &mut EMLElementReader<'_, '_>) -> Result<Self, EMLError> {
+        let reason_code = elem.attribute_value_req("ReasonCode")?.into_owned();
+        let value = elem.string_value()?;
+        Ok(CountInvestigation { reason_code, value })
+    }
+
+    fn write_eml(&self, writer: EMLElementWriter) -> Result<(), EMLError> {
+        writer
+            .attr("ReasonCode", &self.reason_code)?
+            .text(self.value.raw().as_ref())?
+            .finish()
+    }
+}
+
+/// The number of valid votes for an affiliation or a candidate.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CountSelection {
+    /// The affiliation or candidate the votes were cast for.
+    pub selected: CountSelected,
+
+    /// The number of valid votes.
+    pub valid_votes: StringValue<u64>,
+
+    /// Unknown attributes and child elements, see [`Extensions`].
+    pub extensions: Extensions,
+}
+
+/// The affiliation or candidate the votes in a selection were cast for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CountSelected {
+    /// Votes for an affiliation.
+    Affiliation(AffiliationIdentifier),
+    /// Votes for a candidate.
+    Candidate(CountCandidate),
+}
+
+struct CountSelectionInternal {
+    affiliation: Option<AffiliationIdentifier>,
+    candidate: Option<CountCandidate>,
+    valid_votes: StringValue<u64>,
+    /// Unknown attributes and child elements, see [`Extensions`].
+    extensions: Extensions,
+}
+
+impl EMLElement for CountSelection {
+    const EML_NAME: QualifiedName<'_, '_> = QualifiedName::from_static("Selection", Some(NS_EML));
+
+    fn read_eml(elem: &mut EMLElementReader<'_, '_>) -> Result<Self, EMLError> {
+        let data = collect_struct!(elem, CountSelectionInternal {
+            affiliation as Option: AffiliationIdentifier::EML_NAME => |elem| AffiliationIdentifier::read_eml(elem)?,
+            candidate as Option: CountCandidate::EML_NAME => |elem| CountCandidate::read_eml(elem)?,
+            valid_votes: ("ValidVotes", NS_EML) => |elem| elem.string_value()?,
+            extensions as Extensions,
+        });
+
+        let selected = match (data.affiliation, data.candidate) {
+            (Some(affiliation), None) => CountSelected::Affiliation(affiliation),
+            (None, Some(candidate)) => CountSelected::Candidate(candidate),
+            (Some(affiliation), Some(_)) => {
+                let err = EMLErrorKind::UnexpectedElement(
+                    CountCandidate::EML_NAME.as_owned(),
+                    CountSelection::EML_NAME.as_owned(),
+                )
+                .add_span(elem.span());
+                if elem.parsing_mode().is_strict() {
+                    return Err(err);
+                } else {
+                    elem.push_err(err);
+                    CountSelected::Affiliation(affiliation)
+                }
+            }
+            (None, None) => {
+                return Err(EMLErrorKind::MissingChoiceElements(vec![
+                    AffiliationIdentifier::EML_NAME.as_owned(),
+                    CountCandidate::EML_NAME.as_owned(),
+                ])
+                .add_span(elem.span()));
+            }
+        };
+
+        Ok(CountSelection {
+            selected,
+            valid_votes: data.valid_votes,
+            extensions: data.extensions,
+        })
+    }
+
+    fn write_eml(&self, writer: EMLElementWriter) -> Result<(), EMLError> {
+        let writer = writer.extensions(&self.extensions)?;
+        match &self.selected {
+            CountSelected::Affiliation(affiliation) => {
+                writer.child_elem(AffiliationIdentifier::EML_NAME, affiliation)?
+            }
+            CountSelected::Candidate(candidate) => {
+                writer.child_elem(CountCandidate::EML_NAME, candidate)?
+            }
+        }
+        .child(("ValidVotes", NS_EML), |elem| {
+            elem.text(self.valid_votes.raw().as_ref())?.finish()
+        })?
+        .finish()
+    }
+}
+
+/// A candidate in a count selection.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CountCandidate {
+    /// The candidate identifier.
+    pub identifier: CountCandidateIdentifier,
+    /// Unknown attributes and child elements, see [`Extensions`].
+    pub extensions: Extensions,
+}
+
+impl EMLElement for CountCandidate {
+    const EML_NAME: QualifiedName<'_, '_> = QualifiedName::from_static("Candidate", Some(NS_EML));
+
+    fn read_eml(elem: &mut EMLElementReader<'_, '_>) -> Result<Self, EMLError> {
+        Ok(collect_struct!(elem, CountCandidate {
+            identifier: CountCandidateIdentifier::EML_NAME => |elem| CountCandidateIdentifier::read_eml(elem)?,
+            extensions as Extensions,
+        }))
+    }
+
+    fn write_eml(&self, writer: EMLElementWriter) -> Result<(), EMLError> {
+        writer
+            .extensions(&self.extensions)?
+            .child_elem(CountCandidateIdentifier::EML_NAME, &self.identifier)?
+            .finish()
+    }
+}
+
+/// Candidate identifier for 510 document types.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CountCandidateIdentifier {
+    /// The candidate id.
+    pub id: StringValue<CandidateIdType>,
+}
+
+impl EMLElement for CountCandidateIdentifier {
+    const EML_NAME: QualifiedName<'_, '_> =
+        QualifiedName::from_static("CandidateIdentifier", Some(NS_EML));
+
+    fn read_eml(elem: &mut EMLElementReader<'_, '_>) -> Result<Self, EMLError> {
+        let id = elem.string_value_attr("Id", None)?;
+        elem.skip()?;
+        Ok(CountCandidateIdentifier { id })
+    }
+
+    fn write_eml(&self, writer: EMLElementWriter) -> Result<(), EMLError> {
+        writer.attr("Id", self.id.raw().as_ref())?.empty()
+    }
+}
+
+/// The number of rejected votes for a specific reason.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CountRejectedVotes {
+    /// The reason the votes were rejected.
+    pub reason_code: StringValue<RejectedVotesReason>,
+
+    /// The number of rejected votes.
+    pub value: StringValue<u64>,
+}
+
+impl EMLElement for CountRejectedVotes {
+    const EML_NAME: QualifiedName<'_, '_> =
+        QualifiedName::from_static("RejectedVotes", Some(NS_EML));
+
+    fn read_eml(elem: &mut EMLElementReader<'_, '_>) -> Result<Self, EMLError> {
+        let reason_code = elem.string_value_attr("ReasonCode", None)?;
+        let value = elem.string_value()?;
+        Ok(CountRejectedVotes { reason_code, value })
+    }
+
+    fn write_eml(&self, writer: EMLElementWriter) -> Result<(), EMLError> {
+        writer
+            .attr("ReasonCode", self.reason_code.raw().as_ref())?
+            .text(self.value.raw().as_ref())?
+            .finish()
+    }
+}
+
+/// The number of uncounted votes for a specific reason.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CountUncountedVotes {
+    /// The reason the votes were not counted.
+    pub reason_code: String,
+
+    /// The number of uncounted votes.
+    pub value: StringValue<u64>,
+}
+
+impl EMLElement for CountUncountedVotes {
+    const EML_NAME: QualifiedName<'_, '_> =
+        QualifiedName::from_static("UncountedVotes", Some(NS_EML));
+
+    fn read_eml(elem: &mut EMLElementReader<'_, '_>) -> Result<Self, EMLError> {
+        let reason_code = elem.attribute_value_req("ReasonCode")?.into_owned();
+        let value = elem.string_value()?;
+        Ok(CountUncountedVotes { reason_code, value })
+    }
+
+    fn write_eml(&self, writer: EMLElementWriter) -> Result<(), EMLError> {
+        writer
+            .attr("ReasonCode", &self.reason_code)?
+            .text(self.value.raw().as_ref())?
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::io::{EMLParsingMode, EMLRead as _, EMLWrite as _};
+
+    use super::*;
+
+    #[test]
+    fn test_count_with_investigations_round_trip() {
+        let doc = include_str!("../../test-emls/eml510b_with_investigations.eml.xml");
+        let count = Count::parse_eml(doc, EMLParsingMode::Strict)
+            .ok()
+            .expect("Failed to parse EML document");
+        assert_eq!(count.level, CountLevel::Municipality);
+
+        let contest = &count.count.election.contests[0];
+        assert_eq!(contest.total_votes.selections.len(), 3);
+        assert!(matches!(
+            contest.total_votes.selections[1].selected,
+            CountSelected::Candidate(_)
+        ));
+        assert_eq!(
+            contest.total_votes.rejected_votes[1]
+                .reason_code
+                .value()
+                .ok()
+                .map(|v| *v),
+            Some(RejectedVotesReason::Blank)
+        );
+        let investigations = &contest.reporting_unit_votes[0].investigations;
+        assert_eq!(investigations.len(), 3);
+        assert_eq!(investigations[0].value.value().ok().map(|v| *v), Some(true));
+
+        let written = count
+            .write_eml_root_str(true, true)
+            .expect("Failed to write EML document");
+        let reparsed = Count::parse_eml(&written, EMLParsingMode::Strict)
+            .ok()
+            .expect("Failed to parse written EML document");
+        let contest = &reparsed.count.election.contests[0];
+        assert_eq!(contest.total_votes.selections.len(), 3);
+        assert_eq!(contest.reporting_unit_votes[0].investigations.len(), 3);
+    }
+
+    #[test]
+    fn test_count_levels() {
+        let doc = include_str!("../../test-emls/deserialize_eml510b_test.eml.xml");
+        for id in ["510a", "510b", "510c", "510d"] {
+            let doc = doc.replace(r#"Id="510b""#, &format!(r#"Id="{id}""#));
+            let count = Count::parse_eml(&doc, EMLParsingMode::Strict)
+                .ok()
+                .expect("Failed to parse EML document");
+            assert_eq!(count.level.to_eml_id(), id);
+
+            let written = count
+                .write_eml_root_str(false, false)
+                .expect("Failed to write EML document");
+            assert!(written.contains(&format!(r#"Id="{id}""#)));
+        }
+
+        let doc = doc.replace(r#"Id="510b""#, r#"Id="520""#);
+        assert!(Count::parse_eml(&doc, EMLParsingMode::Strict).ok().is_err());
+    }
+}
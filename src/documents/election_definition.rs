@@ -6,20 +6,24 @@ use crate::{
     EML_SCHEMA_VERSION, EMLError, NS_EML, NS_KR,
     common::{
         CanonicalizationMethod, ContestIdentifier, CreationDateTime, ElectionDomain, ElectionTree,
-        IssueDate, ManagingAuthority, TransactionId,
+        IssueDate, ManagingAuthority, Signature, TransactionId,
     },
     documents::accepted_root,
     error::{EMLErrorKind, EMLResultExt},
-    io::{EMLElement, EMLElementReader, EMLElementWriter, QualifiedName, collect_struct},
+    io::{
+        EMLElement, EMLElementReader, EMLElementWriter, Extensions, QualifiedName, collect_struct,
+    },
     utils::{
         ElectionCategory, ElectionIdType, ElectionSubcategory, StringValue, VotingMethod, XsDate,
     },
+    validation::{ValidationFinding, parsed_value},
 };
 
 pub(crate) const EML_ELECTION_DEFINITION_ID: &str = "110a";
 
 /// Representing a `110a` document, containing an election definition.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ElectionDefinition {
     /// Transaction id of the document.
     pub transaction_id: TransactionId,
@@ -38,6 +42,147 @@ pub struct ElectionDefinition {
 
     /// The election event defined in this document.
     pub election_event: ElectionDefinitionElectionEvent,
+
+    /// Enveloped signature of this document, if present.
+    pub signature: Option<Signature>,
+
+    /// Unknown attributes and child elements, see [`Extensions`].
+    pub extensions: Extensions,
+}
+
+/// Councils with fewer seats than this are small councils, which use a
+/// different subcategory and preference threshold.
+const SMALL_COUNCIL_SEATS: u64 = 19;
+
+impl ElectionDefinition {
+    /// Validate this election definition against the rules of the Kiesraad.
+    ///
+    /// The following rules are checked:
+    ///
+    /// * The subcategory must belong to the category, and for councils must
+    ///   match the number of seats (e.g. `GR1` for less than 19 seats).
+    /// * The number of seats must be within the bounds of the category.
+    /// * The preference threshold must be 50% for small councils and 25%
+    ///   otherwise.
+    /// * The nomination date must be before the election date.
+    /// * The election tree must contain at least one region.
+    pub fn validate(&self) -> Vec<ValidationFinding> {
+        let mut findings = Vec::new();
+        let election = &self.election_event.election;
+        let identifier = &election.identifier;
+
+        let category = parsed_value(&identifier.category, "ElectionCategory", &mut findings);
+        let subcategory = parsed_value(
+            &identifier.subcategory,
+            "ElectionSubcategory",
+            &mut findings,
+        );
+        let seats = parsed_value(&election.number_of_seats, "NumberOfSeats", &mut findings);
+        let threshold = parsed_value(
+            &election.preference_threshold,
+            "PreferenceThreshold",
+            &mut findings,
+        );
+
+        if let (Some(category), Some(subcategory)) = (category, subcategory)
+            && subcategory.category() != category
+        {
+            findings.push(ValidationFinding::SubcategoryMismatch(
+                category.to_str_value().to_string(),
+                subcategory.to_str_value().to_string(),
+            ));
+        }
+
+        if let (Some(category), Some(seats)) = (category, seats)
+            && let Some((min, max)) = seat_bounds(category)
+            && !(min..=max).contains(&seats)
+        {
+            findings.push(ValidationFinding::NumberOfSeatsOutOfRange(
+                category.to_str_value().to_string(),
+                seats,
+                min,
+                max,
+            ));
+        }
+
+        if let (Some(subcategory), Some(seats)) = (subcategory, seats) {
+            let small = seats < SMALL_COUNCIL_SEATS;
+            let expected = match subcategory {
+                ElectionSubcategory::GR1 | ElectionSubcategory::GR2 if small => {
+                    Some(ElectionSubcategory::GR1)
+                }
+                ElectionSubcategory::GR1 | ElectionSubcategory::GR2 => {
+                    Some(ElectionSubcategory::GR2)
+                }
+                ElectionSubcategory::AB1 | ElectionSubcategory::AB2 if small => {
+                    Some(ElectionSubcategory::AB1)
+                }
+                ElectionSubcategory::AB1 | ElectionSubcategory::AB2 => {
+                    Some(ElectionSubcategory::AB2)
+                }
+                _ => None,
+            };
+            if let Some(expected) = expected
+                && expected != subcategory
+            {
+                findings.push(ValidationFinding::SubcategorySeatsMismatch(
+                    subcategory.to_str_value().to_string(),
+                    seats,
+                    expected.to_str_value().to_string(),
+                ));
+            }
+        }
+
+        if let (Some(category), Some(seats), Some(threshold)) = (category, seats, threshold)
+            && matches!(
+                category,
+                ElectionCategory::TK
+                    | ElectionCategory::EP
+                    | ElectionCategory::PS
+                    | ElectionCategory::AB
+                    | ElectionCategory::GR
+                    | ElectionCategory::ER
+            )
+        {
+            let expected = if seats < SMALL_COUNCIL_SEATS { 50 } else { 25 };
+            if threshold != expected {
+                findings.push(ValidationFinding::PreferenceThresholdMismatch(
+                    threshold, expected,
+                ));
+            }
+        }
+
+        let election_date = parsed_value(&identifier.election_date, "ElectionDate", &mut findings);
+        let nomination_date =
+            parsed_value(&identifier.nomination_date, "NominationDate", &mut findings);
+        if let (Some(election_date), Some(nomination_date)) = (election_date, nomination_date)
+            && nomination_date.date >= election_date.date
+        {
+            findings.push(ValidationFinding::NominationDateNotBeforeElectionDate(
+                identifier.nomination_date.raw().into_owned(),
+                identifier.election_date.raw().into_owned(),
+            ));
+        }
+
+        if election.election_tree.regions.is_empty() {
+            findings.push(ValidationFinding::EmptyElectionTree);
+        }
+
+        findings
+    }
+}
+
+/// Allowed number of seats for an election category, if the category has
+/// fixed bounds.
+fn seat_bounds(category: ElectionCategory) -> Option<(u64, u64)> {
+    match category {
+        ElectionCategory::TK => Some((150, 150)),
+        ElectionCategory::EK => Some((75, 75)),
+        ElectionCategory::PS => Some((39, 55)),
+        ElectionCategory::GR => Some((9, 45)),
+        ElectionCategory::ER => Some((5, 9)),
+        _ => None,
+    }
 }
 
 impl EMLElement for ElectionDefinition {
@@ -46,13 +191,13 @@ impl EMLElement for ElectionDefinition {
     fn read_eml(elem: &mut EMLElementReader<'_, '_>) -> Result<Self, EMLError> {
         accepted_root(elem)?;
 
-        let document_id = elem.attribute_value_req(("Id", None))?;
+        let (document_id, id_span) = elem.attribute_value_req_with_span(("Id", None))?;
         if document_id != EML_ELECTION_DEFINITION_ID {
             return Err(EMLErrorKind::InvalidDocumentType(
                 EML_ELECTION_DEFINITION_ID,
                 document_id.to_string(),
             ))
-            .with_span(elem.span());
+            .with_span(id_span);
         }
 
         Ok(collect_struct!(elem, ElectionDefinition {
@@ -62,11 +207,14 @@ impl EMLElement for ElectionDefinition {
             creation_date_time: CreationDateTime::EML_NAME => |elem| CreationDateTime::read_eml(elem)?,
             canonicalization_method as Option: CanonicalizationMethod::EML_NAME => |elem| CanonicalizationMethod::read_eml(elem)?,
             election_event: ElectionDefinitionElectionEvent::EML_NAME => |elem| ElectionDefinitionElectionEvent::read_eml(elem)?,
+            signature as Option: Signature::EML_NAME => |elem| Signature::read_eml(elem)?,
+            extensions as Extensions,
         }))
     }
 
     fn write_eml(&self, writer: EMLElementWriter) -> Result<(), EMLError> {
         writer
+            .extensions(&self.extensions)?
             .attr(("Id", None), EML_ELECTION_DEFINITION_ID)?
             .attr(("SchemaVersion", None), EML_SCHEMA_VERSION)?
             .child_elem(TransactionId::EML_NAME, &self.transaction_id)?
@@ -74,29 +222,30 @@ impl EMLElement for ElectionDefinition {
                 ManagingAuthority::EML_NAME,
                 self.managing_authority.as_ref(),
             )?
-            .child_elem(CreationDateTime::EML_NAME, &self.creation_date_time)?
             .child_elem_option(IssueDate::EML_NAME, self.issue_date.as_ref())?
-            // Note: we don't output the CanonicalizationMethod because we aren't canonicalizing our output
-            // .child_elem_option(
-            //     CanonicalizationMethod::EML_NAME,
-            //     self.canonicalization_method.as_ref(),
-            // )?
+            .child_elem(CreationDateTime::EML_NAME, &self.creation_date_time)?
+            .canonicalization_method(self.canonicalization_method.is_some())?
             .child_elem(
                 ElectionDefinitionElectionEvent::EML_NAME,
                 &self.election_event,
             )?
+            .child_elem_option(Signature::EML_NAME, self.signature.as_ref())?
             .finish()
     }
 }
 
 /// Election event defined in the election definition document.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ElectionDefinitionElectionEvent {
     /// Identifier for this election event.
     pub id: ElectionDefinitionElectionEventIdentifier,
 
     /// Election details.
     pub election: ElectionDefinitionElection,
+
+    /// Unknown attributes and child elements, see [`Extensions`].
+    pub extensions: Extensions,
 }
 
 impl EMLElement for ElectionDefinitionElectionEvent {
@@ -107,11 +256,13 @@ impl EMLElement for ElectionDefinitionElectionEvent {
         Ok(collect_struct!(elem, ElectionDefinitionElectionEvent {
             id: ElectionDefinitionElectionEventIdentifier::EML_NAME => |elem| ElectionDefinitionElectionEventIdentifier::read_eml(elem)?,
             election: ElectionDefinitionElection::EML_NAME => |elem| ElectionDefinitionElection::read_eml(elem)?,
+            extensions as Extensions,
         }))
     }
 
     fn write_eml(&self, writer: EMLElementWriter) -> Result<(), EMLError> {
         writer
+            .extensions(&self.extensions)?
             .child_elem(
                 ElectionDefinitionElectionEventIdentifier::EML_NAME,
                 &self.id,
@@ -122,7 +273,8 @@ impl EMLElement for ElectionDefinitionElectionEvent {
 }
 
 /// Event identifier for an election event, is an empty element.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ElectionDefinitionElectionEventIdentifier;
 
 impl EMLElement for ElectionDefinitionElectionEventIdentifier {
@@ -151,7 +303,8 @@ const EML_NAME_PREFERENCE_THRESHOLD: QualifiedName<'_, '_> =
     QualifiedName::from_static("PreferenceThreshold", Some(NS_KR));
 
 /// Election details for an election definition.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ElectionDefinitionElection {
     /// Identifier
     pub identifier: ElectionDefinitionElectionIdentifier,
@@ -165,6 +318,8 @@ pub struct ElectionDefinitionElection {
     pub election_tree: ElectionTree,
     /// A list of registered parties.
     pub registered_parties: Vec<ElectionDefinitionRegisteredParty>,
+    /// Unknown attributes and child elements, see [`Extensions`].
+    pub extensions: Extensions,
 }
 
 impl EMLElement for ElectionDefinitionElection {
@@ -178,17 +333,18 @@ impl EMLElement for ElectionDefinitionElection {
             preference_threshold: EML_NAME_PREFERENCE_THRESHOLD => |elem| elem.string_value()?,
             election_tree: ElectionTree::EML_NAME => |elem| ElectionTree::read_eml(elem)?,
             registered_parties: ("RegisteredParties", NS_KR) => |elem| ElectionDefinitionRegisteredParty::read_list(elem)?,
+            extensions as Extensions,
         }))
     }
 
     fn write_eml(&self, writer: EMLElementWriter) -> Result<(), EMLError> {
         writer
+            .extensions(&self.extensions)?
             .child_elem(
                 ElectionDefinitionElectionIdentifier::EML_NAME,
                 &self.identifier,
             )?
             .child_elem(ElectionDefinitionContest::EML_NAME, &self.contest)?
-            .child_elem(ElectionTree::EML_NAME, &self.election_tree)?
             .child(EML_NAME_NUMBER_OF_SEATS, |elem| {
                 elem.text(self.number_of_seats.raw().as_ref())?.finish()
             })?
@@ -196,6 +352,7 @@ impl EMLElement for ElectionDefinitionElection {
                 elem.text(self.preference_threshold.raw().as_ref())?
                     .finish()
             })?
+            .child_elem(ElectionTree::EML_NAME, &self.election_tree)?
             .child(("RegisteredParties", NS_KR), |elem| {
                 ElectionDefinitionRegisteredParty::write_list(&self.registered_parties, elem)
             })?
@@ -204,7 +361,8 @@ impl EMLElement for ElectionDefinitionElection {
 }
 
 /// Identifier for the election.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ElectionDefinitionElectionIdentifier {
     /// Id of the election
     pub id: StringValue<ElectionIdType>,
@@ -220,6 +378,8 @@ pub struct ElectionDefinitionElectionIdentifier {
     pub election_date: StringValue<XsDate>,
     /// Nomination date for the election
     pub nomination_date: StringValue<XsDate>,
+    /// Unknown attributes and child elements, see [`Extensions`].
+    pub extensions: Extensions,
 }
 
 impl EMLElement for ElectionDefinitionElectionIdentifier {
@@ -237,12 +397,14 @@ impl EMLElement for ElectionDefinitionElectionIdentifier {
                 domain as Option: ElectionDomain::EML_NAME => |elem| ElectionDomain::read_eml(elem)?,
                 election_date: ("ElectionDate", NS_KR) => |elem| elem.string_value()?,
                 nomination_date: ("NominationDate", NS_KR) => |elem| elem.string_value()?,
+                extensions as Extensions,
             }
         ))
     }
 
     fn write_eml(&self, writer: EMLElementWriter) -> Result<(), EMLError> {
         writer
+            .extensions(&self.extensions)?
             .attr("Id", self.id.raw().as_ref())?
             .child(("ElectionName", NS_EML), |elem| {
                 elem.text(self.name.as_ref())?.finish()
@@ -265,7 +427,8 @@ impl EMLElement for ElectionDefinitionElectionIdentifier {
 }
 
 /// Contains details about the voting methods for the election.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ElectionDefinitionContest {
     /// Identifier for the contest.
     pub identifier: ContestIdentifier,
@@ -273,6 +436,8 @@ pub struct ElectionDefinitionContest {
     pub voting_method: StringValue<VotingMethod>,
     /// Maximum number of votes allowed.
     pub max_votes: StringValue<NonZeroU64>,
+    /// Unknown attributes and child elements, see [`Extensions`].
+    pub extensions: Extensions,
 }
 
 impl EMLElement for ElectionDefinitionContest {
@@ -286,11 +451,13 @@ impl EMLElement for ElectionDefinitionContest {
                 let text = elem.text_without_children_opt()?.unwrap_or_else(|| "1".to_string());
                 elem.string_value_from_text(text, None, elem.full_span())?
             },
+            extensions as Extensions,
         }))
     }
 
     fn write_eml(&self, writer: EMLElementWriter) -> Result<(), EMLError> {
         writer
+            .extensions(&self.extensions)?
             .child_elem(ContestIdentifier::EML_NAME, &self.identifier)?
             .child(("VotingMethod", NS_EML), |elem| {
                 elem.text(self.voting_method.raw().as_ref())?.finish()
@@ -312,10 +479,13 @@ impl EMLElement for ElectionDefinitionContest {
 /// In election definitions this is just a party name, for full party details and
 /// candidates see the [`CandidateLists`](crate::documents::candidate_lists::CandidateLists)
 /// document.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ElectionDefinitionRegisteredParty {
     /// Name of the registered party (as registered at the CSB)
     pub registered_appellation: String,
+    /// Unknown attributes and child elements, see [`Extensions`].
+    pub extensions: Extensions,
 }
 
 impl ElectionDefinitionRegisteredParty {
@@ -325,6 +495,7 @@ impl ElectionDefinitionRegisteredParty {
         let mut parties = Vec::new();
         let elem_name = elem.name()?.as_owned();
         while let Some(mut child) = elem.next_child()? {
+            child.drop_annotations(&elem_name);
             if child.has_name(ElectionDefinitionRegisteredParty::EML_NAME)? {
                 let party = ElectionDefinitionRegisteredParty::read_eml(&mut child)?;
                 parties.push(party);
@@ -336,6 +507,7 @@ impl ElectionDefinitionRegisteredParty {
                 .with_span(child.span());
             }
         }
+        elem.drop_annotations(&elem_name);
         Ok(parties)
     }
 
@@ -358,14 +530,230 @@ impl EMLElement for ElectionDefinitionRegisteredParty {
     fn read_eml(elem: &mut EMLElementReader<'_, '_>) -> Result<Self, EMLError> {
         Ok(collect_struct!(elem, ElectionDefinitionRegisteredParty {
             registered_appellation: ("RegisteredAppellation", NS_KR) => |elem| elem.text_without_children()?,
+            extensions as Extensions,
         }))
     }
 
     fn write_eml(&self, writer: EMLElementWriter) -> Result<(), EMLError> {
         writer
+            .extensions(&self.extensions)?
             .child(("RegisteredAppellation", NS_KR), |elem| {
                 elem.text(self.registered_appellation.as_ref())?.finish()
             })?
             .finish()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        io::{EMLParsingMode, EMLRead as _, EMLWrite as _},
+        utils::{CommitteeCategory, RegionCategory},
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_election_definition_round_trip() {
+        let doc = include_str!("../../test-emls/election_definition/eml110a_test.eml.xml");
+        let definition = ElectionDefinition::parse_eml(doc, EMLParsingMode::Strict)
+            .ok()
+            .expect("Failed to parse EML document");
+
+        let tree = &definition.election_event.election.election_tree;
+        assert_eq!(tree.regions.len(), 1);
+        let region = tree.find_region("000").expect("Region not found");
+        assert!(region.is_category(RegionCategory::Municipality));
+        assert_eq!(region.region_name, "Test");
+        assert_eq!(region.committees.len(), 2);
+        assert_eq!(
+            region.committees[1].category.value().ok().map(|c| *c),
+            Some(CommitteeCategory::HSB)
+        );
+
+        let written = definition
+            .write_eml_root_str(true, true)
+            .expect("Failed to write EML document");
+        let reparsed = ElectionDefinition::parse_eml(&written, EMLParsingMode::Strict)
+            .ok()
+            .expect("Failed to parse written EML document");
+        assert_eq!(
+            reparsed.election_event.election.election_tree.regions.len(),
+            1
+        );
+        assert_eq!(
+            reparsed
+                .write_eml_root_str(true, true)
+                .expect("Failed to write EML document"),
+            written
+        );
+    }
+
+    #[test]
+    fn test_election_definition_equality_across_parsing_modes() {
+        let doc = include_str!("../../test-emls/election_definition/eml110a_test.eml.xml");
+        let strict = ElectionDefinition::parse_eml(doc, EMLParsingMode::Strict)
+            .ok()
+            .expect("Failed to parse EML document");
+        let loose = ElectionDefinition::parse_eml(doc, EMLParsingMode::Loose)
+            .ok()
+            .expect("Failed to parse EML document");
+        assert_eq!(strict, loose);
+
+        let mut changed = loose.clone();
+        changed.election_event.election.identifier.name = "Other".to_string();
+        assert_ne!(strict, changed);
+    }
+
+    #[test]
+    fn test_validate_election_definition() {
+        let cases: [(&str, Vec<ValidationFinding>); 6] = [
+            (
+                include_str!("../../test-emls/election_definition/eml110a_test.eml.xml"),
+                vec![],
+            ),
+            (
+                include_str!(
+                    "../../test-emls/election_definition/eml110a_invalid_election_mismatch_preference_threshold.eml.xml"
+                ),
+                vec![ValidationFinding::PreferenceThresholdMismatch(50, 25)],
+            ),
+            (
+                include_str!(
+                    "../../test-emls/election_definition/eml110a_invalid_election_mismatch_preference_threshold_small_election.eml.xml"
+                ),
+                vec![ValidationFinding::PreferenceThresholdMismatch(25, 50)],
+            ),
+            (
+                include_str!(
+                    "../../test-emls/election_definition/eml110a_invalid_election_number_of_seats.eml.xml"
+                ),
+                vec![
+                    ValidationFinding::SubcategorySeatsMismatch(
+                        "GR2".to_string(),
+                        9,
+                        "GR1".to_string(),
+                    ),
+                    ValidationFinding::PreferenceThresholdMismatch(25, 50),
+                ],
+            ),
+            (
+                include_str!(
+                    "../../test-emls/election_definition/eml110a_invalid_election_number_of_seats_out_of_range.eml.xml"
+                ),
+                vec![ValidationFinding::NumberOfSeatsOutOfRange(
+                    "GR".to_string(),
+                    250,
+                    9,
+                    45,
+                )],
+            ),
+            (
+                include_str!(
+                    "../../test-emls/election_definition/eml110a_invalid_election_missing_region.eml.xml"
+                ),
+                vec![ValidationFinding::EmptyElectionTree],
+            ),
+        ];
+
+        for (doc, expected) in cases {
+            let definition = ElectionDefinition::parse_eml(doc, EMLParsingMode::Strict)
+                .ok()
+                .expect("Failed to parse EML document");
+            assert_eq!(definition.validate(), expected);
+        }
+
+        let doc = include_str!(
+            "../../test-emls/election_definition/eml110a_invalid_election_subcategory.eml.xml"
+        );
+        let doc = doc.replace("2022-01-31", "2022-03-16");
+        let definition = ElectionDefinition::parse_eml(&doc, EMLParsingMode::Strict)
+            .ok()
+            .expect("Failed to parse EML document");
+        assert_eq!(
+            definition.validate(),
+            vec![
+                ValidationFinding::SubcategorySeatsMismatch(
+                    "GR1".to_string(),
+                    45,
+                    "GR2".to_string()
+                ),
+                ValidationFinding::NominationDateNotBeforeElectionDate(
+                    "2022-03-16".to_string(),
+                    "2022-03-16".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_election_tree_traversal() {
+        let tree_xml = r#"<kr:ElectionTree>
+                <kr:Region RegionNumber="9" RegionCategory="PROVINCIE">
+                    <kr:RegionName>Utrecht</kr:RegionName>
+                    <kr:Committee CommitteeCategory="CSB" CommitteeName="Centraal stembureau"/>
+                </kr:Region>
+                <kr:Region RegionNumber="10" RegionCategory="KIESKRING" RomanNumerals="X" SuperiorRegionNumber="9" SuperiorRegionCategory="PROVINCIE">
+                    <kr:RegionName>Utrecht</kr:RegionName>
+                    <kr:Committee CommitteeCategory="HSB"/>
+                </kr:Region>
+                <kr:Region RegionNumber="0344" RegionCategory="GEMEENTE" SuperiorRegionNumber="10" SuperiorRegionCategory="KIESKRING">
+                    <kr:RegionName>Utrecht</kr:RegionName>
+                    <kr:Committee CommitteeCategory="GSB"/>
+                </kr:Region>
+                <kr:Region RegionNumber="0307" RegionCategory="GEMEENTE" SuperiorRegionNumber="10" SuperiorRegionCategory="KIESKRING">
+                    <kr:RegionName>Amersfoort</kr:RegionName>
+                </kr:Region>
+            </kr:ElectionTree>"#;
+        let doc = include_str!("../../test-emls/election_definition/eml110a_test.eml.xml");
+        let start = doc.find("<kr:ElectionTree>").unwrap();
+        let end = doc.find("</kr:ElectionTree>").unwrap() + "</kr:ElectionTree>".len();
+        let doc = format!("{}{}{}", &doc[..start], tree_xml, &doc[end..]);
+
+        let definition = ElectionDefinition::parse_eml(&doc, EMLParsingMode::Strict)
+            .ok()
+            .expect("Failed to parse EML document");
+        let tree = &definition.election_event.election.election_tree;
+        assert_eq!(tree.regions.len(), 4);
+        assert_eq!(tree.roots().count(), 1);
+
+        let district = tree
+            .find_region_in_category(RegionCategory::ElectoralDistrict, "10")
+            .expect("Region not found");
+        assert_eq!(district.roman_numerals.as_deref(), Some("X"));
+        let children = tree
+            .children(district)
+            .map(|r| r.region_number.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(children, vec!["0344", "0307"]);
+
+        let municipality = tree.find_region("0307").expect("Region not found");
+        let path = tree
+            .path_to_root(municipality)
+            .into_iter()
+            .map(|r| r.region_number.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(path, vec!["0307", "10", "9"]);
+        assert!(tree.parent(tree.find_region("9").unwrap()).is_none());
+
+        let written = definition
+            .write_eml_root_str(true, true)
+            .expect("Failed to write EML document");
+        let reparsed = ElectionDefinition::parse_eml(&written, EMLParsingMode::Strict)
+            .ok()
+            .expect("Failed to parse written EML document");
+        let reparsed_tree = &reparsed.election_event.election.election_tree;
+        assert_eq!(reparsed_tree.regions.len(), 4);
+        assert_eq!(
+            reparsed_tree.regions[0].committees[0].name.as_deref(),
+            Some("Centraal stembureau")
+        );
+        assert_eq!(
+            reparsed_tree.regions[2]
+                .superior_region_category
+                .as_ref()
+                .map(|c| c.raw().into_owned()),
+            Some("KIESKRING".to_string())
+        );
+    }
+}
@@ -0,0 +1,363 @@
+//! Consistency checks between the documents that together describe an election.
+
+use crate::{
+    common::ElectionDomain,
+    documents::{
+        EML, candidate_lists::CandidateLists, election_definition::ElectionDefinition,
+        polling_stations::PollingStations,
+    },
+    validation::ValidationFinding,
+};
+
+/// A set of documents describing the same election.
+///
+/// An election is usually described by an election definition (`110a`), the
+/// polling stations (`110b`) and the candidate lists (`230b`), which are often
+/// produced by different parties. Use [`ElectionPackage::validate`] to check
+/// that these documents agree with each other.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ElectionPackage {
+    /// The election definition (`110a`), if present.
+    pub election_definition: Option<ElectionDefinition>,
+
+    /// The polling stations (`110b`), if present.
+    pub polling_stations: Option<PollingStations>,
+
+    /// The candidate lists (`230b`), if present.
+    pub candidate_lists: Option<CandidateLists>,
+
+    /// Documents that could not be placed in this package, either because
+    /// their type is not part of a package or because a document of the same
+    /// type was already present.
+    pub other_documents: Vec<EML>,
+}
+
+impl ElectionPackage {
+    /// Create a package from a list of documents.
+    pub fn from_documents(documents: impl IntoIterator<Item = EML>) -> Self {
+        let mut package = ElectionPackage::default();
+        for document in documents {
+            package.add(document);
+        }
+        package
+    }
+
+    /// Add a document to this package.
+    ///
+    /// Documents that do not fit in the package are stored in
+    /// [`ElectionPackage::other_documents`].
+    pub fn add(&mut self, document: EML) {
+        match document {
+            EML::ElectionDefinition(ed) if self.election_definition.is_none() => {
+                self.election_definition = Some(*ed);
+            }
+            EML::PollingStations(ps) if self.polling_stations.is_none() => {
+                self.polling_stations = Some(*ps);
+            }
+            EML::CandidateLists(cl) if self.candidate_lists.is_none() => {
+                self.candidate_lists = Some(*cl);
+            }
+            document => self.other_documents.push(document),
+        }
+    }
+
+    /// Check that the documents in this package describe the same election.
+    ///
+    /// The following rules are checked:
+    ///
+    /// * The election id, category, subcategory, domain and election date
+    ///   must be the same in all documents. A subcategory or domain that is
+    ///   present in one document must also be present in the others.
+    /// * Every affiliation in the candidate lists must be a registered party
+    ///   in the election definition.
+    /// * The package must not contain any other documents.
+    ///
+    /// This does not validate the documents themselves, use the `validate`
+    /// method of each document for that.
+    pub fn validate(&self) -> Vec<ValidationFinding> {
+        let mut findings = Vec::new();
+
+        let summaries = [
+            self.election_definition
+                .as_ref()
+                .map(ElectionSummary::from_election_definition),
+            self.polling_stations
+                .as_ref()
+                .map(ElectionSummary::from_polling_stations),
+            self.candidate_lists
+                .as_ref()
+                .map(ElectionSummary::from_candidate_lists),
+        ];
+        let mut summaries = summaries.iter().flatten();
+        if let Some(reference) = summaries.next() {
+            for other in summaries {
+                reference.compare(other, &mut findings);
+            }
+        }
+
+        if let (Some(definition), Some(candidate_lists)) =
+            (&self.election_definition, &self.candidate_lists)
+        {
+            let registered_parties = &definition.election_event.election.registered_parties;
+            for affiliation in &candidate_lists.candidate_list.election.contest.affiliations {
+                if let Some(name) = &affiliation.identifier.registered_name
+                    && !registered_parties
+                        .iter()
+                        .any(|p| &p.registered_appellation == name)
+                {
+                    findings.push(ValidationFinding::UnregisteredAffiliation(name.clone()));
+                }
+            }
+        }
+
+        for document in &self.other_documents {
+            findings.push(ValidationFinding::UnexpectedPackageDocument(
+                document.to_eml_id().to_string(),
+            ));
+        }
+
+        findings
+    }
+}
+
+/// The election identifying values of a single document, as raw strings.
+struct ElectionSummary {
+    document: &'static str,
+    id: String,
+    category: String,
+    subcategory: Option<String>,
+    domain: Option<String>,
+    election_date: String,
+}
+
+impl ElectionSummary {
+    fn from_election_definition(definition: &ElectionDefinition) -> Self {
+        let identifier = &definition.election_event.election.identifier;
+        ElectionSummary {
+            document: "110a",
+            id: identifier.id.raw().into_owned(),
+            category: identifier.category.raw().into_owned(),
+            subcategory: Some(identifier.subcategory.raw().into_owned()),
+            domain: domain_id(identifier.domain.as_ref()),
+            election_date: identifier.election_date.raw().into_owned(),
+        }
+    }
+
+    fn from_polling_stations(polling_stations: &PollingStations) -> Self {
+        let identifier = &polling_stations.election_event.election.identifier;
+        ElectionSummary {
+            document: "110b",
+            id: identifier.id.raw().into_owned(),
+            category: identifier.category.raw().into_owned(),
+            subcategory: identifier
+                .subcategory
+                .as_ref()
+                .map(|s| s.raw().into_owned()),
+            domain: domain_id(identifier.domain.as_ref()),
+            election_date: identifier.election_date.raw().into_owned(),
+        }
+    }
+
+    fn from_candidate_lists(candidate_lists: &CandidateLists) -> Self {
+        let identifier = &candidate_lists.candidate_list.election.identifier;
+        ElectionSummary {
+            document: "230b",
+            id: identifier.id.raw().into_owned(),
+            category: identifier.category.raw().into_owned(),
+            subcategory: identifier
+                .subcategory
+                .as_ref()
+                .map(|s| s.raw().into_owned()),
+            domain: domain_id(identifier.domain.as_ref()),
+            election_date: identifier.election_date.raw().into_owned(),
+        }
+    }
+
+    fn compare(&self, other: &ElectionSummary, findings: &mut Vec<ValidationFinding>) {
+        let fields = [
+            ("ElectionIdentifier", Some(&self.id), Some(&other.id)),
+            (
+                "ElectionCategory",
+                Some(&self.category),
+                Some(&other.category),
+            ),
+            (
+                "ElectionSubcategory",
+                self.subcategory.as_ref(),
+                other.subcategory.as_ref(),
+            ),
+            (
+                "ElectionDomain",
+                self.domain.as_ref(),
+                other.domain.as_ref(),
+            ),
+            (
+                "ElectionDate",
+                Some(&self.election_date),
+                Some(&other.election_date),
+            ),
+        ];
+        for (field, value, other_value) in fields {
+            match (value, other_value) {
+                (Some(value), Some(other_value)) if value != other_value => {
+                    findings.push(ValidationFinding::ElectionMismatch(
+                        field.to_string(),
+                        self.document.to_string(),
+                        value.clone(),
+                        other.document.to_string(),
+                        other_value.clone(),
+                    ));
+                }
+                (Some(value), None) => {
+                    findings.push(ValidationFinding::ElectionValueMissing(
+                        field.to_string(),
+                        self.document.to_string(),
+                        value.clone(),
+                        other.document.to_string(),
+                    ));
+                }
+                (None, Some(other_value)) => {
+                    findings.push(ValidationFinding::ElectionValueMissing(
+                        field.to_string(),
+                        other.document.to_string(),
+                        other_value.clone(),
+                        self.document.to_string(),
+                    ));
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+fn domain_id(domain: Option<&ElectionDomain>) -> Option<String> {
+    domain.map(|d| d.id.raw().into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::io::{EMLParsingMode, EMLRead as _};
+
+    use super::*;
+
+    fn parse(doc: &str) -> EML {
+        EML::parse_eml(doc, EMLParsingMode::Loose)
+            .ok()
+            .expect("Failed to parse EML document")
+    }
+
+    #[test]
+    fn test_consistent_package() {
+        let package = ElectionPackage::from_documents([
+            parse(include_str!(
+                "../../test-emls/election_definition/eml110a_test.eml.xml"
+            )),
+            parse(include_str!(
+                "../../test-emls/polling_stations/eml110b_test.eml.xml"
+            )),
+            parse(include_str!(
+                "../../test-emls/candidate_list/eml230b_test.eml.xml"
+            )),
+        ]);
+        assert!(package.election_definition.is_some());
+        assert!(package.polling_stations.is_some());
+        assert!(package.candidate_lists.is_some());
+        // The test candidate lists contain one list that is not a registered party
+        assert_eq!(
+            package.validate(),
+            vec![ValidationFinding::UnregisteredAffiliation(
+                "Lijst van de Kandidaten".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn test_inconsistent_package() {
+        let package = ElectionPackage::from_documents([
+            parse(include_str!(
+                "../../test-emls/election_definition/eml110a_test.eml.xml"
+            )),
+            parse(include_str!(
+                "../../test-emls/polling_stations/eml110b_not_matching_election_id.eml.xml"
+            )),
+            parse(include_str!(
+                "../../test-emls/candidate_list/eml230b_invalid_incorrect_election_domain.eml.xml"
+            )),
+            parse(include_str!("../../test-emls/eml520_test.eml.xml")),
+        ]);
+        let findings = package.validate();
+        assert!(findings.contains(&ValidationFinding::ElectionMismatch(
+            "ElectionIdentifier".to_string(),
+            "110a".to_string(),
+            "GR2022_Test".to_string(),
+            "110b".to_string(),
+            "EP2024".to_string(),
+        )));
+        assert!(findings.contains(&ValidationFinding::ElectionMismatch(
+            "ElectionCategory".to_string(),
+            "110a".to_string(),
+            "GR".to_string(),
+            "110b".to_string(),
+            "EP".to_string(),
+        )));
+        assert!(findings.contains(&ValidationFinding::ElectionMismatch(
+            "ElectionDomain".to_string(),
+            "110a".to_string(),
+            "0000".to_string(),
+            "230b".to_string(),
+            "0001".to_string(),
+        )));
+        assert!(
+            findings.contains(&ValidationFinding::UnregisteredAffiliation(
+                "Lijst van de Kandidaten".to_string()
+            ))
+        );
+        assert!(
+            findings.contains(&ValidationFinding::UnexpectedPackageDocument(
+                "520".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_package_missing_election_domain() {
+        let package = ElectionPackage::from_documents([
+            parse(include_str!(
+                "../../test-emls/election_definition/eml110a_invalid_election_missing_election_domain.eml.xml"
+            )),
+            parse(include_str!(
+                "../../test-emls/candidate_list/eml230b_test.eml.xml"
+            )),
+        ]);
+        assert!(
+            package
+                .validate()
+                .contains(&ValidationFinding::ElectionValueMissing(
+                    "ElectionDomain".to_string(),
+                    "230b".to_string(),
+                    "0000".to_string(),
+                    "110a".to_string(),
+                ))
+        );
+
+        let package = ElectionPackage::from_documents([
+            parse(include_str!(
+                "../../test-emls/election_definition/eml110a_test.eml.xml"
+            )),
+            parse(include_str!(
+                "../../test-emls/candidate_list/eml230b_invalid_missing_election_domain.eml.xml"
+            )),
+        ]);
+        assert!(
+            package
+                .validate()
+                .contains(&ValidationFinding::ElectionValueMissing(
+                    "ElectionDomain".to_string(),
+                    "110a".to_string(),
+                    "0000".to_string(),
+                    "230b".to_string(),
+                ))
+        );
+    }
+}
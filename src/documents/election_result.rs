@@ -0,0 +1,398 @@
+//! Document variant for the EML_NL Election Result (`520`) document.
+
+use std::num::NonZeroU64;
+
+use crate::{
+    EML_SCHEMA_VERSION, EMLError, NS_EML,
+    common::{
+        AffiliationIdentifier, CandidateIdentifier, CanonicalizationMethod, ContestIdentifier,
+        CreationDateTime, IssueDate, ManagingAuthority, PersonNameStructure, Signature,
+        TransactionId,
+    },
+    documents::{
+        accepted_root, candidate_lists::QualifyingAddress, count::CountElectionIdentifier,
+    },
+    error::{EMLErrorKind, EMLResultExt},
+    io::{
+        EMLElement, EMLElementReader, EMLElementWriter, EMLReadElement as _, Extensions,
+        QualifiedName, collect_struct, write_eml_element,
+    },
+    utils::{GenderType, StringValue, XsDate, YesNoType},
+};
+
+pub(crate) const EML_ELECTION_RESULT_ID: &str = "520";
+
+/// Representing a `520` document, containing the result of an election.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ElectionResult {
+    /// Transaction id of the document.
+    pub transaction_id: TransactionId,
+
+    /// Managing authority of the document.
+    pub managing_authority: ManagingAuthority,
+
+    /// Issue date of the document, if present.
+    pub issue_date: Option<IssueDate>,
+
+    /// Creation date and time of the document.
+    pub creation_date_time: CreationDateTime,
+
+    /// Canonicalization method used in this document, if present.
+    pub canonicalization_method: Option<CanonicalizationMethod>,
+
+    /// The result contained in this document.
+    pub result: ElectionResultResult,
+
+    /// Enveloped signature of this document, if present.
+    pub signature: Option<Signature>,
+
+    /// Unknown attributes and child elements, see [`Extensions`].
+    pub extensions: Extensions,
+}
+
+impl EMLElement for ElectionResult {
+    const EML_NAME: QualifiedName<'_, '_> = QualifiedName::from_static("EML", Some(NS_EML));
+
+    fn read_eml(elem: &mut EMLElementReader<'_, '_>) -> Result<Self, EMLError> {
+        accepted_root(elem)?;
+
+        let (document_id, id_span) = elem.attribute_value_req_with_span(("Id", None))?;
+        if document_id != EML_ELECTION_RESULT_ID {
+            return Err(EMLErrorKind::InvalidDocumentType(
+                EML_ELECTION_RESULT_ID,
+                document_id.to_string(),
+            ))
+            .with_span(id_span);
+        }
+
+        Ok(collect_struct!(elem, ElectionResult {
+            transaction_id: TransactionId::EML_NAME => |elem| TransactionId::read_eml(elem)?,
+            managing_authority: ManagingAuthority::EML_NAME => |elem| ManagingAuthority::read_eml(elem)?,
+            issue_date as Option: IssueDate::EML_NAME => |elem| IssueDate::read_eml(elem)?,
+            creation_date_time: CreationDateTime::EML_NAME => |elem| CreationDateTime::read_eml(elem)?,
+            canonicalization_method as Option: CanonicalizationMethod::EML_NAME => |elem| CanonicalizationMethod::read_eml(elem)?,
+            result: ElectionResultResult::EML_NAME => |elem| ElectionResultResult::read_eml(elem)?,
+            signature as Option: Signature::EML_NAME => |elem| Signature::read_eml(elem)?,
+            extensions as Extensions,
+        }))
+    }
+
+    fn write_eml(&self, writer: EMLElementWriter) -> Result<(), EMLError> {
+        writer
+            .extensions(&self.extensions)?
+            .attr(("Id", None), EML_ELECTION_RESULT_ID)?
+            .attr(("SchemaVersion", None), EML_SCHEMA_VERSION)?
+            .child_elem(TransactionId::EML_NAME, &self.transaction_id)?
+            .child_elem(ManagingAuthority::EML_NAME, &self.managing_authority)?
+            .child_elem_option(IssueDate::EML_NAME, self.issue_date.as_ref())?
+            .child_elem(CreationDateTime::EML_NAME, &self.creation_date_time)?
+            .canonicalization_method(self.canonicalization_method.is_some())?
+            .child_elem(ElectionResultResult::EML_NAME, &self.result)?
+            .child_elem_option(Signature::EML_NAME, self.signature.as_ref())?
+            .finish()?;
+
+        Ok(())
+    }
+}
+
+/// The result element, containing the elected affiliations and candidates.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ElectionResultResult {
+    /// The election the result belongs to.
+    pub election: ElectionResultElection,
+    /// Unknown attributes and child elements, see [`Extensions`].
+    pub extensions: Extensions,
+}
+
+impl EMLElement for ElectionResultResult {
+    const EML_NAME: QualifiedName<'_, '_> = QualifiedName::from_static("Result", Some(NS_EML));
+
+    fn read_eml(elem: &mut EMLElementReader<'_, '_>) -> Result<Self, EMLError> {
+        Ok(collect_struct!(elem, ElectionResultResult {
+            election: ElectionResultElection::EML_NAME => |elem| ElectionResultElection::read_eml(elem)?,
+            extensions as Extensions,
+        }))
+    }
+
+    fn write_eml(&self, writer: EMLElementWriter) -> Result<(), EMLError> {
+        writer
+            .extensions(&self.extensions)?
+            .child_elem(ElectionResultElection::EML_NAME, &self.election)?
+            .finish()
+    }
+}
+
+/// The election the result belongs to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ElectionResultElection {
+    /// Identifier of the election.
+    pub identifier: CountElectionIdentifier,
+
+    /// The contests in the election.
+    pub contests: Vec<ElectionResultContest>,
+
+    /// Unknown attributes and child elements, see [`Extensions`].
+    pub extensions: Extensions,
+}
+
+impl EMLElement for ElectionResultElection {
+    const EML_NAME: QualifiedName<'_, '_> = QualifiedName::from_static("Election", Some(NS_EML));
+
+    fn read_eml(elem: &mut EMLElementReader<'_, '_>) -> Result<Self, EMLError> {
+        Ok(collect_struct!(elem, ElectionResultElection {
+            identifier: CountElectionIdentifier::EML_NAME => |elem| CountElectionIdentifier::read_eml(elem)?,
+            contests as Vec: ElectionResultContest::EML_NAME => |elem| ElectionResultContest::read_eml(elem)?,
+            extensions as Extensions,
+        }))
+    }
+
+    fn write_eml(&self, writer: EMLElementWriter) -> Result<(), EMLError> {
+        let mut writer = writer
+            .extensions(&self.extensions)?
+            .child_elem(CountElectionIdentifier::EML_NAME, &self.identifier)?;
+        for contest in &self.contests {
+            writer = writer.child_elem(ElectionResultContest::EML_NAME, contest)?;
+        }
+        writer.finish()
+    }
+}
+
+/// A contest in the election result.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ElectionResultContest {
+    /// Identifier of the contest.
+    pub identifier: ContestIdentifier,
+
+    /// The selected affiliations and candidates.
+    pub selections: Vec<ElectionResultSelection>,
+
+    /// Unknown attributes and child elements, see [`Extensions`].
+    pub extensions: Extensions,
+}
+
+impl EMLElement for ElectionResultContest {
+    const EML_NAME: QualifiedName<'_, '_> = QualifiedName::from_static("Contest", Some(NS_EML));
+
+    fn read_eml(elem: &mut EMLElementReader<'_, '_>) -> Result<Self, EMLError> {
+        Ok(collect_struct!(elem, ElectionResultContest {
+            identifier: ContestIdentifier::EML_NAME => |elem| ContestIdentifier::read_eml(elem)?,
+            selections as Vec: ElectionResultSelection::EML_NAME => |elem| ElectionResultSelection::read_eml(elem)?,
+            extensions as Extensions,
+        }))
+    }
+
+    fn write_eml(&self, writer: EMLElementWriter) -> Result<(), EMLError> {
+        let mut writer = writer
+            .extensions(&self.extensions)?
+            .child_elem(ContestIdentifier::EML_NAME, &self.identifier)?;
+        for selection in &self.selections {
+            writer = writer.child_elem(ElectionResultSelection::EML_NAME, selection)?;
+        }
+        writer.finish()
+    }
+}
+
+/// A selected affiliation or candidate in the election result.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ElectionResultSelection {
+    /// The affiliation or candidate that was selected.
+    pub selected: ElectionResultSelected,
+
+    /// The ranking of the candidate, if present.
+    pub ranking: Option<StringValue<NonZeroU64>>,
+
+    /// Whether the affiliation or candidate was elected.
+    pub elected: StringValue<YesNoType>,
+
+    /// Unknown attributes and child elements, see [`Extensions`].
+    pub extensions: Extensions,
+}
+
+/// The affiliation or candidate that was selected in the election result.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ElectionResultSelected {
+    /// An affiliation.
+    Affiliation(AffiliationIdentifier),
+    /// A candidate.
+    Candidate(Box<ElectionResultCandidate>),
+}
+
+struct ElectionResultSelectionInternal {
+    affiliation: Option<AffiliationIdentifier>,
+    candidate: Option<ElectionResultCandidate>,
+    ranking: Option<StringValue<NonZeroU64>>,
+    elected: StringValue<YesNoType>,
+    /// Unknown attributes and child elements, see [`Extensions`].
+    extensions: Extensions,
+}
+
+impl EMLElement for ElectionResultSelection {
+    const EML_NAME: QualifiedName<'_, '_> = QualifiedName::from_static("Selection", Some(NS_EML));
+
+    fn read_eml(elem: &mut EMLElementReader<'_, '_>) -> Result<Self, EMLError> {
+        let data = collect_struct!(elem, ElectionResultSelectionInternal {
+            affiliation as Option: AffiliationIdentifier::EML_NAME => |elem| AffiliationIdentifier::read_eml(elem)?,
+            candidate as Option: ElectionResultCandidate::EML_NAME => |elem| ElectionResultCandidate::read_eml(elem)?,
+            ranking as Option: ("Ranking", NS_EML) => |elem| elem.string_value()?,
+            elected: ("Elected", NS_EML) => |elem| elem.string_value()?,
+            extensions as Extensions,
+        });
+
+        let selected = match (data.affiliation, data.candidate) {
+            (Some(affiliation), None) => ElectionResultSelected::Affiliation(affiliation),
+            (None, Some(candidate)) => ElectionResultSelected::Candidate(Box::new(candidate)),
+            (Some(affiliation), Some(_)) => {
+                let err = EMLErrorKind::UnexpectedElement(
+                    ElectionResultCandidate::EML_NAME.as_owned(),
+                    ElectionResultSelection::EML_NAME.as_owned(),
+                )
+                .add_span(elem.span());
+                if elem.parsing_mode().is_strict() {
+                    return Err(err);
+                } else {
+                    elem.push_err(err);
+                    ElectionResultSelected::Affiliation(affiliation)
+                }
+            }
+            (None, None) => {
+                return Err(EMLErrorKind::MissingChoiceElements(vec![
+                    AffiliationIdentifier::EML_NAME.as_owned(),
+                    ElectionResultCandidate::EML_NAME.as_owned(),
+                ])
+                .add_span(elem.span()));
+            }
+        };
+
+        Ok(ElectionResultSelection {
+            selected,
+            ranking: data.ranking,
+            elected: data.elected,
+            extensions: data.extensions,
+        })
+    }
+
+    fn write_eml(&self, writer: EMLElementWriter) -> Result<(), EMLError> {
+        let writer = writer.extensions(&self.extensions)?;
+        match &self.selected {
+            ElectionResultSelected::Affiliation(affiliation) => {
+                writer.child_elem(AffiliationIdentifier::EML_NAME, affiliation)?
+            }
+            ElectionResultSelected::Candidate(candidate) => {
+                writer.child_elem(ElectionResultCandidate::EML_NAME, candidate.as_ref())?
+            }
+        }
+        .child_option(("Ranking", NS_EML), self.ranking.as_ref(), |elem, value| {
+            elem.text(value.raw().as_ref())?.finish()
+        })?
+        .child(("Elected", NS_EML), |elem| {
+            elem.text(self.elected.raw().as_ref())?.finish()
+        })?
+        .finish()
+    }
+}
+
+/// A candidate in the election result.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ElectionResultCandidate {
+    /// The candidate identifier.
+    pub identifier: CandidateIdentifier,
+
+    /// The full name of the candidate.
+    pub full_name: PersonNameStructure,
+
+    /// The date of birth of the candidate, if present.
+    pub date_of_birth: Option<StringValue<XsDate>>,
+
+    /// The gender of the candidate, if present.
+    pub gender: Option<StringValue<GenderType>>,
+
+    /// The qualifying address of the candidate, if present.
+    pub qualifying_address: Option<QualifyingAddress>,
+
+    /// Unknown attributes and child elements, see [`Extensions`].
+    pub extensions: Extensions,
+}
+
+impl EMLElement for ElectionResultCandidate {
+    const EML_NAME: QualifiedName<'_, '_> = QualifiedName::from_static("Candidate", Some(NS_EML));
+
+    fn read_eml(elem: &mut EMLElementReader<'_, '_>) -> Result<Self, EMLError> {
+        Ok(collect_struct!(elem, ElectionResultCandidate {
+            identifier: CandidateIdentifier::EML_NAME => |elem| CandidateIdentifier::read_eml(elem)?,
+            full_name: ("CandidateFullName", NS_EML) => |elem| PersonNameStructure::read_eml_element(elem)?,
+            date_of_birth as Option: ("DateOfBirth", NS_EML) => |elem| elem.string_value()?,
+            gender as Option: ("Gender", NS_EML) => |elem| elem.string_value()?,
+            qualifying_address as Option: QualifyingAddress::EML_NAME => |elem| QualifyingAddress::read_eml(elem)?,
+            extensions as Extensions,
+        }))
+    }
+
+    fn write_eml(&self, writer: EMLElementWriter) -> Result<(), EMLError> {
+        writer
+            .extensions(&self.extensions)?
+            .child_elem(CandidateIdentifier::EML_NAME, &self.identifier)?
+            .child(
+                ("CandidateFullName", NS_EML),
+                write_eml_element(&self.full_name),
+            )?
+            .child_option(
+                ("DateOfBirth", NS_EML),
+                self.date_of_birth.as_ref(),
+                |elem, value| elem.text(value.raw().as_ref())?.finish(),
+            )?
+            .child_option(("Gender", NS_EML), self.gender.as_ref(), |elem, value| {
+                elem.text(value.raw().as_ref())?.finish()
+            })?
+            .child_elem_option(
+                QualifyingAddress::EML_NAME,
+                self.qualifying_address.as_ref(),
+            )?
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::io::{EMLParsingMode, EMLRead as _, EMLWrite as _};
+
+    use super::*;
+
+    #[test]
+    fn test_election_result_round_trip() {
+        let doc = include_str!("../../test-emls/eml520_test.eml.xml");
+        let result = ElectionResult::parse_eml(doc, EMLParsingMode::Strict)
+            .ok()
+            .expect("Failed to parse EML document");
+
+        let contest = &result.result.election.contests[0];
+        assert_eq!(contest.selections.len(), 13);
+        let ElectionResultSelected::Candidate(candidate) = &contest.selections[1].selected else {
+            panic!("Expected a candidate selection");
+        };
+        assert!(candidate.qualifying_address.is_some());
+        assert_eq!(
+            contest.selections[5].ranking.as_ref().map(|r| r.raw()),
+            Some("2".into())
+        );
+
+        let written = result
+            .write_eml_root_str(true, true)
+            .expect("Failed to write EML document");
+        let reparsed = ElectionResult::parse_eml(&written, EMLParsingMode::Strict)
+            .ok()
+            .expect("Failed to parse written EML document");
+        assert_eq!(
+            reparsed
+                .write_eml_root_str(true, true)
+                .expect("Failed to write EML document"),
+            written
+        );
+    }
+}
@@ -4,28 +4,53 @@ use crate::{
     EML_SCHEMA_VERSION, EMLError, EMLErrorKind, EMLResultExt as _, NS_EML,
     documents::{
         candidate_lists::{CandidateLists, EML_CANDIDATE_LISTS_ID},
+        count::{
+            Count, CountLevel, EML_COUNT_ID, EML_POLLING_STATION_COUNT_ID,
+            EML_PRINCIPAL_COMMITTEE_COUNT_ID,
+        },
         election_definition::{EML_ELECTION_DEFINITION_ID, ElectionDefinition},
+        election_result::{EML_ELECTION_RESULT_ID, ElectionResult},
+        nomination::{EML_NOMINATION_ID, Nomination},
         polling_stations::{EML_POLLING_STATIONS_ID, PollingStations},
+        totals::{EML_TOTALS_ID, Totals},
     },
     io::{EMLElement, EMLElementReader, EMLElementWriter, QualifiedName},
 };
 
 pub mod candidate_lists;
+pub mod count;
 pub mod election_definition;
+pub mod election_package;
+pub mod election_result;
+pub mod nomination;
 pub mod polling_stations;
+pub mod totals;
 
 /// Generic EML document that can represent any of the supported EML variants.
 ///
 /// You can use this struct to parse an EML document of any variant if you don't
 /// know in advance which variant you will receive.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum EML {
     /// Representing a `110a` document, containing an election definition.
     ElectionDefinition(Box<ElectionDefinition>),
     /// Representing a `110b` document, containing polling stations.
     PollingStations(Box<PollingStations>),
+    /// Representing a `210` document, containing the nomination of a candidate list.
+    Nomination(Box<Nomination>),
     /// Representing a `230b` document, containing a candidate list.
     CandidateLists(Box<CandidateLists>),
+    /// Representing a `510a` document, containing the count of a polling station.
+    PollingStationCount(Box<Count>),
+    /// Representing a `510b` document, containing the count of a municipality.
+    Count(Box<Count>),
+    /// Representing a `510c` document, containing the count of a principal electoral committee.
+    PrincipalCommitteeCount(Box<Count>),
+    /// Representing a `510d` document, containing the final totals of an election.
+    Totals(Box<Totals>),
+    /// Representing a `520` document, containing the result of an election.
+    ElectionResult(Box<ElectionResult>),
 }
 
 impl EML {
@@ -34,7 +59,13 @@ impl EML {
         match self {
             EML::ElectionDefinition(_) => EML_ELECTION_DEFINITION_ID,
             EML::PollingStations(_) => EML_POLLING_STATIONS_ID,
+            EML::Nomination(_) => EML_NOMINATION_ID,
             EML::CandidateLists(_) => EML_CANDIDATE_LISTS_ID,
+            EML::PollingStationCount(_) => EML_POLLING_STATION_COUNT_ID,
+            EML::Count(_) => EML_COUNT_ID,
+            EML::PrincipalCommitteeCount(_) => EML_PRINCIPAL_COMMITTEE_COUNT_ID,
+            EML::Totals(_) => EML_TOTALS_ID,
+            EML::ElectionResult(_) => EML_ELECTION_RESULT_ID,
         }
     }
 
@@ -43,7 +74,13 @@ impl EML {
         match self {
             EML::ElectionDefinition(_) => "Election Definition",
             EML::PollingStations(_) => "Polling Stations",
+            EML::Nomination(_) => "Nomination",
             EML::CandidateLists(_) => "Candidate List",
+            EML::PollingStationCount(_) => "Polling Station Count",
+            EML::Count(_) => "Count",
+            EML::PrincipalCommitteeCount(_) => "Principal Committee Count",
+            EML::Totals(_) => "Totals",
+            EML::ElectionResult(_) => "Election Result",
         }
     }
 
@@ -83,6 +120,24 @@ impl EML {
         }
     }
 
+    /// Create a generic EML document from a Nomination (`210`) document.
+    pub fn from_nomination_doc(n: Nomination) -> Self {
+        EML::Nomination(Box::new(n))
+    }
+
+    /// Check if this EML document is a Nomination (`210`) document.
+    pub fn is_nomination_doc(&self) -> bool {
+        matches!(self, EML::Nomination(_))
+    }
+
+    /// Get a reference to this EML document as a Nomination (`210`) document, if possible.
+    pub fn as_nomination_doc(&self) -> Option<&Nomination> {
+        match self {
+            EML::Nomination(n) => Some(n),
+            _ => None,
+        }
+    }
+
     /// Create a generic EML document from a Candidate Lists (`230b`) document.
     pub fn from_candidate_lists_doc(cl: CandidateLists) -> Self {
         EML::CandidateLists(Box::new(cl))
@@ -100,6 +155,83 @@ impl EML {
             _ => None,
         }
     }
+
+    /// Create a generic EML document from a Count (`510a`, `510b`, `510c` or `510d`) document.
+    ///
+    /// The variant is chosen based on the [`CountLevel`] of the count.
+    pub fn from_count_doc(c: Count) -> Self {
+        match c.level {
+            CountLevel::PollingStation => EML::PollingStationCount(Box::new(c)),
+            CountLevel::Municipality => EML::Count(Box::new(c)),
+            CountLevel::PrincipalCommittee => EML::PrincipalCommitteeCount(Box::new(c)),
+            CountLevel::CentralCommittee => EML::Totals(Box::new(c)),
+        }
+    }
+
+    /// Check if this EML document is a Count (`510a`, `510b`, `510c` or `510d`) document.
+    pub fn is_count_doc(&self) -> bool {
+        self.count_level().is_some()
+    }
+
+    /// Get a reference to this EML document as a Count (`510a`, `510b`, `510c` or `510d`) document, if possible.
+    pub fn as_count_doc(&self) -> Option<&Count> {
+        match self {
+            EML::PollingStationCount(c)
+            | EML::Count(c)
+            | EML::PrincipalCommitteeCount(c)
+            | EML::Totals(c) => Some(c),
+            _ => None,
+        }
+    }
+
+    /// Get the level of the count if this EML document is any of the `510` documents.
+    ///
+    /// The level is determined by the document variant.
+    pub fn count_level(&self) -> Option<CountLevel> {
+        match self {
+            EML::PollingStationCount(_) => Some(CountLevel::PollingStation),
+            EML::Count(_) => Some(CountLevel::Municipality),
+            EML::PrincipalCommitteeCount(_) => Some(CountLevel::PrincipalCommittee),
+            EML::Totals(_) => Some(CountLevel::CentralCommittee),
+            _ => None,
+        }
+    }
+
+    /// Create a generic EML document from a Totals (`510d`) document.
+    pub fn from_totals_doc(t: Totals) -> Self {
+        EML::Totals(Box::new(t))
+    }
+
+    /// Check if this EML document is a Totals (`510d`) document.
+    pub fn is_totals_doc(&self) -> bool {
+        matches!(self, EML::Totals(_))
+    }
+
+    /// Get a reference to this EML document as a Totals (`510d`) document, if possible.
+    pub fn as_totals_doc(&self) -> Option<&Totals> {
+        match self {
+            EML::Totals(t) => Some(t),
+            _ => None,
+        }
+    }
+
+    /// Create a generic EML document from an Election Result (`520`) document.
+    pub fn from_election_result_doc(er: ElectionResult) -> Self {
+        EML::ElectionResult(Box::new(er))
+    }
+
+    /// Check if this EML document is an Election Result (`520`) document.
+    pub fn is_election_result_doc(&self) -> bool {
+        matches!(self, EML::ElectionResult(_))
+    }
+
+    /// Get a reference to this EML document as an Election Result (`520`) document, if possible.
+    pub fn as_election_result_doc(&self) -> Option<&ElectionResult> {
+        match self {
+            EML::ElectionResult(er) => Some(er),
+            _ => None,
+        }
+    }
 }
 
 impl EMLElement for EML {
@@ -108,7 +240,7 @@ impl EMLElement for EML {
     fn read_eml(elem: &mut EMLElementReader<'_, '_>) -> Result<Self, EMLError> {
         accepted_root(elem)?;
 
-        let document_id = elem.attribute_value_req(("Id", None))?;
+        let (document_id, id_span) = elem.attribute_value_req_with_span(("Id", None))?;
         Ok(match document_id.as_ref() {
             EML_ELECTION_DEFINITION_ID => {
                 EML::ElectionDefinition(Box::new(ElectionDefinition::read_eml(elem)?))
@@ -116,12 +248,24 @@ impl EMLElement for EML {
             EML_POLLING_STATIONS_ID => {
                 EML::PollingStations(Box::new(PollingStations::read_eml(elem)?))
             }
+            EML_NOMINATION_ID => EML::Nomination(Box::new(Nomination::read_eml(elem)?)),
             EML_CANDIDATE_LISTS_ID => {
                 EML::CandidateLists(Box::new(CandidateLists::read_eml(elem)?))
             }
+            EML_POLLING_STATION_COUNT_ID => {
+                EML::PollingStationCount(Box::new(Count::read_eml(elem)?))
+            }
+            EML_COUNT_ID => EML::Count(Box::new(Count::read_eml(elem)?)),
+            EML_PRINCIPAL_COMMITTEE_COUNT_ID => {
+                EML::PrincipalCommitteeCount(Box::new(Count::read_eml(elem)?))
+            }
+            EML_TOTALS_ID => EML::Totals(Box::new(Totals::read_eml(elem)?)),
+            EML_ELECTION_RESULT_ID => {
+                EML::ElectionResult(Box::new(ElectionResult::read_eml(elem)?))
+            }
             _ => {
                 return Err(EMLErrorKind::UnknownDocumentType(document_id.to_string()))
-                    .with_span(elem.span());
+                    .with_span(id_span);
             }
         })
     }
@@ -130,7 +274,15 @@ impl EMLElement for EML {
         match self {
             EML::ElectionDefinition(ed) => ed.write_eml(writer),
             EML::PollingStations(ps) => ps.write_eml(writer),
+            EML::Nomination(n) => n.write_eml(writer),
             EML::CandidateLists(cl) => cl.write_eml(writer),
+            EML::PollingStationCount(c) => c.write_eml_at_level(CountLevel::PollingStation, writer),
+            EML::Count(c) => c.write_eml_at_level(CountLevel::Municipality, writer),
+            EML::PrincipalCommitteeCount(c) => {
+                c.write_eml_at_level(CountLevel::PrincipalCommittee, writer)
+            }
+            EML::Totals(t) => t.write_eml_at_level(CountLevel::CentralCommittee, writer),
+            EML::ElectionResult(er) => er.write_eml(writer),
         }
     }
 }
@@ -140,20 +292,24 @@ fn accepted_root(elem: &EMLElementReader<'_, '_>) -> Result<(), EMLError> {
         return Err(EMLErrorKind::InvalidRootElement).with_span(elem.span());
     }
 
-    let schema_version = elem.attribute_value_req(("SchemaVersion", None))?;
+    let (schema_version, version_span) =
+        elem.attribute_value_req_with_span(("SchemaVersion", None))?;
     if schema_version == EML_SCHEMA_VERSION {
         Ok(())
     } else {
         Err(EMLErrorKind::SchemaVersionNotSupported(
             schema_version.to_string(),
         ))
-        .with_span(elem.span())
+        .with_span(version_span)
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::io::{EMLParsingMode, EMLRead as _, EMLWrite as _};
+    use crate::io::{
+        Annotation, AnnotationKind, CanonicalizationAlgorithm, EMLParsingMode, EMLRead as _,
+        EMLReadOptions, EMLWrite as _, canonicalize,
+    };
 
     use super::*;
 
@@ -176,6 +332,353 @@ mod tests {
             .ok()
             .expect("Failed to parse EML document");
         assert!(matches!(eml, EML::PollingStations(_)));
+
+        let doc = include_str!("../../test-emls/nomination/eml210_test.eml.xml");
+        let eml = EML::parse_eml(doc, EMLParsingMode::Strict)
+            .ok()
+            .expect("Failed to parse EML document");
+        assert!(matches!(eml, EML::Nomination(_)));
+
+        let doc = include_str!("../../test-emls/deserialize_eml510b_test.eml.xml");
+        let eml = EML::parse_eml(doc, EMLParsingMode::Strict)
+            .ok()
+            .expect("Failed to parse EML document");
+        assert!(matches!(eml, EML::Count(_)));
+        assert_eq!(eml.to_friendly_name(), "Count");
+
+        let doc = doc.replace(r#"Id="510b""#, r#"Id="510c""#);
+        let eml = EML::parse_eml(&doc, EMLParsingMode::Strict)
+            .ok()
+            .expect("Failed to parse EML document");
+        assert!(matches!(eml, EML::PrincipalCommitteeCount(_)));
+        assert_eq!(eml.count_level(), Some(CountLevel::PrincipalCommittee));
+        assert_eq!(eml.to_eml_id(), "510c");
+        assert_eq!(eml.to_friendly_name(), "Principal Committee Count");
+
+        let doc = include_str!("../../test-emls/deserialize_eml510d_test.eml.xml");
+        let eml = EML::parse_eml(doc, EMLParsingMode::Strict)
+            .ok()
+            .expect("Failed to parse EML document");
+        assert!(matches!(eml, EML::Totals(_)));
+        assert_eq!(eml.count_level(), Some(CountLevel::CentralCommittee));
+        assert_eq!(eml.to_eml_id(), "510d");
+        assert_eq!(eml.to_friendly_name(), "Totals");
+
+        let doc = include_str!("../../test-emls/eml520_test.eml.xml");
+        let eml = EML::parse_eml(doc, EMLParsingMode::Strict)
+            .ok()
+            .expect("Failed to parse EML document");
+        assert!(matches!(eml, EML::ElectionResult(_)));
+    }
+
+    #[test]
+    fn test_preserve_unknown_round_trip() {
+        let doc = include_str!("../../test-emls/polling_stations/eml110b_test.eml.xml")
+            .replacen("<EML ", r#"<EML xmlns:ext="urn:example:extension" "#, 1)
+            .replacen("<Election>", r#"<Election ext:flag="yes">"#, 1)
+            .replacen(
+                "<kr:ElectionDate>",
+                r#"<ext:Note ext:lang="nl">Extra <ext:b>info</ext:b></ext:Note><kr:ElectionDate>"#,
+                1,
+            )
+            .replacen(
+                "<AuthorityAddress/>",
+                "<AuthorityAddress><ext:Street>Dorpsstraat</ext:Street></AuthorityAddress>",
+                1,
+            );
+        let options =
+            EMLReadOptions::new(EMLParsingMode::StrictFallback).with_preserve_unknown(true);
+
+        let (eml, errors) = EML::parse_eml(&doc, options)
+            .ok_with_errors()
+            .expect("Failed to parse EML document");
+        // The fixture already contains a NominationDate that is not part of this document
+        assert_eq!(errors.len(), 3);
+        let EML::PollingStations(polling_stations) = &eml else {
+            panic!("Expected a polling stations document");
+        };
+        let election = &polling_stations.election_event.election;
+        assert_eq!(election.extensions.attributes.len(), 1);
+        assert_eq!(election.extensions.attributes[0].value, "yes");
+        let identifier_extensions = &election.identifier.extensions;
+        assert_eq!(identifier_extensions.elements.len(), 2);
+        assert_eq!(identifier_extensions.elements[0].position, 4);
+        assert_eq!(
+            identifier_extensions.elements[0].namespaces,
+            vec![(Some("ext".to_string()), "urn:example:extension".to_string())]
+        );
+        assert_eq!(
+            polling_stations
+                .managing_authority
+                .authority_address
+                .extensions
+                .elements
+                .len(),
+            1
+        );
+
+        let written = eml
+            .write_eml_root_str(false, false)
+            .expect("Failed to write EML document");
+        assert!(written.contains(
+            r#"<ext:Note xmlns:ext="urn:example:extension" ext:lang="nl">Extra <ext:b>info</ext:b></ext:Note><kr:ElectionDate>"#
+        ));
+        let reparsed = EML::parse_eml(&written, options)
+            .ok_with_errors()
+            .expect("Failed to parse written EML document")
+            .0;
+        assert_eq!(reparsed.write_eml_root_str(false, false).unwrap(), written);
+
+        // Without the option nothing is preserved
+        let eml = EML::parse_eml(&doc, EMLParsingMode::StrictFallback)
+            .ok()
+            .expect("Failed to parse EML document");
+        let EML::PollingStations(polling_stations) = &eml else {
+            panic!("Expected a polling stations document");
+        };
+        assert!(
+            polling_stations
+                .election_event
+                .election
+                .extensions
+                .is_empty()
+        );
+        assert!(
+            polling_stations
+                .election_event
+                .election
+                .identifier
+                .extensions
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_preserve_unknown_around_canonicalization_method() {
+        let options = EMLReadOptions::new(EMLParsingMode::Strict).with_preserve_unknown(true);
+        let extension = r#"<x:Ext xmlns:x="urn:example:extension"/>"#;
+
+        // The CanonicalizationMethod is read, but not written for regular output
+        let doc = include_str!("../../test-emls/eml520_test.eml.xml").replacen(
+            "<Result>",
+            &format!("{extension}<Result>"),
+            1,
+        );
+        let eml = EML::parse_eml(&doc, options)
+            .ok()
+            .expect("Failed to parse EML document");
+        let written = eml.write_eml_root_str(false, false).unwrap();
+        assert!(!written.contains("CanonicalizationMethod"));
+        assert!(
+            written.contains(&format!("</kr:CreationDateTime>{extension}<Result>")),
+            "Extension moved in {written}"
+        );
+
+        // The CanonicalizationMethod is not read, but written for canonical output
+        let doc = include_str!("../../test-emls/eml520_test.eml.xml")
+            .replacen("</Result>", &format!("</Result>{extension}"), 1)
+            .replacen(
+                r#"<ds:CanonicalizationMethod
+        Algorithm="http://www.w3.org/TR/2001/REC-xml-c14n-20010315#WithComments"></ds:CanonicalizationMethod>"#,
+                "",
+                1,
+            );
+        let eml = EML::parse_eml(&doc, options)
+            .ok()
+            .expect("Failed to parse EML document");
+        let written = eml
+            .write_eml_root_canonical_str(CanonicalizationAlgorithm::C14N)
+            .unwrap();
+        assert!(written.contains("CanonicalizationMethod"));
+        assert!(
+            written.contains(r#"</Result><x:Ext xmlns:x="urn:example:extension"></x:Ext></EML>"#),
+            "Extension moved in {written}"
+        );
+    }
+
+    #[test]
+    fn test_preserve_comments_round_trip() {
+        let doc = include_str!("../../test-emls/polling_stations/eml110b_test.eml.xml")
+            .replacen(
+                "<TransactionId>",
+                "<!-- Corrected by the municipality --><TransactionId>",
+                1,
+            )
+            .replacen(
+                "<AuthorityAddress/>",
+                "<AuthorityAddress><!-- No address --></AuthorityAddress>",
+                1,
+            )
+            .replacen(
+                "<kr:ElectionDate>",
+                r#"<?review status="done"?><ext:Note xmlns:ext="urn:example:extension"/><!-- After note --><kr:ElectionDate>"#,
+                1,
+            )
+            .replacen("</Contest>", "<!-- End of contest --></Contest>", 1);
+        let options = EMLReadOptions::new(EMLParsingMode::StrictFallback)
+            .with_preserve_unknown(true)
+            .with_preserve_comments(true);
+
+        let eml = EML::parse_eml(&doc, options)
+            .ok()
+            .expect("Failed to parse EML document");
+        let EML::PollingStations(polling_stations) = &eml else {
+            panic!("Expected a polling stations document");
+        };
+        assert_eq!(
+            polling_stations.extensions.annotations,
+            vec![Annotation {
+                kind: AnnotationKind::Comment,
+                content: " Corrected by the municipality ".to_string(),
+                position: 0,
+                preceding_elements: 0,
+            }]
+        );
+        let annotations = &polling_stations
+            .election_event
+            .election
+            .identifier
+            .extensions
+            .annotations;
+        assert_eq!(annotations.len(), 2);
+        assert_eq!(annotations[0].kind, AnnotationKind::ProcessingInstruction);
+        assert_eq!(annotations[0].content, r#"review status="done""#);
+        assert_eq!(annotations[1].preceding_elements, 1);
+
+        let written = eml
+            .write_eml_root_str(false, false)
+            .expect("Failed to write EML document");
+        for expected in [
+            "><!-- Corrected by the municipality --><TransactionId>",
+            "<AuthorityAddress><!-- No address --></AuthorityAddress>",
+            r#"<?review status="done"?><ext:Note xmlns:ext="urn:example:extension"/><!-- After note --><kr:ElectionDate>"#,
+            "<!-- End of contest --></Contest>",
+        ] {
+            assert!(
+                written.contains(expected),
+                "Missing {expected} in {written}"
+            );
+        }
+        let reparsed = EML::parse_eml(&written, options)
+            .ok()
+            .expect("Failed to parse written EML document");
+        assert_eq!(reparsed.write_eml_root_str(false, false).unwrap(), written);
+
+        // Without the option comments are dropped
+        let written = EML::parse_eml(&doc, EMLParsingMode::StrictFallback)
+            .ok()
+            .expect("Failed to parse EML document")
+            .write_eml_root_str(false, false)
+            .expect("Failed to write EML document");
+        assert!(!written.contains("<!--"));
+    }
+
+    #[test]
+    fn test_preserve_comments_inside_wrapper() {
+        let doc = include_str!("../../test-emls/deserialize_eml510b_test.eml.xml").replacen(
+            "<Contests>",
+            "<Contests><!-- In contests -->",
+            1,
+        );
+        let options = EMLReadOptions::new(EMLParsingMode::Strict).with_preserve_comments(true);
+
+        // The Contests element has no extensions, so the comment cannot be kept
+        let (eml, errors) = EML::parse_eml(&doc, options)
+            .ok_with_errors()
+            .expect("Failed to parse EML document");
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(
+            errors[0].kind(),
+            EMLErrorKind::AnnotationNotPreserved(name) if name.local_name == "Contests"
+        ));
+        let span = errors[0].span().expect("Error should have a span");
+        assert_eq!(
+            &doc[span.start as usize..span.end as usize],
+            "<!-- In contests -->"
+        );
+
+        // It is not attached to the contest that follows it
+        let written = eml.write_eml_root_str(false, false).unwrap();
+        assert!(!written.contains("<!--"), "Comment moved in {written}");
+    }
+
+    #[test]
+    fn test_preserve_unknown_round_trips_fixtures() {
+        let docs = [
+            include_str!("../../test-emls/candidate_list/eml230b_test.eml.xml"),
+            include_str!("../../test-emls/election_definition/eml110a_test.eml.xml"),
+            include_str!("../../test-emls/polling_stations/eml110b_test.eml.xml"),
+            include_str!("../../test-emls/nomination/eml210_test.eml.xml"),
+            include_str!("../../test-emls/deserialize_eml510b_test.eml.xml"),
+            include_str!("../../test-emls/deserialize_eml510d_test.eml.xml"),
+            include_str!("../../test-emls/eml510b_with_investigations.eml.xml"),
+            include_str!("../../test-emls/eml520_test.eml.xml"),
+        ];
+        let options = EMLReadOptions::new(EMLParsingMode::Strict).with_preserve_unknown(true);
+        for doc in docs {
+            let (plain, errors) = EML::parse_eml(doc, EMLParsingMode::Strict)
+                .ok_with_errors()
+                .expect("Failed to parse EML document");
+            let preserved = EML::parse_eml(doc, options)
+                .ok()
+                .expect("Failed to parse EML document");
+            let written = preserved.write_eml_root_str(true, true).unwrap();
+
+            // Known content is written only once
+            if errors.is_empty() {
+                assert_eq!(written, plain.write_eml_root_str(true, true).unwrap());
+            }
+            let reparsed = EML::parse_eml(&written, options)
+                .ok()
+                .expect("Failed to parse written EML document");
+            assert_eq!(reparsed.write_eml_root_str(true, true).unwrap(), written);
+        }
+    }
+
+    #[test]
+    fn test_write_canonical() {
+        let doc = include_str!("../../test-emls/eml520_test.eml.xml");
+        let eml = EML::parse_eml(doc, EMLParsingMode::Strict)
+            .ok()
+            .expect("Failed to parse EML document");
+
+        for algorithm in [
+            CanonicalizationAlgorithm::C14N,
+            CanonicalizationAlgorithm::C14NWithComments,
+            CanonicalizationAlgorithm::ExclusiveC14N,
+        ] {
+            let written = eml
+                .write_eml_root_canonical_str(algorithm)
+                .expect("Failed to write EML document");
+            assert!(written.starts_with("<EML "));
+            assert!(written.contains(&format!(
+                r#"Algorithm="{}"></ds:CanonicalizationMethod>"#,
+                algorithm.uri()
+            )));
+            assert_eq!(canonicalize(&written, algorithm).unwrap(), written);
+
+            let EML::ElectionResult(reparsed) = EML::parse_eml(&written, EMLParsingMode::Strict)
+                .ok()
+                .expect("Failed to parse written EML document")
+            else {
+                panic!("Expected an election result document");
+            };
+            assert_eq!(
+                reparsed
+                    .canonicalization_method
+                    .as_ref()
+                    .and_then(|method| method.algorithm()),
+                Some(algorithm)
+            );
+            assert_eq!(
+                reparsed.write_eml_root_canonical_str(algorithm).unwrap(),
+                written
+            );
+        }
+
+        // Regular output does not claim to be canonicalized
+        let written = eml.write_eml_root_str(true, true).unwrap();
+        assert!(!written.contains("CanonicalizationMethod"));
     }
 
     #[test]
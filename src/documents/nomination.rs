@@ -0,0 +1,177 @@
+//! Document variant for the EML_NL Nomination (`210`) document.
+
+use crate::{
+    EML_SCHEMA_VERSION, EMLError, NS_EML,
+    common::{
+        CanonicalizationMethod, ContestIdentifier, CreationDateTime, IssueDate, ManagingAuthority,
+        Signature, TransactionId,
+    },
+    documents::{
+        accepted_root,
+        candidate_lists::{CandidateListsAffiliation, CandidateListsElectionIdentifier},
+    },
+    error::{EMLErrorKind, EMLResultExt},
+    io::{
+        EMLElement, EMLElementReader, EMLElementWriter, Extensions, QualifiedName, collect_struct,
+    },
+};
+
+pub(crate) const EML_NOMINATION_ID: &str = "210";
+
+/// Representing a `210` document, containing the nomination of a single candidate list.
+///
+/// Nominations are submitted by the parties and are combined into a
+/// [`CandidateLists`](crate::documents::candidate_lists::CandidateLists) document
+/// once all lists have been accepted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Nomination {
+    /// Transaction id of the document.
+    pub transaction_id: TransactionId,
+
+    /// Managing authority of the document, if present.
+    pub managing_authority: Option<ManagingAuthority>,
+
+    /// Issue date of the document, if present.
+    pub issue_date: Option<IssueDate>,
+
+    /// Creation date and time of the document.
+    pub creation_date_time: CreationDateTime,
+
+    /// Canonicalization method used in this document, if present.
+    pub canonicalization_method: Option<CanonicalizationMethod>,
+
+    /// The nomination contained in this document.
+    pub nomination: NominationNomination,
+
+    /// Enveloped signature of this document, if present.
+    pub signature: Option<Signature>,
+
+    /// Unknown attributes and child elements, see [`Extensions`].
+    pub extensions: Extensions,
+}
+
+impl EMLElement for Nomination {
+    const EML_NAME: QualifiedName<'_, '_> = QualifiedName::from_static("EML", Some(NS_EML));
+
+    fn read_eml(elem: &mut EMLElementReader<'_, '_>) -> Result<Self, EMLError> {
+        accepted_root(elem)?;
+
+        let (document_id, id_span) = elem.attribute_value_req_with_span(("Id", None))?;
+        if document_id != EML_NOMINATION_ID {
+            return Err(EMLErrorKind::InvalidDocumentType(
+                EML_NOMINATION_ID,
+                document_id.to_string(),
+            ))
+            .with_span(id_span);
+        }
+
+        Ok(collect_struct!(elem, Nomination {
+            transaction_id: TransactionId::EML_NAME => |elem| TransactionId::read_eml(elem)?,
+            managing_authority as Option: ManagingAuthority::EML_NAME => |elem| ManagingAuthority::read_eml(elem)?,
+            issue_date as Option: IssueDate::EML_NAME => |elem| IssueDate::read_eml(elem)?,
+            creation_date_time: CreationDateTime::EML_NAME => |elem| CreationDateTime::read_eml(elem)?,
+            canonicalization_method as Option: CanonicalizationMethod::EML_NAME => |elem| CanonicalizationMethod::read_eml(elem)?,
+            nomination: NominationNomination::EML_NAME => |elem| NominationNomination::read_eml(elem)?,
+            signature as Option: Signature::EML_NAME => |elem| Signature::read_eml(elem)?,
+            extensions as Extensions,
+        }))
+    }
+
+    fn write_eml(&self, writer: EMLElementWriter) -> Result<(), EMLError> {
+        writer
+            .extensions(&self.extensions)?
+            .attr(("Id", None), EML_NOMINATION_ID)?
+            .attr(("SchemaVersion", None), EML_SCHEMA_VERSION)?
+            .child_elem(TransactionId::EML_NAME, &self.transaction_id)?
+            .child_elem_option(
+                ManagingAuthority::EML_NAME,
+                self.managing_authority.as_ref(),
+            )?
+            .child_elem_option(IssueDate::EML_NAME, self.issue_date.as_ref())?
+            .child_elem(CreationDateTime::EML_NAME, &self.creation_date_time)?
+            .canonicalization_method(self.canonicalization_method.is_some())?
+            .child_elem(NominationNomination::EML_NAME, &self.nomination)?
+            .child_elem_option(Signature::EML_NAME, self.signature.as_ref())?
+            .finish()?;
+
+        Ok(())
+    }
+}
+
+/// The nomination element, containing the nominated candidate list.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NominationNomination {
+    /// Identifier of the election the list is nominated for.
+    pub election_identifier: CandidateListsElectionIdentifier,
+
+    /// Identifier of the contest the list is nominated for, if present.
+    pub contest_identifier: Option<ContestIdentifier>,
+
+    /// The nominated affiliation and its candidates.
+    pub affiliation: CandidateListsAffiliation,
+
+    /// Unknown attributes and child elements, see [`Extensions`].
+    pub extensions: Extensions,
+}
+
+impl EMLElement for NominationNomination {
+    const EML_NAME: QualifiedName<'_, '_> = QualifiedName::from_static("Nomination", Some(NS_EML));
+
+    fn read_eml(elem: &mut EMLElementReader<'_, '_>) -> Result<Self, EMLError> {
+        Ok(collect_struct!(elem, NominationNomination {
+            election_identifier: CandidateListsElectionIdentifier::EML_NAME => |elem| CandidateListsElectionIdentifier::read_eml(elem)?,
+            contest_identifier as Option: ContestIdentifier::EML_NAME => |elem| ContestIdentifier::read_eml(elem)?,
+            affiliation: CandidateListsAffiliation::EML_NAME => |elem| CandidateListsAffiliation::read_eml(elem)?,
+            extensions as Extensions,
+        }))
+    }
+
+    fn write_eml(&self, writer: EMLElementWriter) -> Result<(), EMLError> {
+        writer
+            .extensions(&self.extensions)?
+            .child_elem(
+                CandidateListsElectionIdentifier::EML_NAME,
+                &self.election_identifier,
+            )?
+            .child_elem_option(
+                ContestIdentifier::EML_NAME,
+                self.contest_identifier.as_ref(),
+            )?
+            .child_elem(CandidateListsAffiliation::EML_NAME, &self.affiliation)?
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::io::{EMLParsingMode, EMLRead as _, EMLWrite as _};
+
+    use super::*;
+
+    #[test]
+    fn test_nomination_round_trip() {
+        let doc = include_str!("../../test-emls/nomination/eml210_test.eml.xml");
+        let nomination = Nomination::parse_eml(doc, EMLParsingMode::Strict)
+            .ok()
+            .expect("Failed to parse EML document");
+
+        let affiliation = &nomination.nomination.affiliation;
+        assert!(affiliation.identifier.id.is_none());
+        assert_eq!(affiliation.candidates.len(), 2);
+
+        let written = nomination
+            .write_eml_root_str(true, true)
+            .expect("Failed to write EML document");
+        let reparsed = Nomination::parse_eml(&written, EMLParsingMode::Strict)
+            .ok()
+            .expect("Failed to parse written EML document");
+        assert_eq!(
+            reparsed
+                .write_eml_root_str(true, true)
+                .expect("Failed to write EML document"),
+            written
+        );
+    }
+}
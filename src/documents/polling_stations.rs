@@ -1,6 +1,6 @@
 //! Document variant for the EML_NL Polling Stations (`110b`) document.
 
-use std::{num::NonZeroU64, sync::LazyLock};
+use std::{collections::HashSet, num::NonZeroU64, sync::LazyLock};
 
 use regex::Regex;
 use thiserror::Error;
@@ -10,24 +10,26 @@ use crate::{
     common::{
         CanonicalizationMethod, ContestIdentifier, ContestIdentifierGeen, CreationDateTime,
         ElectionDomain, IssueDate, LocalityName, ManagingAuthority, PostalCode,
-        ReportingUnitIdentifier, TransactionId,
+        ReportingUnitIdentifier, Signature, TransactionId,
     },
     documents::accepted_root,
     error::{EMLErrorKind, EMLResultExt},
     io::{
-        EMLElement, EMLElementReader, EMLElementWriter, OwnedQualifiedName, QualifiedName,
-        collect_struct,
+        EMLElement, EMLElementReader, EMLElementWriter, Extensions, OwnedQualifiedName,
+        QualifiedName, collect_struct,
     },
     utils::{
         ElectionCategory, ElectionIdType, ElectionSubcategory, StringValue, StringValueData,
         VotingChannelType, VotingMethod, XsDate,
     },
+    validation::{ValidationFinding, parsed_value},
 };
 
 pub(crate) const EML_POLLING_STATIONS_ID: &str = "110b";
 
 /// Representing a `110b` document, containing polling stations.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PollingStations {
     /// Transaction id of the document.
     pub transaction_id: TransactionId,
@@ -46,6 +48,99 @@ pub struct PollingStations {
 
     /// Election event containing the polling stations.
     pub election_event: PollingStationsElectionEvent,
+
+    /// Enveloped signature of this document, if present.
+    pub signature: Option<Signature>,
+
+    /// Unknown attributes and child elements, see [`Extensions`].
+    pub extensions: Extensions,
+}
+
+/// Minimum number of polling stations a polling stations document must contain.
+const MIN_POLLING_STATIONS: usize = 10;
+
+impl PollingStations {
+    /// Validate this polling stations document against the rules of the Kiesraad.
+    ///
+    /// The following rules are checked:
+    ///
+    /// * Polling station ids must be unique and in ascending order.
+    /// * The number of registered voters of each polling station must be a
+    ///   number, and the sum of these must equal the maximum number of votes.
+    /// * The document must contain at least 10 polling stations.
+    /// * The reporting unit must be the managing authority.
+    pub fn validate(&self) -> Vec<ValidationFinding> {
+        let mut findings = Vec::new();
+        let contest = &self.election_event.election.contest;
+
+        let mut seen_ids = HashSet::new();
+        let mut previous: Option<(u64, String)> = None;
+        let mut total_voters = Some(0u64);
+        for polling_place in &contest.polling_places {
+            let polling_station = &polling_place.physical_location.polling_station;
+            let raw_id = polling_station.id.raw().into_owned();
+
+            if !seen_ids.insert(raw_id.clone()) {
+                findings.push(ValidationFinding::DuplicatePollingStationId(raw_id.clone()));
+            }
+
+            let number = parsed_value(&polling_station.id, "PollingStation", &mut findings)
+                .and_then(|id| id.0.parse::<u64>().ok());
+            if let Some(number) = number {
+                if let Some((previous_number, previous_id)) = &previous
+                    && number <= *previous_number
+                {
+                    findings.push(ValidationFinding::PollingStationIdOutOfOrder(
+                        raw_id.clone(),
+                        previous_id.clone(),
+                    ));
+                }
+                previous = Some((number, raw_id.clone()));
+            }
+
+            match polling_station.data.trim().parse::<u64>() {
+                Ok(voters) => {
+                    // A total that does not fit cannot be compared against MaxVotes
+                    total_voters = total_voters.and_then(|total| total.checked_add(voters))
+                }
+                Err(_) => {
+                    findings.push(ValidationFinding::InvalidNumberOfVoters(
+                        raw_id,
+                        polling_station.data.clone(),
+                    ));
+                    total_voters = None;
+                }
+            }
+        }
+
+        let max_votes = parsed_value(&contest.max_votes, "MaxVotes", &mut findings);
+        if let (Some(total_voters), Some(max_votes)) = (total_voters, max_votes)
+            && total_voters != max_votes.get()
+        {
+            findings.push(ValidationFinding::NumberOfVotersMismatch(
+                total_voters,
+                max_votes.get(),
+            ));
+        }
+
+        if contest.polling_places.len() < MIN_POLLING_STATIONS {
+            findings.push(ValidationFinding::TooFewPollingStations(
+                contest.polling_places.len(),
+                MIN_POLLING_STATIONS,
+            ));
+        }
+
+        let reporting_unit_id = contest.reporting_unit.identifier.id.raw();
+        let authority_id = self.managing_authority.authority_identifier.id.raw();
+        if reporting_unit_id != authority_id {
+            findings.push(ValidationFinding::ReportingUnitAuthorityMismatch(
+                reporting_unit_id.into_owned(),
+                authority_id.into_owned(),
+            ));
+        }
+
+        findings
+    }
 }
 
 impl EMLElement for PollingStations {
@@ -54,13 +149,13 @@ impl EMLElement for PollingStations {
     fn read_eml(elem: &mut EMLElementReader<'_, '_>) -> Result<Self, EMLError> {
         accepted_root(elem)?;
 
-        let document_id = elem.attribute_value_req(("Id", None))?;
+        let (document_id, id_span) = elem.attribute_value_req_with_span(("Id", None))?;
         if document_id != EML_POLLING_STATIONS_ID {
             return Err(EMLErrorKind::InvalidDocumentType(
                 EML_POLLING_STATIONS_ID,
                 document_id.to_string(),
             ))
-            .with_span(elem.span());
+            .with_span(id_span);
         }
 
         Ok(collect_struct!(elem, PollingStations {
@@ -70,23 +165,23 @@ impl EMLElement for PollingStations {
             creation_date_time: CreationDateTime::EML_NAME => |elem| CreationDateTime::read_eml(elem)?,
             canonicalization_method as Option: CanonicalizationMethod::EML_NAME => |elem| CanonicalizationMethod::read_eml(elem)?,
             election_event: PollingStationsElectionEvent::EML_NAME => |elem| PollingStationsElectionEvent::read_eml(elem)?,
+            signature as Option: Signature::EML_NAME => |elem| Signature::read_eml(elem)?,
+            extensions as Extensions,
         }))
     }
 
     fn write_eml(&self, writer: EMLElementWriter) -> Result<(), EMLError> {
         writer
+            .extensions(&self.extensions)?
             .attr(("Id", None), EML_POLLING_STATIONS_ID)?
             .attr(("SchemaVersion", None), EML_SCHEMA_VERSION)?
             .child_elem(TransactionId::EML_NAME, &self.transaction_id)?
             .child_elem(ManagingAuthority::EML_NAME, &self.managing_authority)?
             .child_elem_option(IssueDate::EML_NAME, self.issue_date.as_ref())?
             .child_elem(CreationDateTime::EML_NAME, &self.creation_date_time)?
-            // Note: we don't output the CanonicalizationMethod because we aren't canonicalizing our output
-            // .child_elem_option(
-            //     CanonicalizationMethod::EML_NAME,
-            //     self.canonicalization_method.as_ref(),
-            // )?
+            .canonicalization_method(self.canonicalization_method.is_some())?
             .child_elem(PollingStationsElectionEvent::EML_NAME, &self.election_event)?
+            .child_elem_option(Signature::EML_NAME, self.signature.as_ref())?
             .finish()?;
 
         Ok(())
@@ -94,13 +189,17 @@ impl EMLElement for PollingStations {
 }
 
 /// Election event containing polling stations.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PollingStationsElectionEvent {
     /// Identifier for this election event.
     pub id: PollingStationsElectionEventIdentifier,
 
     /// Election details.
     pub election: PollingStationsElection,
+
+    /// Unknown attributes and child elements, see [`Extensions`].
+    pub extensions: Extensions,
 }
 
 impl EMLElement for PollingStationsElectionEvent {
@@ -114,11 +213,13 @@ impl EMLElement for PollingStationsElectionEvent {
         Ok(collect_struct!(elem, PollingStationsElectionEvent {
             id: PollingStationsElectionEventIdentifier::EML_NAME => |elem| PollingStationsElectionEventIdentifier::read_eml(elem)?,
             election: PollingStationsElection::EML_NAME => |elem| PollingStationsElection::read_eml(elem)?,
+            extensions as Extensions,
         }))
     }
 
     fn write_eml(&self, writer: EMLElementWriter) -> Result<(), EMLError> {
         writer
+            .extensions(&self.extensions)?
             .child_elem(PollingStationsElectionEventIdentifier::EML_NAME, &self.id)?
             .child_elem(PollingStationsElection::EML_NAME, &self.election)?
             .finish()
@@ -126,7 +227,8 @@ impl EMLElement for PollingStationsElectionEvent {
 }
 
 /// Identifier for a polling stations election event.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PollingStationsElectionEventIdentifier;
 
 impl EMLElement for PollingStationsElectionEventIdentifier {
@@ -147,13 +249,17 @@ impl EMLElement for PollingStationsElectionEventIdentifier {
 }
 
 /// Election definition containing polling stations.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PollingStationsElection {
     /// Identifier of the election.
     pub identifier: PollingStationsElectionIdentifier,
 
     /// Contest containing the polling stations.
     pub contest: PollingStationsContest,
+
+    /// Unknown attributes and child elements, see [`Extensions`].
+    pub extensions: Extensions,
 }
 
 impl EMLElement for PollingStationsElection {
@@ -166,11 +272,13 @@ impl EMLElement for PollingStationsElection {
         Ok(collect_struct!(elem, PollingStationsElection {
             identifier: PollingStationsElectionIdentifier::EML_NAME => |elem| PollingStationsElectionIdentifier::read_eml(elem)?,
             contest: PollingStationsContest::EML_NAME => |elem| PollingStationsContest::read_eml(elem)?,
+            extensions as Extensions,
         }))
     }
 
     fn write_eml(&self, writer: EMLElementWriter) -> Result<(), EMLError> {
         writer
+            .extensions(&self.extensions)?
             .child_elem(
                 PollingStationsElectionIdentifier::EML_NAME,
                 &self.identifier,
@@ -181,7 +289,8 @@ impl EMLElement for PollingStationsElection {
 }
 
 /// Identifier of an election in the polling stations document.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PollingStationsElectionIdentifier {
     /// Election id.
     pub id: StringValue<ElectionIdType>,
@@ -200,6 +309,9 @@ pub struct PollingStationsElectionIdentifier {
 
     /// Date of the election
     pub election_date: StringValue<XsDate>,
+
+    /// Unknown attributes and child elements, see [`Extensions`].
+    pub extensions: Extensions,
 }
 
 struct PollingStationsElectionIdentifierInternal {
@@ -210,6 +322,8 @@ struct PollingStationsElectionIdentifierInternal {
     domain: Option<ElectionDomain>,
     election_date: Option<StringValue<XsDate>>,
     election_date_eml: Option<StringValue<XsDate>>,
+    /// Unknown attributes and child elements, see [`Extensions`].
+    extensions: Extensions,
 }
 
 impl EMLElement for PollingStationsElectionIdentifier {
@@ -238,6 +352,7 @@ impl EMLElement for PollingStationsElectionIdentifier {
                     }
                     elem.string_value()?
                 },
+                extensions as Extensions,
             }
         );
 
@@ -262,11 +377,13 @@ impl EMLElement for PollingStationsElectionIdentifier {
             subcategory: data.subcategory,
             domain: data.domain,
             election_date,
+            extensions: data.extensions,
         })
     }
 
     fn write_eml(&self, writer: EMLElementWriter) -> Result<(), EMLError> {
         writer
+            .extensions(&self.extensions)?
             .attr("Id", self.id.raw().as_ref())?
             .child_option(
                 ("ElectionName", NS_EML),
@@ -290,7 +407,8 @@ impl EMLElement for PollingStationsElectionIdentifier {
 }
 
 /// Contest containing polling stations.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PollingStationsContest {
     /// Identifier for the contest.
     pub identifier: ContestIdentifierGeen,
@@ -302,6 +420,8 @@ pub struct PollingStationsContest {
     pub max_votes: StringValue<NonZeroU64>,
     /// List of polling places in this contest.
     pub polling_places: Vec<PollingPlace>,
+    /// Unknown attributes and child elements, see [`Extensions`].
+    pub extensions: Extensions,
 }
 
 struct PollingStationsContestInternal {
@@ -310,6 +430,8 @@ struct PollingStationsContestInternal {
     pub voting_method: StringValue<VotingMethod>,
     pub max_votes: StringValue<NonZeroU64>,
     pub polling_places: Vec<PollingPlace>,
+    /// Unknown attributes and child elements, see [`Extensions`].
+    pub extensions: Extensions,
 }
 
 impl EMLElement for PollingStationsContest {
@@ -325,6 +447,7 @@ impl EMLElement for PollingStationsContest {
                 elem.string_value_from_text(text, None, elem.full_span())?
             },
             polling_places as Vec: PollingPlace::EML_NAME => |elem| PollingPlace::read_eml(elem)?,
+            extensions as Extensions,
         });
 
         // Some municipalities omit the ContestIdentifier element, even though it is required.
@@ -346,11 +469,13 @@ impl EMLElement for PollingStationsContest {
             voting_method: data.voting_method,
             max_votes: data.max_votes,
             polling_places: data.polling_places,
+            extensions: data.extensions,
         })
     }
 
     fn write_eml(&self, writer: EMLElementWriter) -> Result<(), EMLError> {
         let mut writer = writer
+            .extensions(&self.extensions)?
             .child_elem(ContestIdentifier::EML_NAME, &self.identifier)?
             .child_elem(PollingStationsReportingUnit::EML_NAME, &self.reporting_unit)?
             .child(("VotingMethod", NS_EML), |elem| {
@@ -374,10 +499,13 @@ impl EMLElement for PollingStationsContest {
 }
 
 /// Reporting unit for the contest
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PollingStationsReportingUnit {
     /// Identifier of the reporting unit.
     pub identifier: ReportingUnitIdentifier,
+    /// Unknown attributes and child elements, see [`Extensions`].
+    pub extensions: Extensions,
 }
 
 impl EMLElement for PollingStationsReportingUnit {
@@ -387,23 +515,28 @@ impl EMLElement for PollingStationsReportingUnit {
     fn read_eml(elem: &mut EMLElementReader<'_, '_>) -> Result<Self, EMLError> {
         Ok(collect_struct!(elem, PollingStationsReportingUnit {
             identifier: ReportingUnitIdentifier::EML_NAME => |elem| ReportingUnitIdentifier::read_eml(elem)?,
+            extensions as Extensions,
         }))
     }
 
     fn write_eml(&self, writer: EMLElementWriter) -> Result<(), EMLError> {
         writer
+            .extensions(&self.extensions)?
             .child_elem(ReportingUnitIdentifier::EML_NAME, &self.identifier)?
             .finish()
     }
 }
 
 /// A polling place in the polling stations document.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PollingPlace {
     /// Voting channel used at this polling place.
     pub channel: StringValue<VotingChannelType>,
     /// Physical location of the polling place.
     pub physical_location: PhysicalLocation,
+    /// Unknown attributes and child elements, see [`Extensions`].
+    pub extensions: Extensions,
 }
 
 impl EMLElement for PollingPlace {
@@ -414,11 +547,13 @@ impl EMLElement for PollingPlace {
         Ok(collect_struct!(elem, PollingPlace {
             physical_location: PhysicalLocation::EML_NAME => |elem| PhysicalLocation::read_eml(elem)?,
             channel: elem.string_value_attr("Channel", None)?,
+            extensions as Extensions,
         }))
     }
 
     fn write_eml(&self, writer: EMLElementWriter) -> Result<(), EMLError> {
         writer
+            .extensions(&self.extensions)?
             .attr("Channel", self.channel.raw().as_ref())?
             .child_elem(PhysicalLocation::EML_NAME, &self.physical_location)?
             .finish()
@@ -426,12 +561,15 @@ impl EMLElement for PollingPlace {
 }
 
 /// Physical location of a polling place.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PhysicalLocation {
     /// Address of the physical location.
     pub address: PhysicalLocationAddress,
     /// Polling station information of the physical location.
     pub polling_station: PhysicalLocationPollingStation,
+    /// Unknown attributes and child elements, see [`Extensions`].
+    pub extensions: Extensions,
 }
 
 impl EMLElement for PhysicalLocation {
@@ -442,11 +580,13 @@ impl EMLElement for PhysicalLocation {
         Ok(collect_struct!(elem, PhysicalLocation {
             address: PhysicalLocationAddress::EML_NAME => |elem| PhysicalLocationAddress::read_eml(elem)?,
             polling_station: PhysicalLocationPollingStation::EML_NAME => |elem| PhysicalLocationPollingStation::read_eml(elem)?,
+            extensions as Extensions,
         }))
     }
 
     fn write_eml(&self, writer: EMLElementWriter) -> Result<(), EMLError> {
         writer
+            .extensions(&self.extensions)?
             .child_elem(PhysicalLocationAddress::EML_NAME, &self.address)?
             .child_elem(
                 PhysicalLocationPollingStation::EML_NAME,
@@ -457,10 +597,13 @@ impl EMLElement for PhysicalLocation {
 }
 
 /// Address of a physical location.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PhysicalLocationAddress {
     /// Locality of the physical location.
     pub locality: PhysicalLocationLocality,
+    /// Unknown attributes and child elements, see [`Extensions`].
+    pub extensions: Extensions,
 }
 
 impl EMLElement for PhysicalLocationAddress {
@@ -469,21 +612,28 @@ impl EMLElement for PhysicalLocationAddress {
     fn read_eml(elem: &mut EMLElementReader<'_, '_>) -> Result<Self, EMLError> {
         Ok(collect_struct!(elem, PhysicalLocationAddress {
             locality: PhysicalLocationLocality::EML_NAME => |elem| PhysicalLocationLocality::read_eml(elem)?,
+            extensions as Extensions,
         }))
     }
 
     fn write_eml(&self, writer: EMLElementWriter) -> Result<(), EMLError> {
         writer
+            .extensions(&self.extensions)?
             .child_elem(PhysicalLocationLocality::EML_NAME, &self.locality)?
             .finish()
     }
 }
 
 /// Locality of a physical location.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PhysicalLocationLocality {
-    locality_name: LocalityName,
-    postal_code: Option<PostalCode>,
+    /// Name of the locality.
+    pub locality_name: LocalityName,
+    /// Postal code of the locality, if present.
+    pub postal_code: Option<PostalCode>,
+    /// Unknown attributes and child elements, see [`Extensions`].
+    pub extensions: Extensions,
 }
 
 impl EMLElement for PhysicalLocationLocality {
@@ -493,11 +643,13 @@ impl EMLElement for PhysicalLocationLocality {
         Ok(collect_struct!(elem, PhysicalLocationLocality {
             locality_name: LocalityName::EML_NAME => |elem| LocalityName::read_eml(elem)?,
             postal_code as Option: PostalCode::EML_NAME => |elem| PostalCode::read_eml(elem)?,
+            extensions as Extensions,
         }))
     }
 
     fn write_eml(&self, writer: EMLElementWriter) -> Result<(), EMLError> {
         writer
+            .extensions(&self.extensions)?
             .child_elem(LocalityName::EML_NAME, &self.locality_name)?
             .child_elem_option(PostalCode::EML_NAME, self.postal_code.as_ref())?
             .finish()
@@ -505,7 +657,8 @@ impl EMLElement for PhysicalLocationLocality {
 }
 
 /// Polling station information of a physical location.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PhysicalLocationPollingStation {
     /// Identifier of the polling station.
     pub id: StringValue<PhysicalLocationPollingStationId>,
@@ -536,7 +689,7 @@ impl EMLElement for PhysicalLocationPollingStation {
 }
 
 /// Identifier for a physical location polling station.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct PhysicalLocationPollingStationId(String);
 
 /// Error returned when a string could not be parsed as a PhysicalLocationPollingStationId
@@ -568,12 +721,108 @@ impl StringValueData for PhysicalLocationPollingStationId {
     }
 }
 
+crate::utils::impl_serde_raw!(PhysicalLocationPollingStationId);
+
 #[cfg(test)]
 mod tests {
+    use crate::io::{EMLParsingMode, EMLRead as _};
+
     use super::*;
 
     #[test]
     fn test_physical_location_ps_id_regex_compiles() {
         LazyLock::force(&PHYSICAL_LOCATION_PS_ID);
     }
+
+    #[test]
+    fn test_physical_location_polling_station_data() {
+        let xml = r#"<PollingStation xmlns="urn:oasis:names:tc:evs:schema:eml" Id="1">1273</PollingStation>"#;
+        let polling_station =
+            PhysicalLocationPollingStation::parse_eml(xml, EMLParsingMode::Strict)
+                .ok()
+                .expect("Failed to parse polling station");
+        assert_eq!(polling_station.id.raw(), "1");
+        assert_eq!(polling_station.data, "1273");
+    }
+
+    #[test]
+    fn test_validate_polling_stations() {
+        let parse = |doc: &str| {
+            PollingStations::parse_eml(doc, EMLParsingMode::Loose)
+                .ok()
+                .expect("Failed to parse EML document")
+        };
+
+        let doc = include_str!("../../test-emls/polling_stations/eml110b_test.eml.xml");
+        assert_eq!(parse(doc).validate(), vec![]);
+
+        let doc =
+            include_str!("../../test-emls/polling_stations/eml110b_less_than_10_stations.eml.xml");
+        assert!(
+            parse(doc)
+                .validate()
+                .contains(&ValidationFinding::TooFewPollingStations(9, 10))
+        );
+
+        let doc = include_str!(
+            "../../test-emls/polling_stations/eml110b_invalid_number_of_voters.eml.xml"
+        );
+        assert!(
+            parse(doc)
+                .validate()
+                .contains(&ValidationFinding::InvalidValue(
+                    "MaxVotes".to_string(),
+                    "test".to_string()
+                ))
+        );
+
+        let doc = include_str!(
+            "../../test-emls/polling_stations/eml110b_invalid_polling_station_number_of_voters.xml"
+        );
+        let findings = parse(doc).validate();
+        assert!(findings.contains(&ValidationFinding::InvalidNumberOfVoters(
+            "1".to_string(),
+            "geen".to_string()
+        )));
+        assert!(findings.contains(&ValidationFinding::InvalidNumberOfVoters(
+            "3".to_string(),
+            "-".to_string()
+        )));
+
+        let doc = include_str!("../../test-emls/polling_stations/eml110b_test.eml.xml")
+            .replace(r#"<PollingStation Id="3">"#, r#"<PollingStation Id="1">"#)
+            .replace(
+                r#"<ReportingUnitIdentifier Id="0000">"#,
+                r#"<ReportingUnitIdentifier Id="0001">"#,
+            );
+        let findings = parse(&doc).validate();
+        assert!(
+            findings.contains(&ValidationFinding::DuplicatePollingStationId(
+                "1".to_string()
+            ))
+        );
+        assert!(
+            findings.contains(&ValidationFinding::PollingStationIdOutOfOrder(
+                "1".to_string(),
+                "2".to_string()
+            ))
+        );
+        assert!(
+            findings.contains(&ValidationFinding::ReportingUnitAuthorityMismatch(
+                "0001".to_string(),
+                "0000".to_string()
+            ))
+        );
+
+        let doc = include_str!("../../test-emls/polling_stations/eml110b_test.eml.xml").replace(
+            r#"<PollingStation Id="1">1273</PollingStation>"#,
+            r#"<PollingStation Id="1">18446744073709551615</PollingStation>"#,
+        );
+        let findings = parse(&doc).validate();
+        assert!(
+            !findings
+                .iter()
+                .any(|f| matches!(f, ValidationFinding::NumberOfVotersMismatch(..)))
+        );
+    }
 }
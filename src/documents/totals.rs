@@ -0,0 +1,45 @@
+//! Document variant for the EML_NL Totals (`510d`) document.
+
+use crate::documents::count::Count;
+
+pub(crate) const EML_TOTALS_ID: &str = "510d";
+
+/// Representing a `510d` document, containing the final totals of an election.
+///
+/// The totals share their content model with the other count documents, so
+/// this is a [`Count`] at the
+/// [`CountLevel::CentralCommittee`](crate::documents::count::CountLevel::CentralCommittee)
+/// level.
+pub type Totals = Count;
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        documents::{EML, count::CountLevel},
+        io::{EMLParsingMode, EMLRead as _, EMLWrite as _},
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_totals_round_trip() {
+        let doc = include_str!("../../test-emls/deserialize_eml510d_test.eml.xml");
+        let eml = EML::parse_eml(doc, EMLParsingMode::Strict)
+            .ok()
+            .expect("Failed to parse EML document");
+        let totals = eml.as_totals_doc().expect("Expected a totals document");
+        assert_eq!(totals.level, CountLevel::CentralCommittee);
+        assert_eq!(totals.count.election.contests.len(), 1);
+
+        let written = eml
+            .write_eml_root_str(true, true)
+            .expect("Failed to write EML document");
+        assert!(written.contains(r#"Id="510d""#));
+        let reparsed = Totals::parse_eml(&written, EMLParsingMode::Strict)
+            .ok()
+            .expect("Failed to parse written EML document");
+        let contest = &reparsed.count.election.contests[0];
+        assert_eq!(contest.total_votes.selections.len(), 3);
+        assert_eq!(contest.reporting_unit_votes.len(), 1);
+    }
+}
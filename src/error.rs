@@ -23,6 +23,10 @@ pub enum EMLErrorKind {
     #[error("Encoding error: {0}")]
     EncodingError(#[from] quick_xml::encoding::EncodingError),
 
+    /// The encoding of the document is not supported
+    #[error("Unsupported encoding: {0}")]
+    UnsupportedEncoding(String),
+
     /// An error converting from UTF-8
     #[error("UTF-8 conversion error: {0}")]
     FromUtf8Error(#[from] std::string::FromUtf8Error),
@@ -55,6 +59,10 @@ pub enum EMLErrorKind {
     #[error("Unexpected element: {0} inside of {1}")]
     UnexpectedElement(OwnedQualifiedName, OwnedQualifiedName),
 
+    /// A comment or processing instruction was found in an element that cannot preserve it
+    #[error("Comment or processing instruction inside of {0} cannot be preserved")]
+    AnnotationNotPreserved(OwnedQualifiedName),
+
     /// A namespace was encountered that is not recognized
     #[error("Unknown namespace: {0}")]
     UnknownNamespace(String),
@@ -97,6 +105,38 @@ pub enum EMLErrorKind {
     /// The ElectionDate element is used without using the kiesraad namespace
     #[error("Used ElectionDate element without using the kiesraad namespace")]
     InvalidElectionDateNamespace,
+
+    /// The document does not contain a signature
+    #[error("Document is not signed, no Signature element found")]
+    MissingSignature,
+
+    /// An algorithm used in a signature is not supported
+    #[error("Unsupported signature algorithm: {0}")]
+    UnsupportedAlgorithm(String),
+
+    /// The signature does not consist of a single reference to the entire document
+    #[error("Signature must contain a single reference to the entire document")]
+    UnsupportedReference,
+
+    /// The digest of the signed content does not match the digest in the signature
+    #[error("Digest of the signed content does not match the signature")]
+    DigestMismatch,
+
+    /// The signature value could not be verified with the given key
+    #[error("Signature value is not valid for the given key")]
+    InvalidSignature,
+
+    /// A key or certificate could not be decoded
+    #[error("Invalid key: {0}")]
+    InvalidKey(#[source] Box<dyn std::error::Error + Send + Sync + 'static>),
+
+    /// The key info of a signature does not contain a certificate
+    #[error("Key info of the signature does not contain a certificate")]
+    MissingCertificate,
+
+    /// Creating a signature value failed
+    #[error("Signing failed: {0}")]
+    SigningError(#[source] Box<dyn std::error::Error + Send + Sync + 'static>),
 }
 
 impl EMLErrorKind {
@@ -183,6 +223,22 @@ impl EMLError {
         }
     }
 
+    /// Maps the span of this error, and of all errors it consists of.
+    pub(crate) fn map_spans(self, f: &impl Fn(Span) -> Span) -> Self {
+        match self {
+            EMLError::Positioned { kind, span } => EMLError::Positioned {
+                kind,
+                span: f(span),
+            },
+            EMLError::UnknownPosition { kind } => EMLError::UnknownPosition { kind },
+            EMLError::Multiple(MultipleEMLErrors { errors }) => {
+                EMLError::Multiple(MultipleEMLErrors {
+                    errors: errors.into_iter().map(|e| e.map_spans(f)).collect(),
+                })
+            }
+        }
+    }
+
     /// Returns the kind of this error.
     ///
     /// When this error consists of multiple errors, None is returned.
@@ -218,7 +274,9 @@ impl EMLError {
     pub fn is_fatal(&self) -> bool {
         !matches!(
             self.kind(),
-            EMLErrorKind::UnexpectedElement(_, _) | EMLErrorKind::InvalidValue(_, _)
+            EMLErrorKind::UnexpectedElement(_, _)
+                | EMLErrorKind::InvalidValue(_, _)
+                | EMLErrorKind::AnnotationNotPreserved(_)
         )
     }
 }
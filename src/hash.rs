@@ -0,0 +1,256 @@
+//! SHA-256 hashes of EML documents, as shown by the Kiesraad software.
+//!
+//! This module is only available with the `hash` feature enabled.
+//!
+//! Hashes are shown to users as 16 blocks of four hexadecimal characters, so
+//! they can be compared by hand with a printed copy. A user can also type (a
+//! part of) a hash to verify a file, see [`DocumentHash::matches_partial`].
+//!
+//! ```
+//! use eml_nl::hash::DocumentHash;
+//!
+//! let hash = DocumentHash::of_bytes(b"abc");
+//! assert_eq!(
+//!     hash.to_string(),
+//!     "ba78 16bf 8f01 cfea 4141 40de 5dae 2223 b003 61a3 9617 7a9c b410 ff61 f200 15ad"
+//! );
+//! assert!(hash.matches_partial("ba78 16bf ____ cfea"));
+//! ```
+
+use std::fmt;
+
+use sha2::{Digest as _, Sha256};
+use thiserror::Error;
+
+use crate::{
+    EMLError,
+    io::{CanonicalizationAlgorithm, EMLWrite, canonicalize},
+    utils::StringValueData,
+};
+
+/// Number of hexadecimal characters in each block of the displayed hash.
+const CHUNK_LEN: usize = 4;
+
+/// A SHA-256 digest of an EML document.
+///
+/// The [`Display`](fmt::Display) implementation formats the hash in blocks of
+/// four lowercase hexadecimal characters separated by spaces, while the
+/// [`LowerHex`](fmt::LowerHex) implementation formats it without separators.
+/// With the `serde` feature enabled, the hash is serialized as a hexadecimal
+/// string without separators.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DocumentHash([u8; 32]);
+
+impl DocumentHash {
+    /// Hash the raw bytes of a document, e.g. the contents of a file.
+    pub fn of_bytes(bytes: impl AsRef<[u8]>) -> DocumentHash {
+        DocumentHash(Sha256::digest(bytes.as_ref()).into())
+    }
+
+    /// Hash the canonical form of an XML document, so that the hash does not
+    /// depend on formatting or attribute order.
+    pub fn of_canonical(xml: &str, algorithm: CanonicalizationAlgorithm) -> Result<Self, EMLError> {
+        Ok(DocumentHash::of_bytes(canonicalize(xml, algorithm)?))
+    }
+
+    /// Hash a document as it is written in canonical form with the given
+    /// algorithm.
+    pub fn of_document(
+        document: &impl EMLWrite,
+        algorithm: CanonicalizationAlgorithm,
+    ) -> Result<Self, EMLError> {
+        Ok(DocumentHash::of_bytes(
+            document.write_eml_root_canonical(algorithm)?,
+        ))
+    }
+
+    /// Returns the bytes of the digest.
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+
+    /// Returns the digest as a lowercase hexadecimal string without separators.
+    pub fn to_hex(&self) -> String {
+        format!("{self:x}")
+    }
+
+    /// Returns the blocks of four hexadecimal characters the hash is shown in.
+    pub fn chunks(&self) -> Vec<String> {
+        self.to_hex()
+            .as_bytes()
+            .chunks(CHUNK_LEN)
+            .map(|chunk| String::from_utf8_lossy(chunk).into_owned())
+            .collect()
+    }
+
+    /// Checks whether a hash typed by a user matches this hash.
+    ///
+    /// The input is compared with the start of the hash, so it may be
+    /// shorter than the full hash. Whitespace is ignored and letters are
+    /// compared case insensitive. An underscore matches any character, so that
+    /// blocks the user was not asked to type can be skipped. Any other
+    /// character that is not a hexadecimal digit never matches, so typos such
+    /// as `o` for `0` are not accepted. The input must contain at least one
+    /// hexadecimal digit.
+    pub fn matches_partial(&self, input: &str) -> bool {
+        let hex = self.to_hex();
+        let input: Vec<char> = input.chars().filter(|c| !c.is_whitespace()).collect();
+        input.len() <= hex.len()
+            && input.iter().any(char::is_ascii_hexdigit)
+            && input
+                .iter()
+                .zip(hex.chars())
+                .all(|(typed, expected)| *typed == '_' || typed.to_ascii_lowercase() == expected)
+    }
+}
+
+impl fmt::Display for DocumentHash {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.chunks().join(" "))
+    }
+}
+
+impl fmt::LowerHex for DocumentHash {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for byte in self.0 {
+            write!(f, "{byte:02x}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Error type returned when a string is not a valid hexadecimal document hash.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("Invalid document hash: {0}")]
+pub struct InvalidDocumentHashError(String);
+
+impl StringValueData for DocumentHash {
+    type Error = InvalidDocumentHashError;
+
+    /// The hash must consist of exactly 64 hexadecimal characters, without
+    /// separators.
+    fn parse_from_str(s: &str) -> Result<Self, Self::Error>
+    where
+        Self: Sized,
+    {
+        let invalid = || InvalidDocumentHashError(s.to_string());
+        if s.len() != 64 || !s.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(invalid());
+        }
+        let mut bytes = [0u8; 32];
+        for (byte, digits) in bytes.iter_mut().zip(s.as_bytes().chunks(2)) {
+            let digits = std::str::from_utf8(digits).map_err(|_| invalid())?;
+            *byte = u8::from_str_radix(digits, 16).map_err(|_| invalid())?;
+        }
+        Ok(DocumentHash(bytes))
+    }
+
+    fn to_raw_value(&self) -> String {
+        self.to_hex()
+    }
+}
+
+crate::utils::impl_serde_raw!(DocumentHash);
+
+/// Checks whether a hash typed by a user matches the hash of the raw bytes of
+/// a document, see [`DocumentHash::matches_partial`].
+pub fn verify_partial_hash(bytes: impl AsRef<[u8]>, input: &str) -> bool {
+    DocumentHash::of_bytes(bytes).matches_partial(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        documents::EML,
+        io::{EMLParsingMode, EMLRead as _},
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_hash_formatting() {
+        let hash = DocumentHash::of_bytes(b"abc");
+        assert_eq!(
+            hash.to_hex(),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        assert_eq!(hash.chunks().len(), 16);
+        assert_eq!(hash.chunks()[15], "15ad");
+        assert_eq!(
+            hash.to_string().split(' ').collect::<Vec<_>>(),
+            hash.chunks()
+        );
+    }
+
+    #[test]
+    fn test_matches_partial() {
+        let hash = DocumentHash::of_bytes(b"abc");
+        assert!(hash.matches_partial(&hash.to_string()));
+        assert!(hash.matches_partial(&hash.to_hex().to_uppercase()));
+        assert!(hash.matches_partial("ba78"));
+        assert!(hash.matches_partial(" ba78\t16BF "));
+        assert!(hash.matches_partial("____ ____ 8f01"));
+
+        assert!(!hash.matches_partial(""));
+        assert!(!hash.matches_partial("____ ____"));
+        assert!(!hash.matches_partial("ba79"));
+        assert!(!hash.matches_partial("____ 16bf 8f02"));
+        assert!(!hash.matches_partial(&format!("{} 0", hash.to_hex())));
+        assert!(!hash.matches_partial("ba7g 16bf"));
+        assert!(!hash.matches_partial("ba78 16bf 8fo1"));
+        assert!(!hash.matches_partial("**** **** 8f01 cfea"));
+        assert!(verify_partial_hash(b"abc", "ba78 16bf"));
+    }
+
+    #[test]
+    fn test_parse_hash() {
+        let hash = DocumentHash::of_bytes(b"abc");
+        assert_eq!(DocumentHash::parse_from_str(&hash.to_hex()), Ok(hash));
+        assert_eq!(
+            DocumentHash::parse_from_str(&hash.to_hex().to_uppercase()),
+            Ok(hash)
+        );
+        assert!(DocumentHash::parse_from_str(&hash.to_string()).is_err());
+        assert!(DocumentHash::parse_from_str(&hash.to_hex()[2..]).is_err());
+        assert!(DocumentHash::parse_from_str(&hash.to_hex().replace('b', "g")).is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_hash_serde() {
+        let hash = DocumentHash::of_bytes(b"abc");
+        let json = serde_json::to_string(&hash).expect("Failed to serialize");
+        assert_eq!(json, format!(r#""{}""#, hash.to_hex()));
+        let deserialized: DocumentHash =
+            serde_json::from_str(&json).expect("Failed to deserialize");
+        assert_eq!(deserialized, hash);
+        assert!(serde_json::from_str::<DocumentHash>(r#""ba78""#).is_err());
+    }
+
+    #[test]
+    fn test_canonical_hash() {
+        let doc = include_str!("../test-emls/polling_stations/eml110b_test.eml.xml");
+        let reformatted = doc
+            .replace("\n", "\r\n")
+            .replace(r#"Id="110b""#, "Id='110b'");
+        assert_ne!(
+            DocumentHash::of_bytes(doc),
+            DocumentHash::of_bytes(&reformatted)
+        );
+
+        let algorithm = CanonicalizationAlgorithm::C14N;
+        assert_eq!(
+            DocumentHash::of_canonical(&reformatted, algorithm).unwrap(),
+            DocumentHash::of_canonical(doc, algorithm).unwrap()
+        );
+
+        let eml = EML::parse_eml(doc, EMLParsingMode::Strict)
+            .ok()
+            .expect("Failed to parse EML document");
+        let written = eml.write_eml_root_canonical_str(algorithm).unwrap();
+        assert_eq!(
+            DocumentHash::of_document(&eml, algorithm).unwrap(),
+            DocumentHash::of_bytes(written)
+        );
+    }
+}
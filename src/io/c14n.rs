@@ -0,0 +1,491 @@
+//! Canonical XML output, as defined by
+//! [Canonical XML 1.0](https://www.w3.org/TR/2001/REC-xml-c14n-20010315) and
+//! [Exclusive XML Canonicalization 1.0](https://www.w3.org/TR/2002/REC-xml-exc-c14n-20020718/).
+//!
+//! The canonical form of a document only depends on its logical content, so
+//! the canonical form of two equivalent documents is byte for byte identical.
+//! This makes it suitable for hashing and signing documents.
+
+use std::collections::BTreeMap;
+
+use quick_xml::{
+    Reader,
+    escape::unescape,
+    events::{BytesStart, Event},
+};
+
+use crate::{EMLError, EMLErrorKind, EMLResultExt, NS_XML};
+
+/// A canonicalization algorithm, identified by its URI in a
+/// `CanonicalizationMethod` element.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CanonicalizationAlgorithm {
+    /// Canonical XML 1.0, omitting comments.
+    C14N,
+    /// Canonical XML 1.0, including comments.
+    C14NWithComments,
+    /// Exclusive XML Canonicalization 1.0, omitting comments.
+    ExclusiveC14N,
+    /// Exclusive XML Canonicalization 1.0, including comments.
+    ExclusiveC14NWithComments,
+}
+
+impl CanonicalizationAlgorithm {
+    /// Returns the URI identifying this algorithm.
+    pub fn uri(self) -> &'static str {
+        match self {
+            CanonicalizationAlgorithm::C14N => "http://www.w3.org/TR/2001/REC-xml-c14n-20010315",
+            CanonicalizationAlgorithm::C14NWithComments => {
+                "http://www.w3.org/TR/2001/REC-xml-c14n-20010315#WithComments"
+            }
+            CanonicalizationAlgorithm::ExclusiveC14N => "http://www.w3.org/2001/10/xml-exc-c14n#",
+            CanonicalizationAlgorithm::ExclusiveC14NWithComments => {
+                "http://www.w3.org/2001/10/xml-exc-c14n#WithComments"
+            }
+        }
+    }
+
+    /// Returns the algorithm identified by the given URI, if it is supported.
+    pub fn from_uri(uri: &str) -> Option<CanonicalizationAlgorithm> {
+        [
+            CanonicalizationAlgorithm::C14N,
+            CanonicalizationAlgorithm::C14NWithComments,
+            CanonicalizationAlgorithm::ExclusiveC14N,
+            CanonicalizationAlgorithm::ExclusiveC14NWithComments,
+        ]
+        .into_iter()
+        .find(|algorithm| algorithm.uri() == uri)
+    }
+
+    /// Returns whether comments are kept in the canonical form.
+    pub fn with_comments(self) -> bool {
+        matches!(
+            self,
+            CanonicalizationAlgorithm::C14NWithComments
+                | CanonicalizationAlgorithm::ExclusiveC14NWithComments
+        )
+    }
+
+    /// Returns whether this is one of the exclusive canonicalization algorithms,
+    /// which only output namespace declarations where they are used.
+    pub fn is_exclusive(self) -> bool {
+        matches!(
+            self,
+            CanonicalizationAlgorithm::ExclusiveC14N
+                | CanonicalizationAlgorithm::ExclusiveC14NWithComments
+        )
+    }
+}
+
+/// Namespace bindings by prefix, where None is the default namespace. An empty
+/// URI for the default namespace means there is no default namespace.
+type Namespaces = BTreeMap<Option<String>, String>;
+
+/// Namespace bindings of an open element.
+struct Scope {
+    /// All bindings in scope of the element.
+    in_scope: Namespaces,
+    /// The bindings that were written on the element or its ancestors.
+    rendered: Namespaces,
+}
+
+/// Converts an entire XML document to its canonical form.
+///
+/// Document type declarations are removed, but entities declared in them are
+/// not supported. The `InclusiveNamespaces` prefix list of exclusive
+/// canonicalization is not supported either.
+pub fn canonicalize(xml: &str, algorithm: CanonicalizationAlgorithm) -> Result<String, EMLError> {
+    canonicalize_nodes(xml, None, algorithm)
+}
+
+/// Converts a single element of an XML document and its content to canonical
+/// form, e.g. the `SignedInfo` element of a signature.
+///
+/// The element is identified by the byte offset of its start tag in `xml`,
+/// after line endings have been normalized. Namespace declarations of its
+/// ancestors that are in scope are written on the element, but attributes in
+/// the `xml` namespace are not inherited from its ancestors.
+#[cfg(any(test, feature = "xmldsig"))]
+pub(crate) fn canonicalize_element(
+    xml: &str,
+    offset: usize,
+    algorithm: CanonicalizationAlgorithm,
+) -> Result<String, EMLError> {
+    canonicalize_nodes(xml, Some(offset), algorithm)
+}
+
+/// Converts the entire document, or only the element starting at the given
+/// offset, to its canonical form.
+fn canonicalize_nodes(
+    xml: &str,
+    element: Option<usize>,
+    algorithm: CanonicalizationAlgorithm,
+) -> Result<String, EMLError> {
+    // Line endings are normalized by XML processors before parsing
+    let xml = xml.replace("\r\n", "\n").replace('\r', "\n");
+    let mut reader = Reader::from_str(&xml);
+    reader.config_mut().expand_empty_elements = true;
+
+    let mut output = String::with_capacity(xml.len());
+    let mut scopes: Vec<Scope> = Vec::new();
+    let mut after_root = false;
+    // Output offset and depth of the element, once it has been found
+    let mut element_start = None;
+    loop {
+        let is_root_level = scopes.is_empty();
+        let position = reader.buffer_position();
+        match reader.read_event().without_span()? {
+            Event::Start(tag) => {
+                let scope = if element.is_some_and(|offset| offset as u64 == position) {
+                    element_start = Some((output.len(), scopes.len()));
+                    // Nothing was written outside of the element, so all
+                    // namespaces in scope still need to be rendered
+                    let parent = scopes.last().map(|parent| Scope {
+                        in_scope: parent.in_scope.clone(),
+                        rendered: Namespaces::new(),
+                    });
+                    write_start_tag(&mut output, &tag, parent.as_ref(), algorithm)?
+                } else {
+                    write_start_tag(&mut output, &tag, scopes.last(), algorithm)?
+                };
+                scopes.push(scope);
+            }
+            Event::End(tag) => {
+                scopes.pop();
+                output.push_str("</");
+                output.push_str(&decode(tag.name().as_ref())?);
+                output.push('>');
+                after_root = scopes.is_empty();
+                if let Some((start, depth)) = element_start
+                    && depth == scopes.len()
+                {
+                    return Ok(output.split_off(start));
+                }
+            }
+            Event::Text(text) if !is_root_level => {
+                escape_text(&mut output, &decode(&text)?);
+            }
+            Event::CData(text) if !is_root_level => {
+                escape_text(&mut output, &decode(&text)?);
+            }
+            Event::GeneralRef(reference) if !is_root_level => {
+                match reference.resolve_char_ref().without_span()? {
+                    Some(c) => escape_text(&mut output, c.encode_utf8(&mut [0; 4])),
+                    None => {
+                        let entity = format!("&{};", decode(&reference)?);
+                        escape_text(&mut output, &unescape(&entity).without_span()?);
+                    }
+                }
+            }
+            Event::Comment(comment) if algorithm.with_comments() => {
+                write_comment_or_pi(&mut output, is_root_level, after_root, || {
+                    format!("<!--{}-->", String::from_utf8_lossy(&comment))
+                });
+            }
+            Event::PI(pi) => {
+                let content = decode(&pi)?;
+                let (target, data) = content
+                    .split_once(|c: char| c.is_ascii_whitespace())
+                    .map_or((content.as_str(), ""), |(target, data)| {
+                        (target, data.trim_start())
+                    });
+                write_comment_or_pi(&mut output, is_root_level, after_root, || {
+                    if data.is_empty() {
+                        format!("<?{target}?>")
+                    } else {
+                        format!("<?{target} {data}?>")
+                    }
+                });
+            }
+            Event::Eof => break,
+            // Declarations, document types, whitespace outside of the root
+            // element and comments when not including them are all removed
+            _ => {}
+        }
+    }
+    if !scopes.is_empty() || element.is_some() {
+        return Err(EMLErrorKind::UnexpectedEof).without_span();
+    }
+    Ok(output)
+}
+
+/// Writes a comment or processing instruction. Outside of the root element,
+/// these are separated from the root element by a line feed.
+fn write_comment_or_pi(
+    output: &mut String,
+    is_root_level: bool,
+    after_root: bool,
+    node: impl FnOnce() -> String,
+) {
+    if is_root_level && after_root {
+        output.push('\n');
+    }
+    output.push_str(&node());
+    if is_root_level && !after_root {
+        output.push('\n');
+    }
+}
+
+/// Writes the canonical form of a start tag, returning the namespace scope of
+/// the element.
+fn write_start_tag(
+    output: &mut String,
+    tag: &BytesStart<'_>,
+    parent: Option<&Scope>,
+    algorithm: CanonicalizationAlgorithm,
+) -> Result<Scope, EMLError> {
+    let mut in_scope = parent.map(|p| p.in_scope.clone()).unwrap_or_default();
+    let mut rendered = parent.map(|p| p.rendered.clone()).unwrap_or_default();
+
+    let mut attributes = Vec::new();
+    for attr in tag.attributes() {
+        let attr = attr.without_span()?;
+        let key = decode(attr.key.as_ref())?;
+        let value = attribute_value(&attr.value)?;
+        if key == "xmlns" {
+            in_scope.insert(None, value);
+        } else if let Some(prefix) = key.strip_prefix("xmlns:") {
+            in_scope.insert(Some(prefix.to_string()), value);
+        } else {
+            attributes.push((key, value));
+        }
+    }
+
+    let name = decode(tag.name().as_ref())?;
+    let mut used_prefixes = vec![prefix_of(&name)];
+    let mut sorted_attributes = Vec::with_capacity(attributes.len());
+    for (key, value) in attributes {
+        let prefix = prefix_of(&key);
+        let namespace = match prefix.as_deref() {
+            None => String::new(),
+            Some("xml") => NS_XML.to_string(),
+            Some(_) => {
+                used_prefixes.push(prefix.clone());
+                namespace_uri(&in_scope, &prefix)?
+            }
+        };
+        let local_name = key.split_once(':').map_or(key.as_str(), |(_, l)| l);
+        sorted_attributes.push(((namespace, local_name.to_string()), key, value));
+    }
+    sorted_attributes.sort();
+
+    // Namespace declarations that need to be written on this element
+    let candidates: Vec<Option<String>> = if algorithm.is_exclusive() {
+        used_prefixes
+    } else {
+        in_scope.keys().cloned().collect()
+    };
+    let mut declarations = Namespaces::new();
+    for prefix in candidates {
+        if prefix.as_deref() == Some("xml") {
+            continue;
+        }
+        let uri = match &prefix {
+            None => in_scope.get(&None).cloned().unwrap_or_default(),
+            Some(_) => namespace_uri(&in_scope, &prefix)?,
+        };
+        let current = rendered
+            .get(&prefix)
+            .map(String::as_str)
+            .unwrap_or_default();
+        if current != uri || (prefix.is_some() && !rendered.contains_key(&prefix)) {
+            declarations.insert(prefix.clone(), uri.clone());
+            rendered.insert(prefix, uri);
+        }
+    }
+
+    output.push('<');
+    output.push_str(&name);
+    // The default namespace (None) is sorted before all prefixes
+    for (prefix, uri) in &declarations {
+        match prefix {
+            Some(prefix) => {
+                output.push_str(" xmlns:");
+                output.push_str(prefix);
+            }
+            None => output.push_str(" xmlns"),
+        }
+        output.push_str("=\"");
+        escape_attribute(output, uri);
+        output.push('"');
+    }
+    for (_, key, value) in &sorted_attributes {
+        output.push(' ');
+        output.push_str(key);
+        output.push_str("=\"");
+        escape_attribute(output, value);
+        output.push('"');
+    }
+    output.push('>');
+
+    Ok(Scope { in_scope, rendered })
+}
+
+/// Returns the prefix of a qualified name.
+fn prefix_of(name: &str) -> Option<String> {
+    name.split_once(':').map(|(prefix, _)| prefix.to_string())
+}
+
+/// Returns the namespace URI bound to the given prefix.
+fn namespace_uri(in_scope: &Namespaces, prefix: &Option<String>) -> Result<String, EMLError> {
+    in_scope
+        .get(prefix)
+        .cloned()
+        .ok_or_else(|| EMLErrorKind::UnknownNamespace(prefix.clone().unwrap_or_default()))
+        .without_span()
+}
+
+/// Decodes the normalized value of an attribute: literal whitespace is
+/// replaced by spaces and references are replaced by their characters.
+fn attribute_value(raw: &[u8]) -> Result<String, EMLError> {
+    let raw = decode(raw)?.replace(['\t', '\n'], " ");
+    Ok(unescape(&raw).without_span()?.into_owned())
+}
+
+fn decode(bytes: &[u8]) -> Result<String, EMLError> {
+    Ok(std::str::from_utf8(bytes)
+        .map_err(|e| quick_xml::Error::Encoding(e.into()))
+        .without_span()?
+        .to_string())
+}
+
+fn escape_text(output: &mut String, text: &str) {
+    for c in text.chars() {
+        match c {
+            '&' => output.push_str("&amp;"),
+            '<' => output.push_str("&lt;"),
+            '>' => output.push_str("&gt;"),
+            '\r' => output.push_str("&#xD;"),
+            c => output.push(c),
+        }
+    }
+}
+
+fn escape_attribute(output: &mut String, value: &str) {
+    for c in value.chars() {
+        match c {
+            '&' => output.push_str("&amp;"),
+            '<' => output.push_str("&lt;"),
+            '"' => output.push_str("&quot;"),
+            '\t' => output.push_str("&#x9;"),
+            '\n' => output.push_str("&#xA;"),
+            '\r' => output.push_str("&#xD;"),
+            c => output.push(c),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn c14n(xml: &str) -> String {
+        canonicalize(xml, CanonicalizationAlgorithm::C14N).unwrap()
+    }
+
+    #[test]
+    fn test_algorithm_uris() {
+        for algorithm in [
+            CanonicalizationAlgorithm::C14N,
+            CanonicalizationAlgorithm::C14NWithComments,
+            CanonicalizationAlgorithm::ExclusiveC14N,
+            CanonicalizationAlgorithm::ExclusiveC14NWithComments,
+        ] {
+            assert_eq!(
+                CanonicalizationAlgorithm::from_uri(algorithm.uri()),
+                Some(algorithm)
+            );
+        }
+        assert_eq!(CanonicalizationAlgorithm::from_uri("urn:unknown"), None);
+    }
+
+    // Examples from section 3 of the Canonical XML 1.0 specification
+    #[test]
+    fn test_pis_comments_and_outside_of_document_element() {
+        let xml = "<?xml version=\"1.0\"?>\n\n<?xml-stylesheet   href=\"doc.xsl\"\n   type=\"text/xsl\"   ?>\n\n<doc>Hello, world!<!-- Comment 1 --></doc>\n\n<?pi-without-data     ?>\n\n<!-- Comment 2 -->\n\n<!-- Comment 3 -->";
+        assert_eq!(
+            c14n(xml),
+            "<?xml-stylesheet href=\"doc.xsl\"\n   type=\"text/xsl\"   ?>\n<doc>Hello, world!</doc>\n<?pi-without-data?>"
+        );
+        assert_eq!(
+            canonicalize(xml, CanonicalizationAlgorithm::C14NWithComments).unwrap(),
+            "<?xml-stylesheet href=\"doc.xsl\"\n   type=\"text/xsl\"   ?>\n<doc>Hello, world!<!-- Comment 1 --></doc>\n<?pi-without-data?>\n<!-- Comment 2 -->\n<!-- Comment 3 -->"
+        );
+    }
+
+    #[test]
+    fn test_start_and_end_tags() {
+        let xml = r#"<doc>
+   <e1   />
+   <e2   ></e2>
+   <e3   name = "elem3"   id="elem3"   />
+   <e4   name="elem4"   id="elem4"   ></e4>
+   <e5 a:attr="out" b:attr="sorted" attr2="all" attr="I'm"
+      xmlns:b="http://www.ietf.org"
+      xmlns:a="http://www.w3.org"
+      xmlns="http://example.org"/>
+   <e6 xmlns="" xmlns:a="http://www.w3.org">
+      <e7 xmlns="http://www.ietf.org">
+         <e8 xmlns="" xmlns:a="http://www.w3.org">
+            <e9 xmlns="" xmlns:a="http://www.ietf.org"/>
+         </e8>
+      </e7>
+   </e6>
+</doc>"#;
+        assert_eq!(
+            c14n(xml),
+            r#"<doc>
+   <e1></e1>
+   <e2></e2>
+   <e3 id="elem3" name="elem3"></e3>
+   <e4 id="elem4" name="elem4"></e4>
+   <e5 xmlns="http://example.org" xmlns:a="http://www.w3.org" xmlns:b="http://www.ietf.org" attr="I'm" attr2="all" b:attr="sorted" a:attr="out"></e5>
+   <e6 xmlns:a="http://www.w3.org">
+      <e7 xmlns="http://www.ietf.org">
+         <e8 xmlns="">
+            <e9 xmlns:a="http://www.ietf.org"></e9>
+         </e8>
+      </e7>
+   </e6>
+</doc>"#
+        );
+    }
+
+    #[test]
+    fn test_character_modifications_and_references() {
+        let xml = "<doc>\r\n   <text>First line&#x0d;&#10;Second line</text>\n   <value>&#x32;</value>\n   <compute><![CDATA[value>\"0\" && value<\"10\" ?\"valid\":\"error\"]]></compute>\n   <norm attr=' &apos;   &#x20;&#13;&#xa;&#9;   &apos; '/>\n</doc>";
+        assert_eq!(
+            c14n(xml),
+            "<doc>\n   <text>First line&#xD;\nSecond line</text>\n   <value>2</value>\n   <compute>value&gt;\"0\" &amp;&amp; value&lt;\"10\" ?\"valid\":\"error\"</compute>\n   <norm attr=\" '    &#xD;&#xA;&#x9;   ' \"></norm>\n</doc>"
+        );
+    }
+
+    #[test]
+    fn test_exclusive_namespaces() {
+        let xml = r#"<n0:root xmlns:n0="urn:n0" xmlns:n1="urn:n1" xmlns:n2="urn:n2"><n1:elem n2:attr="x"><n1:child/></n1:elem></n0:root>"#;
+        assert_eq!(
+            canonicalize(xml, CanonicalizationAlgorithm::ExclusiveC14N).unwrap(),
+            r#"<n0:root xmlns:n0="urn:n0"><n1:elem xmlns:n1="urn:n1" xmlns:n2="urn:n2" n2:attr="x"><n1:child></n1:child></n1:elem></n0:root>"#
+        );
+        assert_eq!(
+            c14n(xml),
+            r#"<n0:root xmlns:n0="urn:n0" xmlns:n1="urn:n1" xmlns:n2="urn:n2"><n1:elem n2:attr="x"><n1:child></n1:child></n1:elem></n0:root>"#
+        );
+    }
+
+    #[test]
+    fn test_canonicalize_element() {
+        let xml = r#"<n0:root xmlns:n0="urn:n0" xmlns:n1="urn:n1"><n1:elem/><n1:elem b="2"   a="1"><n1:child/></n1:elem></n0:root>"#;
+        let offset = xml.find("<n1:elem b").unwrap();
+        assert_eq!(
+            canonicalize_element(xml, offset, CanonicalizationAlgorithm::C14N).unwrap(),
+            r#"<n1:elem xmlns:n0="urn:n0" xmlns:n1="urn:n1" a="1" b="2"><n1:child></n1:child></n1:elem>"#
+        );
+        assert_eq!(
+            canonicalize_element(xml, offset, CanonicalizationAlgorithm::ExclusiveC14N).unwrap(),
+            r#"<n1:elem xmlns:n1="urn:n1" a="1" b="2"><n1:child></n1:child></n1:elem>"#
+        );
+        assert!(canonicalize_element(xml, offset + 1, CanonicalizationAlgorithm::C14N).is_err());
+    }
+}
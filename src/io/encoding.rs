@@ -0,0 +1,333 @@
+use std::borrow::Cow;
+
+use encoding_rs::{DecoderResult, Encoding, UTF_8, UTF_16BE, UTF_16LE};
+use quick_xml::encoding::EncodingError;
+
+use crate::{EMLError, EMLErrorKind, EMLResultExt, io::Span};
+
+/// A document decoded to UTF-8, with the information needed to convert
+/// positions in the decoded document back to positions in the original bytes.
+pub(crate) struct DecodedDocument<'a> {
+    /// The decoded document.
+    pub(crate) text: Cow<'a, str>,
+    /// Runs of characters that have the same length in both the decoded and
+    /// the original document, ordered by their offset in the decoded document.
+    runs: Vec<Run>,
+}
+
+/// A run of characters that all have the same length in the decoded and in
+/// the original document.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Run {
+    /// Offset of the run in the decoded document.
+    decoded_offset: u64,
+    /// Offset of the run in the original document.
+    original_offset: u64,
+    /// Length of each character of the run in the decoded document.
+    decoded_len: u64,
+    /// Length of each character of the run in the original document.
+    original_len: u64,
+}
+
+impl DecodedDocument<'_> {
+    /// Converts a span in the decoded document to a span in the original bytes.
+    pub(crate) fn original_span(&self, span: Span) -> Span {
+        Span::new(
+            self.original_offset(span.start),
+            self.original_offset(span.end),
+        )
+    }
+
+    fn original_offset(&self, offset: u64) -> u64 {
+        let index = self
+            .runs
+            .partition_point(|run| run.decoded_offset <= offset);
+        match index.checked_sub(1).map(|i| self.runs[i]) {
+            Some(run) => {
+                let chars = (offset - run.decoded_offset) / run.decoded_len;
+                run.original_offset + chars * run.original_len
+            }
+            None => offset,
+        }
+    }
+}
+
+/// Decodes an XML document to UTF-8.
+///
+/// The encoding is determined from a byte order mark if present, otherwise
+/// from the encoding in the XML declaration. Documents without either are
+/// read as UTF-8. Only UTF-8, UTF-16 and single byte encodings (such as
+/// ISO-8859-1 and windows-1252) are supported.
+pub(crate) fn decode_document(input: &[u8]) -> Result<DecodedDocument<'_>, EMLError> {
+    let (encoding, bom_len) = match Encoding::for_bom(input) {
+        Some((encoding, bom_len)) => (encoding, bom_len),
+        None => (detect_encoding(input)?, 0),
+    };
+    let bytes = &input[bom_len..];
+
+    if encoding == UTF_8 {
+        let text = std::str::from_utf8(bytes).map_err(|e| {
+            let start = (bom_len + e.valid_up_to()) as u64;
+            let end = start + e.error_len().unwrap_or(bytes.len() - e.valid_up_to()) as u64;
+            EMLErrorKind::EncodingError(EncodingError::Utf8(e)).add_span(Span::new(start, end))
+        })?;
+        let runs = if bom_len > 0 {
+            vec![Run {
+                decoded_offset: 0,
+                original_offset: bom_len as u64,
+                decoded_len: 1,
+                original_len: 1,
+            }]
+        } else {
+            vec![]
+        };
+        return Ok(DecodedDocument {
+            text: Cow::Borrowed(text),
+            runs,
+        });
+    }
+
+    let text = decode_with(encoding, bytes, bom_len)?;
+    let runs = character_runs(&text, encoding, bom_len as u64);
+    Ok(DecodedDocument {
+        text: Cow::Owned(text),
+        runs,
+    })
+}
+
+/// Decodes an EML document to UTF-8, in the same way as
+/// [`EMLRead::parse_eml_bytes`](crate::io::EMLRead::parse_eml_bytes).
+///
+/// Errors of parsing the decoded text with
+/// [`EMLRead::parse_eml`](crate::io::EMLRead::parse_eml) have spans in the
+/// decoded text, so they can be shown with a
+/// [`Diagnostic`](crate::diagnostics::Diagnostic) of that text. Spans in an
+/// error returned by this function are byte offsets in the given input.
+pub fn decode_eml_bytes(input: &[u8]) -> Result<Cow<'_, str>, EMLError> {
+    decode_document(input).map(|decoded| decoded.text)
+}
+
+/// Determines the encoding of a document without a byte order mark.
+fn detect_encoding(input: &[u8]) -> Result<&'static Encoding, EMLError> {
+    // UTF-16 without a byte order mark, starting with `<?`
+    match input {
+        [b'<', 0, b'?', 0, ..] => return Ok(UTF_16LE),
+        [0, b'<', 0, b'?', ..] => return Ok(UTF_16BE),
+        _ => {}
+    }
+
+    let Some((label, span)) = declared_encoding(input) else {
+        return Ok(UTF_8);
+    };
+    let encoding = Encoding::for_label(label).ok_or_else(|| {
+        EMLErrorKind::UnsupportedEncoding(String::from_utf8_lossy(label).into_owned())
+            .add_span(span)
+    })?;
+
+    if encoding == UTF_16LE || encoding == UTF_16BE {
+        // The declaration could be read as ASCII, so it cannot be UTF-16
+        Ok(UTF_8)
+    } else if encoding.is_single_byte() || encoding == UTF_8 {
+        Ok(encoding)
+    } else {
+        Err(EMLErrorKind::UnsupportedEncoding(
+            encoding.name().to_string(),
+        ))
+        .with_span(span)
+    }
+}
+
+/// Returns the value of the encoding in the XML declaration, and its span.
+fn declared_encoding(input: &[u8]) -> Option<(&[u8], Span)> {
+    let end = input
+        .strip_prefix(b"<?xml")?
+        .windows(2)
+        .position(|w| w == b"?>")?
+        + 5;
+    let declaration = &input[..end];
+    let mut rest = declaration
+        .windows(8)
+        .position(|w| w == b"encoding")
+        .map(|i| i + 8)?;
+
+    let skip_whitespace = |mut i: usize| {
+        while declaration.get(i).is_some_and(u8::is_ascii_whitespace) {
+            i += 1;
+        }
+        i
+    };
+    rest = skip_whitespace(rest);
+    if declaration.get(rest) != Some(&b'=') {
+        return None;
+    }
+    rest = skip_whitespace(rest + 1);
+    let quote = *declaration
+        .get(rest)
+        .filter(|q| matches!(q, b'"' | b'\''))?;
+    let start = rest + 1;
+    let len = declaration[start..].iter().position(|b| *b == quote)?;
+    Some((
+        &declaration[start..start + len],
+        Span::new(start as u64, (start + len) as u64),
+    ))
+}
+
+/// Decodes the bytes with the given encoding, failing on malformed input.
+///
+/// The `offset` is the position of the bytes in the original document.
+fn decode_with(
+    encoding: &'static Encoding,
+    bytes: &[u8],
+    offset: usize,
+) -> Result<String, EMLError> {
+    let mut decoder = encoding.new_decoder_without_bom_handling();
+    let mut text = String::with_capacity(
+        decoder
+            .max_utf8_buffer_length_without_replacement(bytes.len())
+            .unwrap_or(bytes.len()),
+    );
+    let (result, read) = decoder.decode_to_string_without_replacement(bytes, &mut text, true);
+    match result {
+        DecoderResult::InputEmpty => Ok(text),
+        DecoderResult::Malformed(malformed, unread) => {
+            let end = (offset + read - unread as usize) as u64;
+            Err(EMLErrorKind::EncodingError(EncodingError::Other(encoding)))
+                .with_span(Span::new(end - malformed as u64, end))
+        }
+        DecoderResult::OutputFull => {
+            unreachable!("Buffer was allocated for the maximum decoded length")
+        }
+    }
+}
+
+/// Determines the runs of characters with the same length in the decoded
+/// document and the original document.
+fn character_runs(text: &str, encoding: &'static Encoding, offset: u64) -> Vec<Run> {
+    let mut runs: Vec<Run> = Vec::new();
+    let mut decoded_offset = 0;
+    let mut original_offset = offset;
+    for c in text.chars() {
+        let decoded_len = c.len_utf8() as u64;
+        let original_len = if encoding.is_single_byte() {
+            1
+        } else {
+            2 * c.len_utf16() as u64
+        };
+        if runs
+            .last()
+            .is_none_or(|run| run.decoded_len != decoded_len || run.original_len != original_len)
+        {
+            runs.push(Run {
+                decoded_offset,
+                original_offset,
+                decoded_len,
+                original_len,
+            });
+        }
+        decoded_offset += decoded_len;
+        original_offset += original_len;
+    }
+    runs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_encodings() {
+        let xml = "<?xml version=\"1.0\" encoding=\"ISO-8859-1\"?><a>é€</a>";
+        let (latin1, _, _) = encoding_rs::WINDOWS_1252.encode(xml);
+        let decoded = decode_document(&latin1).unwrap();
+        assert_eq!(decoded.text, xml);
+        // The `</a>` after the two single byte characters
+        let end_tag = xml.find("</a>").unwrap() as u64;
+        assert_eq!(
+            decoded.original_span(Span::new(end_tag, end_tag + 4)),
+            Span::new(end_tag - 3, end_tag + 1)
+        );
+
+        let xml = "<a>é𝄞</a>";
+        let mut utf16 = vec![0xFF, 0xFE];
+        utf16.extend(xml.encode_utf16().flat_map(u16::to_le_bytes));
+        let decoded = decode_document(&utf16).unwrap();
+        assert_eq!(decoded.text, xml);
+        assert_eq!(decoded.original_span(Span::new(0, 3)), Span::new(2, 8));
+        let end_tag = xml.find("</a>").unwrap() as u64;
+        assert_eq!(
+            decoded.original_span(Span::new(end_tag, end_tag + 4)),
+            Span::new(2 + 12, 2 + 20)
+        );
+
+        let xml = "<?xml version='1.0' encoding = 'utf-8' ?><a/>";
+        let mut utf8 = vec![0xEF, 0xBB, 0xBF];
+        utf8.extend_from_slice(xml.as_bytes());
+        let decoded = decode_document(&utf8).unwrap();
+        assert_eq!(decoded.text, xml);
+        assert_eq!(decoded.original_span(Span::new(0, 5)), Span::new(3, 8));
+    }
+
+    #[test]
+    fn test_decode_errors() {
+        let err = decode_document(b"<a>\xff</a>").err().unwrap();
+        assert!(matches!(err.kind(), EMLErrorKind::EncodingError(_)));
+        assert_eq!(err.span(), Some(Span::new(3, 4)));
+
+        let err = decode_document(b"<?xml version=\"1.0\" encoding=\"EBCDIC\"?><a/>")
+            .err()
+            .unwrap();
+        assert!(matches!(err.kind(), EMLErrorKind::UnsupportedEncoding(_)));
+        assert_eq!(err.span(), Some(Span::new(30, 36)));
+
+        // Unpaired surrogate in UTF-16
+        let err = decode_document(b"\xff\xfe<\0a\0>\0\x00\xd8").err().unwrap();
+        assert!(matches!(err.kind(), EMLErrorKind::EncodingError(_)));
+        assert_eq!(err.span(), Some(Span::new(8, 10)));
+    }
+
+    #[test]
+    fn test_parse_eml_bytes() {
+        use crate::{
+            documents::count::Count,
+            io::{EMLParsingMode, EMLRead},
+        };
+
+        let doc = include_str!("../../test-emls/deserialize_eml510b_test.eml.xml")
+            .replacen("encoding=\"UTF-8\"", "encoding=\"ISO-8859-1\"", 1)
+            .replacen(
+                "Municipal Election",
+                "Gemeenteraadsverkiezing Zuidwest-Fryslân",
+                1,
+            )
+            .replacen("<Cast>", "<Cast>x", 1);
+        let expected = Count::parse_eml(&doc, EMLParsingMode::StrictFallback)
+            .ok_with_errors()
+            .unwrap();
+        let (latin1, _, _) = encoding_rs::WINDOWS_1252.encode(&doc);
+
+        let (count, errors) = Count::parse_eml_bytes(&latin1, EMLParsingMode::StrictFallback)
+            .ok_with_errors()
+            .unwrap();
+        assert_eq!(count, expected.0);
+        assert_eq!(errors.len(), 1);
+        // The error comes after the single byte `â`
+        let span = expected.1[0].span().unwrap();
+        assert_eq!(
+            errors[0].span(),
+            Some(Span::new(span.start - 1, span.end - 1))
+        );
+
+        let from_reader = Count::parse_eml_reader(&latin1[..], EMLParsingMode::StrictFallback)
+            .ok_with_errors()
+            .unwrap();
+        assert_eq!(from_reader.0, expected.0);
+
+        // Decoding first gives spans in the decoded text
+        let decoded = decode_eml_bytes(&latin1).unwrap();
+        assert_eq!(decoded, doc);
+        let (_, errors) = Count::parse_eml(&decoded, EMLParsingMode::StrictFallback)
+            .ok_with_errors()
+            .unwrap();
+        assert_eq!(errors[0].span(), Some(span));
+    }
+}
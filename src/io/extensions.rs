@@ -0,0 +1,109 @@
+use crate::io::OwnedQualifiedName;
+
+/// Content of an element that is not part of the model, preserved so that it
+/// can be written back when writing the document.
+///
+/// Unknown content is only preserved when reading with
+/// [`EMLReadOptions::preserve_unknown`](crate::io::EMLReadOptions::preserve_unknown)
+/// enabled, otherwise the extensions of all elements are empty. Unknown child
+/// elements are still reported as
+/// [`UnexpectedElement`](crate::EMLErrorKind::UnexpectedElement) errors when
+/// they are preserved.
+///
+/// Comments and processing instructions are only preserved when reading with
+/// [`EMLReadOptions::preserve_comments`](crate::io::EMLReadOptions::preserve_comments)
+/// enabled. They are attached to the closest parent element in the model, so
+/// annotations inside an element that is not part of the model end up in its
+/// parent instead.
+///
+/// Only elements that contain child elements carry extensions. Unknown
+/// attributes on elements that only contain a text value are not preserved,
+/// and neither are comments inside such elements or outside of the root
+/// element.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Extensions {
+    /// Attributes of the element that were not read while parsing the element.
+    pub attributes: Vec<UnknownAttribute>,
+
+    /// Child elements that were not expected at their position, in document
+    /// order.
+    pub elements: Vec<UnknownElement>,
+
+    /// Comments and processing instructions in between the children of the
+    /// element, in document order.
+    pub annotations: Vec<Annotation>,
+}
+
+impl Extensions {
+    /// Returns true if no unknown content was preserved.
+    pub fn is_empty(&self) -> bool {
+        self.attributes.is_empty() && self.elements.is_empty() && self.annotations.is_empty()
+    }
+}
+
+/// An attribute that is not part of the model.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UnknownAttribute {
+    /// Name and namespace of the attribute.
+    pub name: OwnedQualifiedName,
+
+    /// Prefix used for the attribute in the original document, if any. The
+    /// prefix is reused when the namespace is not known when writing.
+    pub prefix: Option<String>,
+
+    /// Value of the attribute, with entities already unescaped.
+    pub value: String,
+}
+
+/// A child element that is not part of the model, stored as a raw XML fragment.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UnknownElement {
+    /// Name and namespace of the element.
+    pub name: OwnedQualifiedName,
+
+    /// Number of known child elements that preceded this element in the
+    /// parent. The element is written after that many known child elements.
+    pub position: usize,
+
+    /// Namespace declarations from outside of the fragment that are used in
+    /// the fragment, as pairs of prefix (None for the default namespace) and
+    /// namespace URI. An empty URI means the name is not in any namespace.
+    pub namespaces: Vec<(Option<String>, String)>,
+
+    /// The original XML of the element, from its start tag up to and including
+    /// its end tag.
+    pub xml: String,
+}
+
+/// A comment or processing instruction in between the children of an element.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Annotation {
+    /// Whether this is a comment or a processing instruction.
+    pub kind: AnnotationKind,
+
+    /// The raw content, without the `<!--`/`-->` or `<?`/`?>` delimiters.
+    pub content: String,
+
+    /// Number of known child elements that preceded this annotation in the
+    /// parent.
+    pub position: usize,
+
+    /// Number of preserved unknown elements that preceded this annotation in
+    /// the parent, to keep the order of annotations and unknown elements at
+    /// the same position.
+    pub preceding_elements: usize,
+}
+
+/// The kind of an [`Annotation`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AnnotationKind {
+    /// An XML comment, `<!-- ... -->`.
+    Comment,
+    /// A processing instruction, `<? ... ?>`.
+    ProcessingInstruction,
+}
@@ -1,11 +1,19 @@
 //! Reading and writing EML_NL documents.
 
+mod c14n;
+mod encoding;
+mod extensions;
 mod qualified_name;
 mod reader;
+mod stream;
 mod writer;
 
+pub use c14n::*;
+pub use encoding::decode_eml_bytes;
+pub use extensions::*;
 pub use qualified_name::*;
 pub use reader::*;
+pub use stream::*;
 pub use writer::*;
 
 use crate::EMLError;
@@ -21,6 +21,7 @@ where
 
 /// A qualified XML name, consisting of a local name and an optional namespace URI.
 #[derive(Clone, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct QualifiedName<'a, 'b> {
     /// Local name of the qualified name.
     pub local_name: Cow<'a, str>,
@@ -98,6 +99,7 @@ impl<'a, 'b> Display for QualifiedName<'a, 'b> {
 
 /// A fully owned qualified name (consisting of local name and optional namespace URI).
 #[derive(Clone, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(transparent)]
 pub struct OwnedQualifiedName(QualifiedName<'static, 'static>);
 
@@ -1,4 +1,4 @@
-use std::{borrow::Cow, collections::HashMap};
+use std::{borrow::Cow, cell::RefCell, collections::HashMap, io::Read};
 
 use quick_xml::{
     NsReader,
@@ -10,11 +10,14 @@ use quick_xml::{
 use crate::{
     MultipleEMLErrors,
     error::{EMLError, EMLErrorKind, EMLResultExt},
-    io::QualifiedName,
+    io::{
+        Annotation, AnnotationKind, OwnedQualifiedName, QualifiedName, UnknownAttribute,
+        UnknownElement, encoding::decode_document,
+    },
     utils::{StringValue, StringValueData},
 };
 
-/// Reading EML documents from a string slice.
+/// Reading EML documents from a string slice, bytes or a reader.
 pub trait EMLRead {
     /// Parse an EML document from the given string slice.
     ///
@@ -24,9 +27,52 @@ pub trait EMLRead {
     /// will be collected and the raw string value will be used instead. If set
     /// to Loose, no parsing will be performed and all values will be stored as
     /// raw strings.
-    fn parse_eml(input: &str, parsing_mode: EMLParsingMode) -> EMLReadResult<Self>
+    ///
+    /// Instead of only a parsing mode, [`EMLReadOptions`] can be given to
+    /// enable additional reading options.
+    fn parse_eml(input: &str, options: impl Into<EMLReadOptions>) -> EMLReadResult<Self>
     where
         Self: Sized;
+
+    /// Parse an EML document from the given bytes.
+    ///
+    /// The encoding of the document is determined from a byte order mark, or
+    /// otherwise from the encoding in the XML declaration, defaulting to UTF-8.
+    /// UTF-8, UTF-16 and single byte encodings such as ISO-8859-1 are
+    /// supported. Spans in errors are byte offsets in the given input, to get
+    /// spans in the decoded text use [`decode_eml_bytes`](crate::io::decode_eml_bytes)
+    /// and [`parse_eml`](EMLRead::parse_eml) instead.
+    ///
+    /// See [`parse_eml`](EMLRead::parse_eml) for the options.
+    fn parse_eml_bytes(input: &[u8], options: impl Into<EMLReadOptions>) -> EMLReadResult<Self>
+    where
+        Self: Sized,
+    {
+        let decoded = match decode_document(input) {
+            Ok(decoded) => decoded,
+            Err(e) => return EMLReadResult::Err(e),
+        };
+        Self::parse_eml(&decoded.text, options).map_spans(&|span| decoded.original_span(span))
+    }
+
+    /// Parse an EML document read from the given reader.
+    ///
+    /// The entire document is read into memory before parsing. See
+    /// [`parse_eml_bytes`](EMLRead::parse_eml_bytes) for how the encoding
+    /// is determined.
+    fn parse_eml_reader(
+        mut reader: impl Read,
+        options: impl Into<EMLReadOptions>,
+    ) -> EMLReadResult<Self>
+    where
+        Self: Sized,
+    {
+        let mut input = Vec::new();
+        if let Err(e) = reader.read_to_end(&mut input).without_span() {
+            return EMLReadResult::Err(e);
+        }
+        Self::parse_eml_bytes(&input, options)
+    }
 }
 
 /// The result of reading an EML document, which may include non-fatal errors.
@@ -47,6 +93,16 @@ impl<T> EMLReadResult<T> {
         }
     }
 
+    /// Maps the spans of all errors in this result.
+    pub(crate) fn map_spans(self, f: &impl Fn(Span) -> Span) -> Self {
+        match self {
+            EMLReadResult::Ok(value, errors) => {
+                EMLReadResult::Ok(value, errors.into_iter().map(|e| e.map_spans(f)).collect())
+            }
+            EMLReadResult::Err(e) => EMLReadResult::Err(e.map_spans(f)),
+        }
+    }
+
     /// Converts this result into a standard Result, returning the value if
     /// successful, or the error(s) if not.
     pub fn ok(self) -> Result<T, EMLError> {
@@ -82,11 +138,11 @@ impl<T> EMLRead for T
 where
     T: EMLReadElement + 'static,
 {
-    fn parse_eml(input: &str, parsing_mode: EMLParsingMode) -> EMLReadResult<Self>
+    fn parse_eml(input: &str, options: impl Into<EMLReadOptions>) -> EMLReadResult<Self>
     where
         Self: Sized + 'static,
     {
-        let mut reader = EMLReader::init_from_str(input, parsing_mode);
+        let mut reader = EMLReader::init_from_str(input, options);
         let res = reader.with_next_element(|r| T::read_eml_element(r));
 
         let e = match res {
@@ -111,6 +167,7 @@ pub(crate) trait EMLReadElement {
 
 /// A span in the input data, represented as byte offsets.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Span {
     /// Start byte offset of the span (inclusive).
     pub start: u64,
@@ -133,6 +190,7 @@ impl std::fmt::Display for Span {
 
 /// The mode to use when parsing stringly values in EML files.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum EMLParsingMode {
     /// Require strict parsing of all stringly values to their respective types
     Strict,
@@ -153,26 +211,82 @@ impl EMLParsingMode {
     }
 }
 
+/// Options for reading EML documents.
+///
+/// A [`EMLParsingMode`] can be converted into options with all other options
+/// disabled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EMLReadOptions {
+    /// The mode to use when parsing stringly values.
+    pub parsing_mode: EMLParsingMode,
+
+    /// Whether unknown elements and attributes should be preserved in the
+    /// [`Extensions`](crate::io::Extensions) of the model, so they are written
+    /// again when writing the document.
+    pub preserve_unknown: bool,
+
+    /// Whether comments and processing instructions should be preserved in
+    /// the [`Extensions`](crate::io::Extensions) of the model, so they are
+    /// written again when writing the document.
+    pub preserve_comments: bool,
+}
+
+impl EMLReadOptions {
+    /// Create options with the given parsing mode and all other options disabled.
+    pub fn new(parsing_mode: EMLParsingMode) -> EMLReadOptions {
+        EMLReadOptions {
+            parsing_mode,
+            preserve_unknown: false,
+            preserve_comments: false,
+        }
+    }
+
+    /// Set whether unknown elements and attributes should be preserved.
+    pub fn with_preserve_unknown(mut self, preserve_unknown: bool) -> EMLReadOptions {
+        self.preserve_unknown = preserve_unknown;
+        self
+    }
+
+    /// Set whether comments and processing instructions should be preserved.
+    pub fn with_preserve_comments(mut self, preserve_comments: bool) -> EMLReadOptions {
+        self.preserve_comments = preserve_comments;
+        self
+    }
+}
+
+impl From<EMLParsingMode> for EMLReadOptions {
+    fn from(parsing_mode: EMLParsingMode) -> Self {
+        EMLReadOptions::new(parsing_mode)
+    }
+}
+
 /// The main EML XML reader.
 ///
 /// We require all EML files to be fully loaded in memory, so this reader only
 /// works on byte slices. Furthermore, all files should be encoded in UTF-8.
 pub(crate) struct EMLReader<'a> {
     inner: NsReader<&'a [u8]>,
+    input: &'a str,
     parsing_mode: EMLParsingMode,
+    preserve_unknown: bool,
+    preserve_comments: bool,
+    /// Comments and processing instructions that were read but not yet
+    /// attached to an element.
+    pending_annotations: Vec<(AnnotationKind, String, Span)>,
     errors: Vec<EMLError>,
 }
 
 impl<'a> EMLReader<'a> {
     /// Create this reader from a string slice.
-    pub fn init_from_str(data: &'a str, parsing_mode: EMLParsingMode) -> EMLReader<'a> {
-        Self::from_reader(NsReader::from_str(data), parsing_mode)
-    }
-
-    pub fn from_reader(reader: NsReader<&'a [u8]>, parsing_mode: EMLParsingMode) -> EMLReader<'a> {
+    pub fn init_from_str(data: &'a str, options: impl Into<EMLReadOptions>) -> EMLReader<'a> {
+        let options = options.into();
         EMLReader {
-            inner: reader,
-            parsing_mode,
+            inner: NsReader::from_str(data),
+            input: data,
+            parsing_mode: options.parsing_mode,
+            preserve_unknown: options.preserve_unknown,
+            preserve_comments: options.preserve_comments,
+            pending_annotations: Vec::new(),
             errors: Vec::new(),
         }
     }
@@ -216,6 +330,12 @@ impl<'a> EMLReader<'a> {
         }
     }
 
+    /// Returns the non-fatal errors collected while reading.
+    #[cfg(feature = "xmldsig")]
+    pub(crate) fn into_errors(self) -> Vec<EMLError> {
+        self.errors
+    }
+
     pub fn with_next_element<R>(
         &mut self,
         f: impl for<'d, 'e> FnOnce(&mut EMLElementReader<'d, 'e>) -> Result<R, EMLError>,
@@ -238,6 +358,9 @@ pub(crate) struct EMLElementReader<'r, 'input> {
     is_empty: bool,
     span: Span,
     last_span: Span,
+    /// Raw names of the attributes that were read, only tracked when
+    /// preserving unknown content.
+    read_attributes: RefCell<Vec<Vec<u8>>>,
 }
 
 impl<'r, 'input> EMLElementReader<'r, 'input> {
@@ -261,6 +384,7 @@ impl<'r, 'input> EMLElementReader<'r, 'input> {
             is_empty,
             span,
             last_span: span,
+            read_attributes: RefCell::new(Vec::new()),
         }
     }
 
@@ -303,6 +427,20 @@ impl<'r, 'input> EMLElementReader<'r, 'input> {
                     )));
                 }
                 None => return Ok(None),
+                Some((Event::Comment(text), span)) if self.reader.preserve_comments => {
+                    let content = self.decode(&text, span)?;
+                    self.reader
+                        .pending_annotations
+                        .push((AnnotationKind::Comment, content, span));
+                }
+                Some((Event::PI(pi), span)) if self.reader.preserve_comments => {
+                    let content = self.decode(&pi, span)?;
+                    self.reader.pending_annotations.push((
+                        AnnotationKind::ProcessingInstruction,
+                        content,
+                        span,
+                    ));
+                }
                 _other => {
                     // Ignore other events
                 }
@@ -316,10 +454,20 @@ impl<'r, 'input> EMLElementReader<'r, 'input> {
         &self,
         name: impl Into<QualifiedName<'a, 'b>>,
     ) -> Result<Cow<'_, str>, EMLError> {
+        self.attribute_value_req_with_span(name)
+            .map(|(value, _)| value)
+    }
+
+    /// Get the value of an attribute together with the span of that value. If
+    /// the attribute does not exist this will return an error.
+    pub fn attribute_value_req_with_span<'a, 'b>(
+        &self,
+        name: impl Into<QualifiedName<'a, 'b>>,
+    ) -> Result<(Cow<'_, str>, Span), EMLError> {
         let name = name.into();
-        self.attribute_value(name.clone())?
+        self.attribute_value_with_span(name.clone())?
             .ok_or_else(|| EMLErrorKind::MissingAttribute(name.as_owned()))
-            .with_span(self.span)
+            .with_span(self.name_span())
     }
 
     /// Get the value of an attribute. If the attribute does not exist this will
@@ -328,15 +476,32 @@ impl<'r, 'input> EMLElementReader<'r, 'input> {
         &self,
         name: impl Into<QualifiedName<'a, 'b>>,
     ) -> Result<Option<Cow<'_, str>>, EMLError> {
+        Ok(self
+            .attribute_value_with_span(name)?
+            .map(|(value, _)| value))
+    }
+
+    /// Get the value of an attribute together with the span of that value
+    /// (excluding the quotes). If the attribute does not exist this will
+    /// return None.
+    pub fn attribute_value_with_span<'a, 'b>(
+        &self,
+        name: impl Into<QualifiedName<'a, 'b>>,
+    ) -> Result<Option<(Cow<'_, str>, Span)>, EMLError> {
         let name = name.into();
-        // quick-xml does not expose any way to get the span of individual attributes, so we use the whole start tag span for now
         for attr in self.start.attributes() {
             let attr = attr.with_span(self.span)?;
-            if self.is_resolved_name(attr.key, self.span, name.clone(), true)? {
-                return Ok(Some(
-                    attr.decode_and_unescape_value(self.reader.inner.decoder())
-                        .with_span(self.span)?,
-                ));
+            let (attr_span, value_span) = self.attribute_spans(attr.key.as_ref());
+            if self.is_resolved_name(attr.key, attr_span, name.clone(), true)? {
+                if self.reader.preserve_unknown {
+                    self.read_attributes
+                        .borrow_mut()
+                        .push(attr.key.as_ref().to_vec());
+                }
+                let value = attr
+                    .decode_and_unescape_value(self.reader.inner.decoder())
+                    .with_span(value_span)?;
+                return Ok(Some((value, value_span)));
             }
         }
         Ok(None)
@@ -346,18 +511,53 @@ impl<'r, 'input> EMLElementReader<'r, 'input> {
     #[expect(unused)]
     pub fn attributes(&self) -> Result<HashMap<QualifiedName<'_, '_>, Cow<'_, str>>, EMLError> {
         let mut attributes = HashMap::new();
-        // quick-xml does not expose any way to get the span of individual attributes, so we use the whole start tag span for now
         for attr in self.start.attributes() {
             let attr = attr.with_span(self.span)?;
-            let name = self.get_resolved_name(attr.key, self.span, true)?;
+            let (attr_span, value_span) = self.attribute_spans(attr.key.as_ref());
+            let name = self.get_resolved_name(attr.key, attr_span, true)?;
             let value = attr
                 .decode_and_unescape_value(self.reader.inner.decoder())
-                .with_span(self.span)?;
+                .with_span(value_span)?;
             attributes.insert(name, value);
         }
         Ok(attributes)
     }
 
+    /// Returns the span of the name of this element in its start tag.
+    pub fn name_span(&self) -> Span {
+        let start = self.start_tag_content_offset();
+        Span::new(start, start + self.start.name().as_ref().len() as u64)
+    }
+
+    /// Returns the span of the attribute with the given raw (prefixed) name,
+    /// including its value, and the span of only the value (excluding the
+    /// quotes). Falls back to the span of the start tag if the attribute could
+    /// not be located.
+    fn attribute_spans(&self, raw_name: &[u8]) -> (Span, Span) {
+        match find_attribute(&self.start, raw_name) {
+            Some((name_start, value_start, value_end)) => {
+                let offset = self.start_tag_content_offset();
+                (
+                    // include the closing quote in the attribute span
+                    Span::new(offset + name_start as u64, offset + value_end as u64 + 1),
+                    Span::new(offset + value_start as u64, offset + value_end as u64),
+                )
+            }
+            None => (self.span, self.span),
+        }
+    }
+
+    /// Byte offset in the document of the start tag content, i.e. of the
+    /// element name just after the `<`.
+    fn start_tag_content_offset(&self) -> u64 {
+        // The start tag content excludes the `<` and `>` (and `/` for empty
+        // elements), the span of the start tag ends just after the `>`.
+        let suffix_len = if self.is_empty { 2 } else { 1 };
+        self.span
+            .end
+            .saturating_sub(suffix_len + self.start.len() as u64)
+    }
+
     /// Extracts the text content of this element. If the element is an empty
     /// element or the element contains no text, this returns None.
     pub fn text_without_children_opt(&mut self) -> Result<Option<String>, EMLError> {
@@ -450,6 +650,178 @@ impl<'r, 'input> EMLElementReader<'r, 'input> {
         self.reader.errors.push(err);
     }
 
+    /// Returns whether unknown elements and attributes should be preserved.
+    pub fn preserve_unknown(&self) -> bool {
+        self.reader.preserve_unknown
+    }
+
+    /// Returns whether comments and processing instructions should be preserved.
+    pub fn preserve_comments(&self) -> bool {
+        self.reader.preserve_comments
+    }
+
+    /// Takes the comments and processing instructions that were read by
+    /// [`next_child`](Self::next_child) and not yet taken, placing them at the
+    /// given position.
+    pub fn take_annotations(
+        &mut self,
+        position: usize,
+        preceding_elements: usize,
+    ) -> impl Iterator<Item = Annotation> {
+        std::mem::take(&mut self.reader.pending_annotations)
+            .into_iter()
+            .map(move |(kind, content, _)| Annotation {
+                kind,
+                content,
+                position,
+                preceding_elements,
+            })
+    }
+
+    /// Drops the comments and processing instructions that were read by
+    /// [`next_child`](Self::next_child) and not taken, as the element they are
+    /// in has no [`Extensions`](crate::io::Extensions) to keep them in. An
+    /// error is recorded for each dropped annotation.
+    pub fn drop_annotations(&mut self, parent: &OwnedQualifiedName) {
+        for (_, _, span) in std::mem::take(&mut self.reader.pending_annotations) {
+            self.push_err(EMLError::Positioned {
+                kind: EMLErrorKind::AnnotationNotPreserved(parent.clone()),
+                span,
+            });
+        }
+    }
+
+    /// Returns all attributes of this element that have not been read so far,
+    /// excluding namespace declarations.
+    pub fn unknown_attributes(&self) -> Result<Vec<UnknownAttribute>, EMLError> {
+        let read_attributes = self.read_attributes.borrow();
+        let mut unknown = Vec::new();
+        for attr in self.start.attributes() {
+            let attr = attr.with_span(self.span)?;
+            if attr.key.as_namespace_binding().is_some()
+                || read_attributes.iter().any(|key| key == attr.key.as_ref())
+            {
+                continue;
+            }
+
+            let (attr_span, value_span) = self.attribute_spans(attr.key.as_ref());
+            let name = self
+                .get_resolved_name(attr.key, attr_span, true)?
+                .as_owned();
+            let prefix = attr
+                .key
+                .prefix()
+                .map(|prefix| self.decode(prefix.as_ref(), attr_span))
+                .transpose()?;
+            let value = attr
+                .decode_and_unescape_value(self.reader.inner.decoder())
+                .with_span(value_span)?
+                .into_owned();
+            unknown.push(UnknownAttribute {
+                name,
+                prefix,
+                value,
+            });
+        }
+        Ok(unknown)
+    }
+
+    /// Reads this element as a raw XML fragment, consuming the entire element.
+    ///
+    /// The position is the number of known elements that preceded this
+    /// element in its parent.
+    pub fn read_unknown_element(&mut self, position: usize) -> Result<UnknownElement, EMLError> {
+        let name = self.name()?.as_owned();
+        let start = self.start_tag_content_offset().saturating_sub(1);
+
+        let mut declared = Vec::new();
+        let mut namespaces = Vec::new();
+        self.collect_namespaces(
+            &self.start.clone(),
+            self.span,
+            &mut declared,
+            &mut namespaces,
+        )?;
+        while let Some((evt, span)) = self.next()? {
+            if let Event::Start(tag) | Event::Empty(tag) = evt {
+                self.collect_namespaces(&tag, span, &mut declared, &mut namespaces)?;
+            }
+        }
+
+        let end = if self.is_empty {
+            self.span.end
+        } else {
+            self.last_span.end
+        };
+        let xml = usize::try_from(start)
+            .ok()
+            .zip(usize::try_from(end).ok())
+            .and_then(|(start, end)| self.reader.input.get(start..end))
+            .ok_or(EMLErrorKind::UnexpectedEvent)
+            .with_span(self.full_span())?
+            .to_string();
+
+        Ok(UnknownElement {
+            name,
+            position,
+            namespaces,
+            xml,
+        })
+    }
+
+    /// Collects the namespace bindings used by the given tag that are not
+    /// declared inside of the fragment that is being read.
+    fn collect_namespaces(
+        &self,
+        tag: &BytesStart<'_>,
+        span: Span,
+        declared: &mut Vec<Option<String>>,
+        namespaces: &mut Vec<(Option<String>, String)>,
+    ) -> Result<(), EMLError> {
+        let mut names = vec![(tag.name(), false)];
+        for attr in tag.attributes() {
+            let attr = attr.with_span(span)?;
+            match attr.key.as_namespace_binding() {
+                Some(quick_xml::name::PrefixDeclaration::Default) => declared.push(None),
+                Some(quick_xml::name::PrefixDeclaration::Named(prefix)) => {
+                    declared.push(Some(self.decode(prefix, span)?))
+                }
+                None if attr.key.prefix().is_some() => names.push((attr.key, true)),
+                // Attributes without a prefix are in no namespace
+                None => {}
+            }
+        }
+
+        for (name, is_attribute) in names {
+            let prefix = name
+                .prefix()
+                .map(|prefix| self.decode(prefix.as_ref(), span))
+                .transpose()?;
+            if declared.contains(&prefix) || namespaces.iter().any(|(p, _)| *p == prefix) {
+                continue;
+            }
+            let (resolved, _) = if is_attribute {
+                self.reader.inner.resolver().resolve_attribute(name)
+            } else {
+                self.reader.inner.resolver().resolve_element(name)
+            };
+            let namespace = self.namespace_name(resolved, span)?.unwrap_or_default();
+            namespaces.push((prefix, namespace.into_owned()));
+        }
+        Ok(())
+    }
+
+    /// Decodes the given bytes from the document.
+    fn decode(&self, bytes: &[u8], span: Span) -> Result<String, EMLError> {
+        Ok(self
+            .reader
+            .inner
+            .decoder()
+            .decode(bytes)
+            .with_span(span)?
+            .into_owned())
+    }
+
     /// Maps a parsing error to an EMLError with context about this element.
     fn map_value_error<'a, 'b, T: StringValueData>(
         &self,
@@ -510,11 +882,11 @@ impl<'r, 'input> EMLElementReader<'r, 'input> {
         attr_name: impl Into<QualifiedName<'a, 'b>>,
     ) -> Result<Option<StringValue<T>>, EMLError> {
         let attr_name = attr_name.into();
-        match self.attribute_value(attr_name.clone())? {
-            Some(value) => Ok(Some(self.string_value_from_text(
+        match self.attribute_value_with_span(attr_name.clone())? {
+            Some((value, span)) => Ok(Some(self.string_value_from_text(
                 value.into_owned(),
                 Some(attr_name),
-                self.span(),
+                span,
             )?)),
             None => Ok(None),
         }
@@ -532,15 +904,13 @@ impl<'r, 'input> EMLElementReader<'r, 'input> {
     ) -> Result<StringValue<T>, EMLError> {
         let attr_name = attr_name.into();
         let value = self
-            .attribute_value(attr_name.clone())?
-            .or_else(|| default_value.map(Cow::Borrowed));
+            .attribute_value_with_span(attr_name.clone())?
+            .map(|(value, span)| (value.into_owned(), span))
+            .or_else(|| default_value.map(|value| (value.to_string(), self.span())));
         match value {
-            Some(value) => {
-                self.string_value_from_text(value.into_owned(), Some(attr_name), self.span())
-            }
-            None => {
-                Err(EMLErrorKind::MissingAttribute(attr_name.as_owned())).with_span(self.span())
-            }
+            Some((value, span)) => self.string_value_from_text(value, Some(attr_name), span),
+            None => Err(EMLErrorKind::MissingAttribute(attr_name.as_owned()))
+                .with_span(self.name_span()),
         }
     }
 
@@ -660,6 +1030,54 @@ impl<'r, 'input> EMLElementReader<'r, 'input> {
     }
 }
 
+/// Locates the attribute with the given raw name in the content of a start tag.
+///
+/// Returns the offsets relative to the start tag content of the start of the
+/// attribute name, and the start and end of its value (excluding the quotes).
+/// Returns None if the attribute could not be found or if the start tag is
+/// malformed.
+fn find_attribute(content: &[u8], raw_name: &[u8]) -> Option<(usize, usize, usize)> {
+    let skip_whitespace = |mut pos: usize| {
+        while content.get(pos).is_some_and(u8::is_ascii_whitespace) {
+            pos += 1;
+        }
+        pos
+    };
+
+    // skip the element name
+    let mut pos = content.iter().position(u8::is_ascii_whitespace)?;
+    loop {
+        pos = skip_whitespace(pos);
+        if pos >= content.len() {
+            return None;
+        }
+
+        let name_start = pos;
+        while content
+            .get(pos)
+            .is_some_and(|b| *b != b'=' && !b.is_ascii_whitespace())
+        {
+            pos += 1;
+        }
+        let name_end = pos;
+
+        pos = skip_whitespace(pos);
+        if content.get(pos) != Some(&b'=') {
+            return None;
+        }
+        pos = skip_whitespace(pos + 1);
+
+        let quote = *content.get(pos).filter(|b| **b == b'"' || **b == b'\'')?;
+        let value_start = pos + 1;
+        let value_end = value_start + content[value_start..].iter().position(|b| *b == quote)?;
+        pos = value_end + 1;
+
+        if &content[name_start..name_end] == raw_name {
+            return Some((name_start, value_start, value_end));
+        }
+    }
+}
+
 impl Drop for EMLElementReader<'_, '_> {
     fn drop(&mut self) {
         // Ensure we have consumed the entire element
@@ -673,14 +1091,22 @@ macro_rules! collect_struct {
     // Once all tokens have been processed, we continue with the @emit rule.
     // In this phase, we output the base structure of the code. In this phase,
     // we again delegate to other rules to output specific parts of the code.
-    // These parts are: @decl: declares temporary variables that
-    // will hold the parsed values; @matcher: code to check for each field while
-    // reading children from the XML element; and @assign: code to assign the
+    // These parts are: @decl: declares temporary variables that will hold the
+    // parsed values, and evaluates direct rows before any child is read, so
+    // that these can still read the text of the element; @matcher: code to
+    // check for each field while reading children from the XML element; and
+    // @assign: code to assign the
     // final values to the struct fields. This final part once again uses a
     // recursive approach to output the assignments one by one because of
     // limitations in macro_rules! that prevent us from directly outputting the
     // list expansions as one (Rust stops expanding once it sees a macro in a
     // field position and then fails with a syntax error).
+    //
+    // The `extensions as Extensions` row collects unknown elements and
+    // attributes when preserving unknown content, and comments when preserving
+    // comments. It must be the last row, so that all attributes have been read
+    // before the unknown ones are collected. Without this row, comments are
+    // dropped with a recorded error instead of attaching to the next element.
 
     // entry point of the macro, forward to expand rules
     ( $root:expr, $ty:ident { $($rest:tt)* }) => {
@@ -720,6 +1146,17 @@ macro_rules! collect_struct {
         ] $($tail)*)
     };
 
+    // accumulate, for the extensions row
+    ( @expand [$root:expr] [$ty:ident] [$($items:tt ; )*]
+        $field:ident as Extensions ,
+        $($tail:tt)*
+    ) => {
+        collect_struct!(@expand [$root] [$ty] [
+            $($items ; )*
+            (@extensions [$field]) ;
+        ] $($tail)*)
+    };
+
     // accumulate for a direct row
     ( @expand [$root:expr] [$ty:ident] [$($items:tt ; )*]
         $field:ident: $value:expr ,
@@ -741,28 +1178,48 @@ macro_rules! collect_struct {
         $( collect_struct!(@decl $items); )*
 
         let elem_name = $root.name()?.as_owned();
+        #[allow(unused_variables)]
+        let mut known_children = 0usize;
+        #[allow(unused_assignments)]
         while let Some(mut next_child) = $root.next_child()? {
+            $( collect_struct!(@annotations next_child, known_children, $items); )*
+            next_child.drop_annotations(&elem_name);
             let name = next_child.name()?.as_owned().into_inner();
             #[allow(unused_mut)]
             let mut handled = false;
 
             $( collect_struct!(@matcher next_child, name, handled, $items); )*
 
-            if !handled {
+            if handled {
+                known_children += 1;
+            } else {
                 next_child.push_err($crate::error::EMLError::Positioned {
                     kind: $crate::error::EMLErrorKind::UnexpectedElement(name.as_owned(), elem_name.clone()),
                     span: next_child.span(),
                 });
                 // Unknown element at this level
-                next_child.skip()?;
+                #[allow(unused_mut)]
+                let mut preserved = false;
+                $( collect_struct!(@unknown next_child, known_children, preserved, $items); )*
+                if !preserved {
+                    next_child.skip()?;
+                }
             }
         }
+        $( collect_struct!(@annotations $root, known_children, $items); )*
+        $root.drop_annotations(&elem_name);
 
         collect_struct!(@assign $root, $ty, [], $($items ; )*)
     }};
 
     // Emit field declarations
-    (@decl (@direct [$field:ident] [$value:expr])) => {};
+    (@decl (@direct [$field:ident] [$value:expr])) => {
+        #[allow(clippy::redundant_locals)]
+        let $field = $value;
+    };
+    (@decl (@extensions [$field:ident])) => {
+        let mut $field = $crate::io::Extensions::default();
+    };
     (@decl (@optional [$field:ident] [$namespaced_name:expr] [$var:ident] [$map:expr])) => {
         collect_struct!(@decl (@field [$field] [$namespaced_name] [$var] [$map]));
     };
@@ -775,6 +1232,7 @@ macro_rules! collect_struct {
 
     // Emit match arms for each field
     (@matcher $next_child:ident, $name:ident, $handled:ident, (@direct [$field:ident] [$value:expr])) => {};
+    (@matcher $next_child:ident, $name:ident, $handled:ident, (@extensions [$field:ident])) => {};
     (@matcher $next_child:ident, $name:ident, $handled:ident, (@optional [$field:ident] [$namespaced_name:expr] [$var:ident] [$map:expr])) => {
         collect_struct!(@matcher $next_child, $name, $handled, (@field [$field] [$namespaced_name] [$var] [$map]));
     };
@@ -799,6 +1257,26 @@ macro_rules! collect_struct {
         }
     };
 
+    // Emit code to preserve an unknown element, only for the extensions row
+    (@unknown $next_child:ident, $position:ident, $preserved:ident, (@extensions [$field:ident])) => {
+        if $next_child.preserve_unknown() {
+            $field.elements.push($next_child.read_unknown_element($position)?);
+            $preserved = true;
+        }
+    };
+    (@unknown $next_child:ident, $position:ident, $preserved:ident, $item:tt) => {};
+
+    // Emit code to attach preceding comments, only for the extensions row
+    (@annotations $reader:expr, $position:ident, (@extensions [$field:ident])) => {
+        if $reader.preserve_comments() {
+            let preceding_elements = $field.elements.len();
+            $field
+                .annotations
+                .extend($reader.take_annotations($position, preceding_elements));
+        }
+    };
+    (@annotations $reader:expr, $position:ident, $item:tt) => {};
+
     (@build_struct $root:expr, $ty:ident, $($items:tt ; )* ) => {
         $ty {
             collect_struct!(@assign $root, $($items ; )*)
@@ -809,7 +1287,18 @@ macro_rules! collect_struct {
     (@assign $root:expr, $ty:ident, [$($out:tt)*], (@direct [$field:ident] [$value:expr]) ; $($tail:tt)*) => {
         collect_struct!(@assign $root, $ty, [
             $($out)*
-            $field: $value,
+            $field: $field,
+        ], $($tail)*)
+    };
+    (@assign $root:expr, $ty:ident, [$($out:tt)*], (@extensions [$field:ident]) ; $($tail:tt)*) => {
+        collect_struct!(@assign $root, $ty, [
+            $($out)*
+            $field: {
+                if $root.preserve_unknown() {
+                    $field.attributes = $root.unknown_attributes()?;
+                }
+                $field
+            },
         ], $($tail)*)
     };
     (@assign $root:expr, $ty:ident, [$($out:tt)*], (@optional [$field:ident] [$namespaced_name:expr] [$var:ident] [$map:expr]) ; $($tail:tt)*) => {
@@ -845,6 +1334,8 @@ pub(crate) use collect_struct;
 
 #[cfg(test)]
 mod tests {
+    use crate::utils::YesNoType;
+
     use super::*;
 
     #[test]
@@ -858,4 +1349,44 @@ mod tests {
             EMLErrorKind::UnknownNamespace(ns) if ns == "eml"
         ));
     }
+
+    #[test]
+    fn test_invalid_attribute_value_span() {
+        let document = "<Root\n  Other = 'x'   Value=\"maybe\" />";
+        let mut reader = EMLReader::init_from_str(document, EMLParsingMode::Strict);
+        let mut root = reader.next_element().unwrap();
+        let error = root
+            .string_value_attr::<YesNoType>("Value", None)
+            .unwrap_err();
+        assert!(matches!(error.kind(), EMLErrorKind::InvalidValue(..)));
+        let span = error.span().unwrap();
+        assert_eq!(&document[span.start as usize..span.end as usize], "maybe");
+
+        let (value, span) = root.attribute_value_req_with_span("Other").unwrap();
+        assert_eq!(value, "x");
+        assert_eq!(&document[span.start as usize..span.end as usize], "x");
+    }
+
+    #[test]
+    fn test_missing_attribute_span() {
+        let document = "<Parent><Child Other=\"1\">text</Child></Parent>";
+        let mut reader = EMLReader::init_from_str(document, EMLParsingMode::Strict);
+        let mut root = reader.next_element().unwrap();
+        let child = root.next_child().unwrap().unwrap();
+        let error = child.attribute_value_req("Value").unwrap_err();
+        assert!(matches!(error.kind(), EMLErrorKind::MissingAttribute(..)));
+        let span = error.span().unwrap();
+        assert_eq!(&document[span.start as usize..span.end as usize], "Child");
+    }
+
+    #[test]
+    fn test_find_attribute() {
+        let content = br#"a:Elem x="1" a:y = 'two"' z="""#;
+        assert_eq!(find_attribute(content, b"x"), Some((7, 10, 11)));
+        assert_eq!(find_attribute(content, b"a:y"), Some((13, 20, 24)));
+        assert_eq!(find_attribute(content, b"z"), Some((26, 29, 29)));
+        assert_eq!(find_attribute(content, b"y"), None);
+        assert_eq!(find_attribute(b"Elem", b"x"), None);
+        assert_eq!(find_attribute(b"Elem x=1", b"x"), None);
+    }
 }
@@ -0,0 +1,627 @@
+use std::{
+    collections::BTreeMap,
+    io::{self, BufRead, Read},
+    marker::PhantomData,
+};
+
+use quick_xml::{
+    Decoder, Reader,
+    escape::escape,
+    events::{BytesStart, Event},
+};
+
+use crate::{
+    EMLError, EMLErrorKind, EMLResultExt,
+    documents::{
+        candidate_lists::{CandidateLists, CandidateListsCandidate},
+        count::{Count, CountReportingUnitVotes},
+        polling_stations::{PollingPlace, PollingStations},
+    },
+    io::{EMLElement, EMLRead, EMLReadOptions, EMLReadResult, QualifiedName, Span},
+};
+
+/// A repeated element of a document that can be read one at a time with an
+/// [`EMLStreamReader`].
+///
+/// This trait is implemented for:
+///
+/// - [`CountReportingUnitVotes`] in a [`Count`] (`510a` up to `510d`)
+/// - [`PollingPlace`] in [`PollingStations`] (`110b`)
+/// - [`CandidateListsCandidate`] in [`CandidateLists`] (`230b`)
+pub trait EMLStreamItem: EMLRead + private::Sealed {
+    /// The document the element is read from.
+    type Document: EMLRead;
+
+    /// Name of the element.
+    const ITEM_NAME: QualifiedName<'static, 'static>;
+}
+
+mod private {
+    pub trait Sealed {}
+}
+
+macro_rules! impl_stream_item {
+    ($item:ty, $document:ty) => {
+        impl private::Sealed for $item {}
+
+        impl EMLStreamItem for $item {
+            type Document = $document;
+
+            const ITEM_NAME: QualifiedName<'static, 'static> = <$item as EMLElement>::EML_NAME;
+        }
+    };
+}
+
+impl_stream_item!(CountReportingUnitVotes, Count);
+impl_stream_item!(PollingPlace, PollingStations);
+impl_stream_item!(CandidateListsCandidate, CandidateLists);
+
+/// Reads a document one element at a time from an [`io::BufRead`], so that
+/// memory use does not depend on the number of elements in the document.
+///
+/// Iterating over the reader yields each element of type `T` in document
+/// order, e.g. the [`CountReportingUnitVotes`] of a count. Elements that fail
+/// to parse are returned as an error, after which reading continues with the
+/// next element. Errors in the XML itself end the iteration. Spans of errors
+/// are positions in the entire document.
+///
+/// Everything except the elements themselves is available through
+/// [`header`](EMLStreamReader::header), as a document that contains everything
+/// read so far, without any of the elements. Before the first element is
+/// read, this contains the document header such as the managing authority and
+/// the election identifier. While iterating, the last contest (or affiliation
+/// for candidates) in the header is the one containing the current element.
+/// After all elements are read, it contains the rest of the document as well.
+///
+/// ```
+/// use eml_nl::{
+///     documents::count::CountReportingUnitVotes,
+///     io::{EMLParsingMode, EMLStreamReader},
+/// };
+///
+/// let doc = include_str!("../../test-emls/deserialize_eml510b_test.eml.xml");
+/// let mut reader =
+///     EMLStreamReader::<_, CountReportingUnitVotes>::new(doc.as_bytes(), EMLParsingMode::Strict);
+/// let header = reader.header().unwrap();
+/// assert_eq!(header.count.election.identifier.name.as_deref(), Some("Municipal Election"));
+///
+/// let mut reporting_units = 0;
+/// for votes in reader {
+///     let votes = votes.ok().unwrap();
+///     reporting_units += 1;
+/// }
+/// assert!(reporting_units > 0);
+/// ```
+pub struct EMLStreamReader<R, T: EMLStreamItem> {
+    reader: Reader<RecordingReader<R>>,
+    options: EMLReadOptions,
+    buffer: Vec<u8>,
+    /// Offset in the document of the first byte that was recorded.
+    recorded_offset: u64,
+    /// The document read so far, without the elements.
+    skeleton: Vec<u8>,
+    /// Pairs of offsets in the skeleton and the number of bytes that were
+    /// removed from the document before that offset.
+    removed: Vec<(usize, u64)>,
+    /// Elements in the skeleton that are not closed yet.
+    open: Vec<OpenElement>,
+    /// Whether the last event was the end of an element.
+    after_item: bool,
+    /// Element that was read, but not yet parsed.
+    pending: Option<RawItem>,
+    /// Whether any element was returned by the iterator.
+    started: bool,
+    finished: bool,
+    /// Parsed header, together with the length of the skeleton it was parsed from.
+    header: Option<(usize, T::Document)>,
+    header_errors: Vec<EMLError>,
+    item: PhantomData<T>,
+}
+
+/// An element in the skeleton that is not closed yet.
+struct OpenElement {
+    name: String,
+    /// Namespaces declared on the element, as prefix and URI.
+    namespaces: Vec<(Option<String>, String)>,
+}
+
+/// The XML of an element that is read, with the namespace declarations in
+/// scope of the element added to its start tag.
+struct RawItem {
+    xml: String,
+    /// Offset of the element in the document.
+    offset: u64,
+    /// Offset in `xml` where the namespace declarations were added.
+    declarations_offset: u64,
+    /// Length of the namespace declarations that were added.
+    declarations_len: u64,
+    /// Number of start tags that have not been closed yet.
+    depth: usize,
+}
+
+impl RawItem {
+    /// Converts a span in the XML of the element to a span in the document.
+    fn document_span(&self, span: Span) -> Span {
+        let to_document = |offset: u64| {
+            let offset = if offset <= self.declarations_offset {
+                offset
+            } else {
+                self.declarations_offset
+                    .max(offset.saturating_sub(self.declarations_len))
+            };
+            self.offset + offset
+        };
+        Span::new(to_document(span.start), to_document(span.end))
+    }
+}
+
+impl<R: BufRead, T: EMLStreamItem> EMLStreamReader<R, T> {
+    /// Create a stream reader that reads the document from the given reader.
+    ///
+    /// The document must be encoded in UTF-8. Unlike
+    /// [`EMLRead::parse_eml_bytes`], the encoding is not detected from a byte
+    /// order mark or the XML declaration, so documents in other encodings
+    /// result in errors or are read incorrectly. Such documents can be
+    /// decoded with [`decode_eml_bytes`](crate::io::decode_eml_bytes) first,
+    /// streaming the decoded text from a byte slice.
+    pub fn new(reader: R, options: impl Into<EMLReadOptions>) -> Self {
+        EMLStreamReader {
+            reader: Reader::from_reader(RecordingReader {
+                inner: reader,
+                recorded: Vec::new(),
+            }),
+            options: options.into(),
+            buffer: Vec::new(),
+            recorded_offset: 0,
+            skeleton: Vec::new(),
+            removed: Vec::new(),
+            open: Vec::new(),
+            after_item: false,
+            pending: None,
+            started: false,
+            finished: false,
+            header: None,
+            header_errors: Vec::new(),
+            item: PhantomData,
+        }
+    }
+
+    /// Returns the document read so far, without the elements that are
+    /// streamed.
+    ///
+    /// If no element has been returned yet, this reads up to the first
+    /// element. The header is only parsed again when more of the document was
+    /// read since the last call.
+    pub fn header(&mut self) -> Result<&T::Document, EMLError> {
+        if !self.started && self.pending.is_none() && !self.finished {
+            self.fill()?;
+        }
+
+        if self
+            .header
+            .as_ref()
+            .is_none_or(|(len, _)| *len != self.skeleton.len())
+        {
+            // Close all open elements to get a complete document
+            let mut xml = String::from_utf8(self.skeleton.clone()).without_span()?;
+            for element in self.open.iter().rev() {
+                xml.push_str("</");
+                xml.push_str(&element.name);
+                xml.push('>');
+            }
+            let map = |span| self.skeleton_span(span);
+            let (document, errors) = T::Document::parse_eml(&xml, self.options)
+                .ok_with_errors()
+                .map_err(|e| e.map_spans(&map))?;
+            self.header_errors = errors.into_iter().map(|e| e.map_spans(&map)).collect();
+            self.header = Some((self.skeleton.len(), document));
+        }
+
+        Ok(&self.header.as_ref().expect("Header was parsed above").1)
+    }
+
+    /// Returns the non-fatal errors of parsing the last returned
+    /// [`header`](EMLStreamReader::header).
+    pub fn header_errors(&self) -> &[EMLError] {
+        &self.header_errors
+    }
+
+    /// Converts a span in the skeleton to a span in the document.
+    fn skeleton_span(&self, span: Span) -> Span {
+        let to_document = |offset: u64| {
+            let removed = self
+                .removed
+                .iter()
+                .take_while(|(at, _)| *at as u64 <= offset)
+                .last()
+                .map_or(0, |(_, removed)| *removed);
+            offset + removed
+        };
+        Span::new(to_document(span.start), to_document(span.end))
+    }
+
+    /// Reads until the next element was read completely, or until the end of
+    /// the document.
+    fn fill(&mut self) -> Result<(), EMLError> {
+        let result = self.fill_until_item();
+        if result.is_err() {
+            self.finished = true;
+        }
+        result
+    }
+
+    fn fill_until_item(&mut self) -> Result<(), EMLError> {
+        let mut item: Option<RawItem> = None;
+        while self.pending.is_none() && !self.finished {
+            self.buffer.clear();
+            let start = self.reader.buffer_position();
+            let event = match self.reader.read_event_into(&mut self.buffer) {
+                // The event is owned so that the reader state can be used below
+                Ok(event) => event.into_owned(),
+                Err(e) => {
+                    let position = self.reader.error_position();
+                    return Err(e).with_span(Span::new(position, position));
+                }
+            };
+            let end = self.reader.buffer_position();
+            let span = Span::new(start, end);
+
+            // The original XML of the event
+            let recorded = &mut self.reader.get_mut().recorded;
+            let raw = recorded[(start - self.recorded_offset) as usize..][..(end - start) as usize]
+                .to_vec();
+            recorded.drain(..(end - self.recorded_offset) as usize);
+            self.recorded_offset = end;
+
+            if let Some(current) = &mut item {
+                current.xml.push_str(&decode(raw, span)?);
+                match event {
+                    Event::Start(_) => current.depth += 1,
+                    Event::End(_) => current.depth -= 1,
+                    Event::Eof => return Err(EMLErrorKind::UnexpectedEof).with_span(span),
+                    _ => {}
+                }
+                if current.depth == 0 {
+                    self.finish_item(item.take(), end);
+                }
+                continue;
+            }
+
+            match event {
+                Event::Start(tag) | Event::Empty(tag) => {
+                    let is_empty = raw.ends_with(b"/>");
+                    let namespaces = declared_namespaces(&tag, self.reader.decoder(), span)?;
+                    if self.is_item(&tag, &namespaces) {
+                        item =
+                            Some(self.start_item(raw, &tag, &namespaces, start, is_empty, span)?);
+                        if is_empty {
+                            self.finish_item(item.take(), end);
+                        }
+                        continue;
+                    }
+                    if !is_empty {
+                        self.open.push(OpenElement {
+                            name: decode(tag.name().as_ref().to_vec(), span)?,
+                            namespaces,
+                        });
+                    }
+                }
+                Event::End(_) => {
+                    self.open.pop();
+                }
+                Event::Text(text)
+                    if self.after_item && text.iter().all(u8::is_ascii_whitespace) =>
+                {
+                    // Whitespace in between elements is removed as well
+                    self.add_removed(raw.len() as u64);
+                    continue;
+                }
+                Event::Eof => {
+                    if !self.open.is_empty() {
+                        return Err(EMLErrorKind::UnexpectedEof).with_span(span);
+                    }
+                    self.finished = true;
+                }
+                _ => {}
+            }
+            self.skeleton.extend_from_slice(&raw);
+            self.after_item = false;
+        }
+        Ok(())
+    }
+
+    /// Returns whether the given start tag is the start of an element.
+    fn is_item(&self, tag: &BytesStart<'_>, namespaces: &[(Option<String>, String)]) -> bool {
+        let name = tag.name();
+        let (prefix, local_name) = match name.as_ref().iter().position(|b| *b == b':') {
+            Some(i) => (Some(&name.as_ref()[..i]), &name.as_ref()[i + 1..]),
+            None => (None, name.as_ref()),
+        };
+        let prefix = prefix.map(|p| String::from_utf8_lossy(p).into_owned());
+        let namespace = namespaces
+            .iter()
+            .rev()
+            .chain(
+                self.open
+                    .iter()
+                    .rev()
+                    .flat_map(|e| e.namespaces.iter().rev()),
+            )
+            .find(|(declared, _)| *declared == prefix)
+            .map(|(_, uri)| uri.as_str())
+            .filter(|uri| !uri.is_empty());
+        local_name == T::ITEM_NAME.local_name.as_bytes()
+            && namespace == T::ITEM_NAME.namespace.as_deref()
+    }
+
+    /// Starts reading an element, adding the namespace declarations in scope
+    /// of the element to its start tag so it can be parsed on its own.
+    fn start_item(
+        &self,
+        raw: Vec<u8>,
+        tag: &BytesStart<'_>,
+        namespaces: &[(Option<String>, String)],
+        offset: u64,
+        is_empty: bool,
+        span: Span,
+    ) -> Result<RawItem, EMLError> {
+        let mut in_scope = BTreeMap::new();
+        for (prefix, uri) in self.open.iter().flat_map(|e| &e.namespaces) {
+            in_scope.insert(prefix, uri);
+        }
+        for (prefix, _) in namespaces {
+            in_scope.remove(prefix);
+        }
+        let mut declarations = String::new();
+        for (prefix, uri) in in_scope {
+            match prefix {
+                Some(prefix) => {
+                    declarations.push_str(" xmlns:");
+                    declarations.push_str(prefix);
+                }
+                None => declarations.push_str(" xmlns"),
+            }
+            declarations.push_str("=\"");
+            declarations.push_str(&escape(uri.as_str()));
+            declarations.push('"');
+        }
+
+        // Declarations are added directly after the name of the element
+        let mut xml = decode(raw, span)?;
+        let declarations_offset = 1 + tag.name().as_ref().len();
+        xml.insert_str(declarations_offset, &declarations);
+        Ok(RawItem {
+            xml,
+            offset,
+            declarations_offset: declarations_offset as u64,
+            declarations_len: declarations.len() as u64,
+            depth: usize::from(!is_empty),
+        })
+    }
+
+    /// Marks the element that was read as pending, removing it from the
+    /// skeleton.
+    fn finish_item(&mut self, item: Option<RawItem>, end: u64) {
+        if let Some(item) = item {
+            self.add_removed(end - item.offset);
+            self.after_item = true;
+            self.pending = Some(item);
+        }
+    }
+
+    /// Records that bytes of the document were removed at the current end of
+    /// the skeleton.
+    fn add_removed(&mut self, len: u64) {
+        let total = self.removed.last().map_or(0, |(_, removed)| *removed) + len;
+        match self.removed.last_mut() {
+            Some((at, removed)) if *at == self.skeleton.len() => *removed = total,
+            _ => self.removed.push((self.skeleton.len(), total)),
+        }
+    }
+}
+
+impl<R: BufRead, T: EMLStreamItem> Iterator for EMLStreamReader<R, T> {
+    type Item = EMLReadResult<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.pending.is_none()
+            && !self.finished
+            && let Err(e) = self.fill()
+        {
+            return Some(EMLReadResult::Err(e));
+        }
+
+        let item = self.pending.take()?;
+        self.started = true;
+        let map = |span| item.document_span(span);
+        Some(T::parse_eml(&item.xml, self.options).map_spans(&map))
+    }
+}
+
+/// Returns the namespaces declared on an element, as prefix and URI.
+fn declared_namespaces(
+    tag: &BytesStart<'_>,
+    decoder: Decoder,
+    span: Span,
+) -> Result<Vec<(Option<String>, String)>, EMLError> {
+    let mut namespaces = Vec::new();
+    for attr in tag.attributes() {
+        let attr = attr.with_span(span)?;
+        let prefix = match attr.key.as_ref() {
+            b"xmlns" => None,
+            key => match key.strip_prefix(b"xmlns:") {
+                Some(prefix) => Some(decode(prefix.to_vec(), span)?),
+                None => continue,
+            },
+        };
+        let uri = attr
+            .decode_and_unescape_value(decoder)
+            .with_span(span)?
+            .into_owned();
+        namespaces.push((prefix, uri));
+    }
+    Ok(namespaces)
+}
+
+fn decode(bytes: Vec<u8>, span: Span) -> Result<String, EMLError> {
+    String::from_utf8(bytes).with_span(span)
+}
+
+/// Wraps a reader to record the bytes consumed by the XML reader, so that the
+/// original XML of each event can be copied.
+struct RecordingReader<R> {
+    inner: R,
+    recorded: Vec<u8>,
+}
+
+impl<R: BufRead> Read for RecordingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let len = {
+            let available = self.fill_buf()?;
+            let len = available.len().min(buf.len());
+            buf[..len].copy_from_slice(&available[..len]);
+            len
+        };
+        self.consume(len);
+        Ok(len)
+    }
+}
+
+impl<R: BufRead> BufRead for RecordingReader<R> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        self.inner.fill_buf()
+    }
+
+    fn consume(&mut self, amt: usize) {
+        // The buffer is already filled at this point, so this does not read
+        if let Ok(buf) = self.inner.fill_buf() {
+            self.recorded.extend_from_slice(&buf[..amt.min(buf.len())]);
+        }
+        self.inner.consume(amt);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::BufReader;
+
+    use crate::io::EMLParsingMode;
+
+    use super::*;
+
+    #[test]
+    fn test_stream_reporting_unit_votes() {
+        let doc = include_str!("../../test-emls/deserialize_eml510b_test.eml.xml");
+        let full = Count::parse_eml(doc, EMLParsingMode::Strict)
+            .ok()
+            .expect("Failed to parse EML document");
+
+        // A small buffer to read the document in many parts
+        let input = BufReader::with_capacity(16, doc.as_bytes());
+        let mut reader =
+            EMLStreamReader::<_, CountReportingUnitVotes>::new(input, EMLParsingMode::Strict);
+        let header = reader.header().unwrap();
+        assert_eq!(header.managing_authority, full.managing_authority);
+        assert_eq!(
+            header.count.election.identifier,
+            full.count.election.identifier
+        );
+        assert!(
+            header.count.election.contests[0]
+                .reporting_unit_votes
+                .is_empty()
+        );
+        assert!(reader.header_errors().is_empty());
+
+        let votes: Vec<_> = reader
+            .by_ref()
+            .map(|votes| votes.ok().expect("Failed to parse reporting unit votes"))
+            .collect();
+        let expected: Vec<_> = full
+            .count
+            .election
+            .contests
+            .iter()
+            .flat_map(|contest| contest.reporting_unit_votes.clone())
+            .collect();
+        assert!(!votes.is_empty());
+        assert_eq!(votes, expected);
+
+        // After reading everything, the header contains the rest of the document
+        let mut without_votes = full.clone();
+        for contest in &mut without_votes.count.election.contests {
+            contest.reporting_unit_votes.clear();
+        }
+        assert_eq!(reader.header().unwrap(), &without_votes);
+    }
+
+    #[test]
+    fn test_stream_polling_places_and_candidates() {
+        let doc = include_str!("../../test-emls/polling_stations/eml110b_test.eml.xml");
+        let full = PollingStations::parse_eml(doc, EMLParsingMode::Strict)
+            .ok()
+            .expect("Failed to parse EML document");
+        let reader =
+            EMLStreamReader::<_, PollingPlace>::new(doc.as_bytes(), EMLParsingMode::Strict);
+        let places: Vec<_> = reader.map(|place| place.ok().unwrap()).collect();
+        assert_eq!(places, full.election_event.election.contest.polling_places);
+
+        let doc = include_str!("../../test-emls/candidate_list/eml230b_test.eml.xml");
+        let full = CandidateLists::parse_eml(doc, EMLParsingMode::Strict)
+            .ok()
+            .expect("Failed to parse EML document");
+        let mut reader = EMLStreamReader::<_, CandidateListsCandidate>::new(
+            doc.as_bytes(),
+            EMLParsingMode::Strict,
+        );
+        for affiliation in &full.candidate_list.election.contest.affiliations {
+            for candidate in &affiliation.candidates {
+                assert_eq!(&reader.next().unwrap().ok().unwrap(), candidate);
+                // The affiliation of the candidate is the last one in the header
+                let header = reader.header().unwrap();
+                let affiliations = &header.candidate_list.election.contest.affiliations;
+                assert_eq!(
+                    affiliations.last().unwrap().identifier,
+                    affiliation.identifier
+                );
+            }
+        }
+        assert!(reader.next().is_none());
+    }
+
+    #[test]
+    fn test_stream_error_positions() {
+        let doc = include_str!("../../test-emls/deserialize_eml510b_test.eml.xml")
+            .replacen("<Cast>", "<Cast>x", 1)
+            .replacen("<ValidVotes>", "<ValidVotes>x", 2);
+        let (_, expected) = Count::parse_eml(&doc, EMLParsingMode::StrictFallback)
+            .ok_with_errors()
+            .unwrap();
+        assert_eq!(expected.len(), 3);
+
+        let mut reader = EMLStreamReader::<_, CountReportingUnitVotes>::new(
+            doc.as_bytes(),
+            EMLParsingMode::StrictFallback,
+        );
+        let mut spans = Vec::new();
+        for votes in reader.by_ref() {
+            let (_, errors) = votes.ok_with_errors().unwrap();
+            spans.extend(errors.iter().map(EMLError::span));
+        }
+        reader.header().unwrap();
+        spans.extend(reader.header_errors().iter().map(EMLError::span));
+        spans.sort_by_key(|span| span.map(|s| s.start));
+        assert_eq!(
+            spans,
+            expected.iter().map(EMLError::span).collect::<Vec<_>>()
+        );
+
+        let truncated = &doc[..doc.len() / 2];
+        let reader = EMLStreamReader::<_, CountReportingUnitVotes>::new(
+            truncated.as_bytes(),
+            EMLParsingMode::Strict,
+        );
+        let results: Vec<_> = reader.collect();
+        assert!(matches!(results.last(), Some(EMLReadResult::Err(_))));
+    }
+}
@@ -1,12 +1,17 @@
 use std::{borrow::Cow, collections::HashMap};
 
 use quick_xml::{
-    Writer,
-    events::{BytesDecl, BytesStart, BytesText, Event, attributes::Attribute},
+    Reader, Writer,
+    events::{BytesDecl, BytesPI, BytesStart, BytesText, Event, attributes::Attribute},
 };
 
 use crate::{
-    EMLError, EMLErrorKind, EMLResultExt, NS_EML, NS_KR, NS_XAL, NS_XNL, io::QualifiedName,
+    EMLError, EMLErrorKind, EMLResultExt, NS_DS, NS_EML, NS_KR, NS_XAL, NS_XML, NS_XNL,
+    common::CanonicalizationMethod,
+    io::{
+        Annotation, AnnotationKind, CanonicalizationAlgorithm, EMLElement as _, Extensions,
+        QualifiedName, UnknownAttribute, UnknownElement, canonicalize,
+    },
 };
 
 #[derive(Debug, Clone)]
@@ -18,6 +23,8 @@ pub(crate) struct NsDefinitions {
 pub(crate) struct EMLWriter {
     ns_definitions: NsDefinitions,
     writer: Writer<Vec<u8>>,
+    /// The algorithm the output will be canonicalized with, if any.
+    canonicalization: Option<CanonicalizationAlgorithm>,
 }
 
 impl EMLWriter {
@@ -99,11 +106,139 @@ impl EMLWriter {
     fn has_default_namespace(&self) -> bool {
         self.ns_definitions.default_namespace_uri.is_some()
     }
+
+    /// Writes a preserved comment or processing instruction.
+    fn write_annotation(&mut self, annotation: &Annotation) -> Result<(), EMLError> {
+        let event = match annotation.kind {
+            AnnotationKind::Comment => {
+                Event::Comment(BytesText::from_escaped(annotation.content.as_str()))
+            }
+            AnnotationKind::ProcessingInstruction => {
+                Event::PI(BytesPI::new(annotation.content.as_str()))
+            }
+        };
+        self.writer.write_event(event).without_span()
+    }
+
+    /// Writes a preserved unknown element by copying the events of its
+    /// original XML.
+    ///
+    /// Namespaces used in the fragment that are not declared with the same URI
+    /// by this writer are declared on the root of the fragment. Whitespace
+    /// between elements is dropped, so that the fragment is formatted like the
+    /// rest of the document.
+    fn write_unknown_element(&mut self, element: &UnknownElement) -> Result<(), EMLError> {
+        let mut reader = Reader::from_str(&element.xml);
+        let mut events = Vec::new();
+        loop {
+            match reader.read_event().without_span()? {
+                Event::Eof => break,
+                evt => events.push(evt),
+            }
+        }
+
+        let mut is_root = true;
+        for (i, evt) in events.iter().enumerate() {
+            match evt {
+                Event::Text(text) if text.iter().all(u8::is_ascii_whitespace) => {
+                    let is_content = i > 0
+                        && matches!(events[i - 1], Event::Start(_))
+                        && matches!(events.get(i + 1), Some(Event::End(_)));
+                    if is_content {
+                        self.writer.write_event(evt.borrow()).without_span()?;
+                    }
+                }
+                Event::Start(tag) | Event::Empty(tag) if is_root => {
+                    is_root = false;
+                    // Namespace declarations are written before the original attributes
+                    let mut root = tag.to_owned();
+                    root.clear_attributes();
+                    for (prefix, uri) in &element.namespaces {
+                        if !self.is_declared(prefix.as_deref(), uri) {
+                            match prefix {
+                                Some(prefix) => root.push_attribute((
+                                    format!("xmlns:{prefix}").as_str(),
+                                    uri.as_str(),
+                                )),
+                                None => root.push_attribute(("xmlns", uri.as_str())),
+                            }
+                        }
+                    }
+                    for attr in tag.attributes() {
+                        root.push_attribute(attr.without_span()?);
+                    }
+                    let evt = match evt {
+                        Event::Start(_) => Event::Start(root),
+                        _ => Event::Empty(root),
+                    };
+                    self.writer.write_event(evt).without_span()?;
+                }
+                evt => self.writer.write_event(evt.borrow()).without_span()?,
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks if the given prefix (or the default namespace if None) is bound
+    /// to the given namespace URI by this writer. An empty URI indicates no
+    /// namespace.
+    fn is_declared(&self, prefix: Option<&str>, uri: &str) -> bool {
+        match prefix {
+            Some("xml") => true,
+            Some(prefix) => self.ns_definitions.namespace_definitions.get(prefix) == Some(&uri),
+            None if uri.is_empty() => !self.has_default_namespace(),
+            None => self.is_default_namespace(Some(uri)),
+        }
+    }
+}
+
+/// Preserved unknown elements and annotations of an element that have not
+/// been written yet.
+#[derive(Default)]
+struct PendingExtensions<'a> {
+    elements: &'a [UnknownElement],
+    annotations: &'a [Annotation],
+    /// Number of unknown elements written so far.
+    elements_written: usize,
+}
+
+impl PendingExtensions<'_> {
+    fn is_empty(&self) -> bool {
+        self.elements.is_empty() && self.annotations.is_empty()
+    }
+
+    /// Writes everything in document order that was positioned at or before
+    /// the given number of known child elements, or everything if None.
+    fn write(&mut self, writer: &mut EMLWriter, up_to: Option<usize>) -> Result<(), EMLError> {
+        let is_due = |position: usize| up_to.is_none_or(|up_to| position <= up_to);
+        loop {
+            let element = self.elements.first().filter(|e| is_due(e.position));
+            let annotation = self.annotations.first().filter(|a| is_due(a.position));
+            match (annotation, element) {
+                (Some(annotation), element)
+                    if element.is_none_or(|element| {
+                        (annotation.position, annotation.preceding_elements)
+                            <= (element.position, self.elements_written)
+                    }) =>
+                {
+                    writer.write_annotation(annotation)?;
+                    self.annotations = &self.annotations[1..];
+                }
+                (_, Some(element)) => {
+                    writer.write_unknown_element(element)?;
+                    self.elements = &self.elements[1..];
+                    self.elements_written += 1;
+                }
+                _ => return Ok(()),
+            }
+        }
+    }
 }
 
 pub(crate) struct EMLElementWriter<'a> {
     start_tag: BytesStart<'a>,
     writer: &'a mut EMLWriter,
+    pending: PendingExtensions<'a>,
 }
 
 impl<'a> EMLElementWriter<'a> {
@@ -119,7 +254,11 @@ impl<'a> EMLElementWriter<'a> {
         }
 
         let start_tag = BytesStart::new(elem_name);
-        Ok(EMLElementWriter { start_tag, writer })
+        Ok(EMLElementWriter {
+            start_tag,
+            writer,
+            pending: PendingExtensions::default(),
+        })
     }
 
     pub fn attr<'b, 'c>(
@@ -150,6 +289,78 @@ impl<'a> EMLElementWriter<'a> {
         self
     }
 
+    /// Writes the preserved unknown attributes of this element, and the
+    /// preserved unknown elements and annotations in between the children of
+    /// this element.
+    pub fn extensions<'e>(
+        mut self,
+        extensions: &'e Extensions,
+    ) -> Result<EMLElementWriter<'e>, EMLError>
+    where
+        'a: 'e,
+    {
+        for attr in &extensions.attributes {
+            self = self.unknown_attr(attr)?;
+        }
+        Ok(EMLElementWriter {
+            start_tag: self.start_tag,
+            writer: self.writer,
+            pending: PendingExtensions {
+                elements: &extensions.elements,
+                annotations: &extensions.annotations,
+                elements_written: 0,
+            },
+        })
+    }
+
+    /// Writes a preserved unknown attribute. If the namespace of the attribute
+    /// is not known to the writer, it is declared on this element.
+    fn unknown_attr(self, attr: &UnknownAttribute) -> Result<Self, EMLError> {
+        let value = attr.value.as_str();
+        let Some(namespace) = attr.name.namespace.as_deref() else {
+            return Ok(self.attr_raw((attr.name.local_name.as_ref(), value)));
+        };
+        if namespace == NS_XML {
+            let name = format!("xml:{}", attr.name.local_name);
+            return Ok(self.attr_raw((name.as_str(), value)));
+        }
+        if let Ok(Some(prefix)) = self.writer.resolve_namespace_prefix(namespace, true) {
+            let name = format!("{prefix}:{}", attr.name.local_name);
+            return Ok(self.attr_raw((name.as_str(), value)));
+        }
+
+        // Find a prefix that is either unused or already declared on this
+        // element for the same namespace, preferring the original prefix
+        let base = attr.prefix.as_deref().unwrap_or("ns");
+        let mut prefix = base.to_string();
+        for i in 1.. {
+            let declaration = format!("xmlns:{prefix}");
+            let declared = self
+                .start_tag
+                .try_get_attribute(declaration.as_str())
+                .without_span()?;
+            match declared {
+                Some(declared) if declared.value.as_ref() == namespace.as_bytes() => {
+                    let name = format!("{prefix}:{}", attr.name.local_name);
+                    return Ok(self.attr_raw((name.as_str(), value)));
+                }
+                None if !self
+                    .writer
+                    .ns_definitions
+                    .namespace_definitions
+                    .contains_key(prefix.as_str()) =>
+                {
+                    let name = format!("{prefix}:{}", attr.name.local_name);
+                    return Ok(self
+                        .attr_raw((declaration.as_str(), namespace))
+                        .attr_raw((name.as_str(), value)));
+                }
+                _ => prefix = format!("{base}{i}"),
+            }
+        }
+        unreachable!("There is always an unused prefix")
+    }
+
     pub fn content(self) -> Result<EMLElementContentWriter<'a>, EMLError> {
         self.writer
             .writer
@@ -158,6 +369,8 @@ impl<'a> EMLElementWriter<'a> {
         Ok(EMLElementContentWriter {
             start_tag: self.start_tag,
             writer: self.writer,
+            pending: self.pending,
+            children: 0,
         })
     }
 
@@ -199,6 +412,9 @@ impl<'a> EMLElementWriter<'a> {
     }
 
     pub fn empty(self) -> Result<(), EMLError> {
+        if !self.pending.is_empty() {
+            return self.content()?.finish();
+        }
         self.writer
             .writer
             .write_event(Event::Empty(self.start_tag.borrow()))
@@ -210,17 +426,22 @@ impl<'a> EMLElementWriter<'a> {
 pub(crate) struct EMLElementContentWriter<'a> {
     start_tag: BytesStart<'a>,
     writer: &'a mut EMLWriter,
+    pending: PendingExtensions<'a>,
+    /// Number of child elements written so far.
+    children: usize,
 }
 
 impl<'a> EMLElementContentWriter<'a> {
     pub fn child<'b, 'c>(
-        self,
+        mut self,
         name: impl Into<QualifiedName<'b, 'c>>,
         child_writer: impl FnOnce(EMLElementWriter) -> Result<(), EMLError>,
     ) -> Result<Self, EMLError> {
+        self.pending.write(self.writer, Some(self.children))?;
         let name = name.into();
         let elem_writer = EMLElementWriter::new(self.writer, &name)?;
         child_writer(elem_writer)?;
+        self.children += 1;
         Ok(self)
     }
 
@@ -255,6 +476,34 @@ impl<'a> EMLElementContentWriter<'a> {
         })
     }
 
+    /// Writes the `CanonicalizationMethod` element describing how the output
+    /// is canonicalized. It is only written for canonicalized output, as
+    /// otherwise it would not be true.
+    ///
+    /// Whether the element was present when reading is needed to keep the
+    /// preserved unknown elements and annotations after it in place, as their
+    /// position counts the known elements that were read before them.
+    pub fn canonicalization_method(mut self, was_read: bool) -> Result<Self, EMLError> {
+        match self
+            .writer
+            .canonicalization
+            .map(CanonicalizationMethod::new)
+        {
+            Some(method) => {
+                self = self.child_elem(CanonicalizationMethod::EML_NAME, &method)?;
+                if !was_read {
+                    self.children -= 1;
+                }
+            }
+            None if was_read => {
+                self.pending.write(self.writer, Some(self.children))?;
+                self.children += 1;
+            }
+            None => {}
+        }
+        Ok(self)
+    }
+
     pub fn text(self, text: &str) -> Result<Self, EMLError> {
         self.writer
             .writer
@@ -263,7 +512,8 @@ impl<'a> EMLElementContentWriter<'a> {
         Ok(self)
     }
 
-    pub fn finish(self) -> Result<(), EMLError> {
+    pub fn finish(mut self) -> Result<(), EMLError> {
+        self.pending.write(self.writer, None)?;
         self.writer
             .writer
             .write_event(quick_xml::events::Event::End(self.start_tag.to_end()))
@@ -280,6 +530,7 @@ pub(crate) trait EMLWriteInternal {
         namespace_definitions: Option<HashMap<&'static str, &'static str>>,
         pretty_print: bool,
         include_declaration: bool,
+        canonicalization: Option<CanonicalizationAlgorithm>,
     ) -> Result<Vec<u8>, EMLError>;
 
     fn write_root_str<'a, 'b>(
@@ -289,6 +540,7 @@ pub(crate) trait EMLWriteInternal {
         namespace_definitions: Option<HashMap<&'static str, &'static str>>,
         pretty_print: bool,
         include_declaration: bool,
+        canonicalization: Option<CanonicalizationAlgorithm>,
     ) -> Result<String, EMLError>;
 }
 
@@ -303,6 +555,7 @@ where
         namespace_definitions: Option<HashMap<&'static str, &'static str>>,
         pretty_print: bool,
         include_declaration: bool,
+        canonicalization: Option<CanonicalizationAlgorithm>,
     ) -> Result<Vec<u8>, EMLError> {
         // default values are for EML root element
         let root = root_name
@@ -314,17 +567,25 @@ where
             ns_defs.insert("kr", NS_KR);
             ns_defs.insert("xal", NS_XAL);
             ns_defs.insert("xnl", NS_XNL);
-            // ns_defs.insert("ds", NS_DS);
+            ns_defs.insert("ds", NS_DS);
             // ns_defs.insert("xmlns", NS_XMLNS);
             // ns_defs.insert("xml", NS_XML);
             ns_defs
         });
+        let mut namespace_definitions = namespace_definitions;
+        if canonicalization.is_some() && !namespace_definitions.values().any(|uri| *uri == NS_DS) {
+            // Needed to write the CanonicalizationMethod element
+            namespace_definitions.insert("ds", NS_DS);
+        }
 
         let ns_definitions = NsDefinitions {
             default_namespace_uri,
             namespace_definitions,
         };
 
+        // Canonical output has no declaration and keeps all whitespace
+        let pretty_print = pretty_print && canonicalization.is_none();
+        let include_declaration = include_declaration && canonicalization.is_none();
         let mut writer = if pretty_print {
             Writer::new_with_indent(Vec::new(), b' ', 4)
         } else {
@@ -339,16 +600,27 @@ where
         let mut eml_writer = EMLWriter {
             ns_definitions: ns_definitions.clone(),
             writer,
+            canonicalization,
         };
         let mut element = EMLElementWriter::new(&mut eml_writer, &root)?;
         if let Some(ns_uri) = ns_definitions.default_namespace_uri {
             element = element.attr_raw(("xmlns", ns_uri));
         }
-        for (prefix, uri) in &ns_definitions.namespace_definitions {
+        // Sort the prefixes so the output does not depend on hash map ordering
+        let mut prefixes: Vec<_> = ns_definitions.namespace_definitions.iter().collect();
+        prefixes.sort();
+        for (prefix, uri) in prefixes {
             element = element.attr_raw((format!("xmlns:{}", *prefix).as_str(), *uri));
         }
         self.write_eml_element(element)?;
-        Ok(eml_writer.writer.into_inner())
+        let output = eml_writer.writer.into_inner();
+        match canonicalization {
+            Some(algorithm) => {
+                let output = String::from_utf8(output).without_span()?;
+                Ok(canonicalize(&output, algorithm)?.into_bytes())
+            }
+            None => Ok(output),
+        }
     }
 
     fn write_root_str<'a, 'b>(
@@ -358,6 +630,7 @@ where
         namespace_definitions: Option<HashMap<&'static str, &'static str>>,
         pretty_print: bool,
         include_declaration: bool,
+        canonicalization: Option<CanonicalizationAlgorithm>,
     ) -> Result<String, EMLError> {
         String::from_utf8(self.write_root(
             root_name,
@@ -365,6 +638,7 @@ where
             namespace_definitions,
             pretty_print,
             include_declaration,
+            canonicalization,
         )?)
         .without_span()
     }
@@ -390,12 +664,57 @@ pub trait EMLWrite {
         pretty_print: bool,
         include_declaration: bool,
     ) -> Result<String, EMLError>;
+
+    /// Writes an EML document with an EML root element to a byte vector in
+    /// canonical form, using the given canonicalization algorithm.
+    ///
+    /// The document includes a `CanonicalizationMethod` element stating the
+    /// algorithm that was used.
+    fn write_eml_root_canonical(
+        &self,
+        algorithm: CanonicalizationAlgorithm,
+    ) -> Result<Vec<u8>, EMLError>;
+
+    /// Writes an EML document with an EML root element to a string in
+    /// canonical form, using the given canonicalization algorithm.
+    fn write_eml_root_canonical_str(
+        &self,
+        algorithm: CanonicalizationAlgorithm,
+    ) -> Result<String, EMLError>;
 }
 
 impl<T> EMLWrite for T
 where
     T: EMLWriteInternal,
 {
+    fn write_eml_root_canonical(
+        &self,
+        algorithm: CanonicalizationAlgorithm,
+    ) -> Result<Vec<u8>, EMLError> {
+        self.write_root(
+            None::<QualifiedName<'_, '_>>,
+            None,
+            None,
+            false,
+            false,
+            Some(algorithm),
+        )
+    }
+
+    fn write_eml_root_canonical_str(
+        &self,
+        algorithm: CanonicalizationAlgorithm,
+    ) -> Result<String, EMLError> {
+        self.write_root_str(
+            None::<QualifiedName<'_, '_>>,
+            None,
+            None,
+            false,
+            false,
+            Some(algorithm),
+        )
+    }
+
     fn write_eml_root(
         &self,
         pretty_print: bool,
@@ -407,6 +726,7 @@ where
             None,
             pretty_print,
             include_declaration,
+            None,
         )
     }
 
@@ -422,6 +742,7 @@ where
             None,
             pretty_print,
             include_declaration,
+            None,
         )
     }
 }
@@ -435,3 +756,33 @@ pub(crate) fn write_eml_element(
 ) -> impl FnOnce(EMLElementWriter) -> Result<(), EMLError> {
     |writer| element.write_eml_element(writer)
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        documents::election_definition::ElectionDefinition,
+        io::{EMLParsingMode, EMLRead as _, EMLWrite as _},
+    };
+
+    #[test]
+    fn test_root_namespace_definitions_sorted() {
+        let doc = include_str!("../../test-emls/election_definition/eml110a_test.eml.xml");
+        let election_definition = ElectionDefinition::parse_eml(doc, EMLParsingMode::Strict)
+            .ok()
+            .expect("Failed to parse EML document");
+        let written = election_definition
+            .write_eml_root_str(false, false)
+            .expect("Failed to write EML document");
+
+        let root = &written[..written.find('>').unwrap()];
+        let prefixes: Vec<_> = root
+            .split(" xmlns:")
+            .skip(1)
+            .map(|def| def.split('=').next().unwrap())
+            .collect();
+        assert!(prefixes.len() > 1);
+        let mut sorted = prefixes.clone();
+        sorted.sort();
+        assert_eq!(prefixes, sorted);
+    }
+}
@@ -17,24 +17,37 @@
 //! trait, while writing is done through the [`EMLWrite`](crate::io::EMLWrite)
 //! trait.
 //!
-//! This crate only parses and writes EML documents in memory, it does not
-//! support streaming parsing or writing. This was a design decision to keep
-//! the code simple and maintainable, and it is expected that EML documents will
-//! generally not be extremely large. Up to a few megabytes were expected, but
-//! larger documents will work fine as long as enough memory is available.
+//! Documents are parsed and written in memory. This was a design decision to
+//! keep the code simple and maintainable, and it is expected that EML documents
+//! will generally not be extremely large. Up to a few megabytes were expected,
+//! but larger documents will work fine as long as enough memory is available.
 //! Expect somewhere between 1.2 and 2.0 times the original document size
 //! depending on the contents of the file.
+//!
+//! For very large documents, such as counts with thousands of reporting units,
+//! the [`EMLStreamReader`](crate::io::EMLStreamReader) reads the reporting
+//! units, polling places or candidates one at a time, while keeping the rest of
+//! the document available.
 
 // This crate must only use safe Rust code.
 #![forbid(unsafe_code)]
 // All public items must have some kinds of documentation.
 #![forbid(missing_docs)]
 
+#[cfg(any(test, feature = "arbitrary"))]
+pub mod arbitrary;
 pub mod common;
+pub mod diagnostics;
+pub mod diff;
 pub mod documents;
 mod error;
+#[cfg(feature = "hash")]
+pub mod hash;
 pub mod io;
 pub mod utils;
+pub mod validation;
+#[cfg(feature = "xmldsig")]
+pub mod xmldsig;
 
 pub use error::*;
 
@@ -58,5 +71,6 @@ pub(crate) const NS_DS: &str = "http://www.w3.org/2000/09/xmldsig#";
 
 // /// Namespace URI for XML Schema
 // pub(crate) const NS_XMLNS: &str = "http://www.w3.org/2000/xmlns/";
-// /// Namespace URI for XML
-// pub(crate) const NS_XML: &str = "http://www.w3.org/XML/1998/namespace";
+
+/// Namespace URI for XML, bound to the `xml` prefix by definition
+pub(crate) const NS_XML: &str = "http://www.w3.org/XML/1998/namespace";
@@ -50,6 +50,8 @@ impl StringValueData for AffiliationIdType {
     }
 }
 
+crate::utils::impl_serde_raw!(AffiliationIdType);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -53,6 +53,8 @@ impl StringValueData for AffiliationType {
     }
 }
 
+crate::utils::impl_serde_raw!(AffiliationType);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,130 @@
+use thiserror::Error;
+
+use crate::utils::StringValueData;
+
+/// Alphabet of the base64 encoding used by `xs:base64Binary`.
+const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Binary data encoded as `xs:base64Binary`, e.g. digest and signature values
+/// in an XML signature.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[repr(transparent)]
+pub struct Base64Binary(Vec<u8>);
+
+impl Base64Binary {
+    /// Create a new Base64Binary from the decoded bytes.
+    pub fn new(bytes: impl Into<Vec<u8>>) -> Self {
+        Base64Binary(bytes.into())
+    }
+
+    /// Get the decoded bytes.
+    pub fn bytes(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+/// Error type returned when an invalid base64 value is encountered.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[repr(transparent)]
+#[error("Invalid base64 value: {0}")]
+pub struct InvalidBase64ValueError(String);
+
+impl StringValueData for Base64Binary {
+    type Error = InvalidBase64ValueError;
+
+    /// Whitespace in the value is ignored, as base64 values in XML are often
+    /// split over multiple lines.
+    fn parse_from_str(s: &str) -> Result<Self, Self::Error>
+    where
+        Self: Sized,
+    {
+        let invalid = || InvalidBase64ValueError(s.to_string());
+        let digits: Vec<u8> = s.bytes().filter(|b| !b.is_ascii_whitespace()).collect();
+        if !digits.len().is_multiple_of(4) {
+            return Err(invalid());
+        }
+        let padding = digits.iter().rev().take_while(|b| **b == b'=').count();
+        if padding > 2 {
+            return Err(invalid());
+        }
+
+        let mut bytes = Vec::with_capacity(digits.len() / 4 * 3);
+        let mut buffer = 0u32;
+        for (i, digit) in digits[..digits.len() - padding].iter().enumerate() {
+            let value = ALPHABET
+                .iter()
+                .position(|c| c == digit)
+                .ok_or_else(invalid)?;
+            buffer = (buffer << 6) | value as u32;
+            if i % 4 == 3 {
+                bytes.extend_from_slice(&buffer.to_be_bytes()[1..]);
+                buffer = 0;
+            }
+        }
+        match padding {
+            1 => bytes.extend_from_slice(&(buffer >> 2).to_be_bytes()[2..]),
+            2 => bytes.push((buffer >> 4) as u8),
+            _ => {}
+        }
+        Ok(Base64Binary(bytes))
+    }
+
+    fn to_raw_value(&self) -> String {
+        let mut output = String::with_capacity(self.0.len().div_ceil(3) * 4);
+        for chunk in self.0.chunks(3) {
+            let mut buffer = [0u8; 3];
+            buffer[..chunk.len()].copy_from_slice(chunk);
+            let value = u32::from_be_bytes([0, buffer[0], buffer[1], buffer[2]]);
+            for i in 0..4 {
+                if i <= chunk.len() {
+                    output.push(ALPHABET[(value >> (18 - 6 * i) & 0x3f) as usize] as char);
+                } else {
+                    output.push('=');
+                }
+            }
+        }
+        output
+    }
+}
+
+crate::utils::impl_serde_raw!(Base64Binary);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_base64_round_trip() {
+        let cases: [(&[u8], &str); 5] = [
+            (b"", ""),
+            (b"f", "Zg=="),
+            (b"fo", "Zm8="),
+            (b"foo", "Zm9v"),
+            (b"foob\xff", "Zm9vYv8="),
+        ];
+        for (bytes, encoded) in cases {
+            assert_eq!(Base64Binary::new(bytes).to_raw_value(), encoded);
+            assert_eq!(
+                Base64Binary::parse_from_str(encoded).unwrap().bytes(),
+                bytes
+            );
+        }
+        assert_eq!(
+            Base64Binary::parse_from_str(" Zm9v\n  Zm8= ")
+                .unwrap()
+                .bytes(),
+            b"foofo"
+        );
+    }
+
+    #[test]
+    fn test_base64_invalid_values() {
+        for value in ["Zm9", "Zm9v!A==", "Z===", "Zm=v"] {
+            assert!(
+                Base64Binary::parse_from_str(value).is_err(),
+                "Expected '{}' to fail parsing",
+                value
+            );
+        }
+    }
+}
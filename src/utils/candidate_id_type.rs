@@ -50,6 +50,8 @@ impl StringValueData for CandidateIdType {
     }
 }
 
+crate::utils::impl_serde_raw!(CandidateIdType);
+
 #[cfg(test)]
 mod tests {
     use super::*;
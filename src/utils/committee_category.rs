@@ -0,0 +1,57 @@
+use thiserror::Error;
+
+use crate::utils::StringValueData;
+
+/// Category of an electoral committee responsible for a region.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommitteeCategory {
+    /// Central electoral committee ("centraal stembureau")
+    CSB,
+    /// Principal electoral committee ("hoofdstembureau")
+    HSB,
+    /// Municipal electoral committee ("gemeentelijk stembureau")
+    GSB,
+}
+
+impl CommitteeCategory {
+    /// Create a CommitteeCategory from a `&str`, if possible.
+    pub fn from_str_value(s: &str) -> Option<Self> {
+        match s {
+            "CSB" => Some(CommitteeCategory::CSB),
+            "HSB" => Some(CommitteeCategory::HSB),
+            "GSB" => Some(CommitteeCategory::GSB),
+            _ => None,
+        }
+    }
+
+    /// Get the `&str` representation of this CommitteeCategory.
+    pub fn to_str_value(&self) -> &'static str {
+        match self {
+            CommitteeCategory::CSB => "CSB",
+            CommitteeCategory::HSB => "HSB",
+            CommitteeCategory::GSB => "GSB",
+        }
+    }
+}
+
+/// Error returned when an unknown committee category string is encountered.
+#[derive(Debug, Clone, Error)]
+#[error("Unknown committee category: {0}")]
+pub struct UnknownCommitteeCategoryError(String);
+
+impl StringValueData for CommitteeCategory {
+    type Error = UnknownCommitteeCategoryError;
+
+    fn parse_from_str(s: &str) -> Result<Self, Self::Error>
+    where
+        Self: Sized,
+    {
+        Self::from_str_value(s).ok_or(UnknownCommitteeCategoryError(s.to_string()))
+    }
+
+    fn to_raw_value(&self) -> String {
+        self.to_str_value().to_string()
+    }
+}
+
+crate::utils::impl_serde_raw!(CommitteeCategory);
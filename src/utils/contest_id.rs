@@ -72,6 +72,8 @@ impl StringValueData for ContestIdType {
     }
 }
 
+crate::utils::impl_serde_raw!(ContestIdType);
+
 /// A ContestIdType representing a fixed "geen" value
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ContestIdTypeGeen;
@@ -121,6 +123,8 @@ impl StringValueData for ContestIdTypeGeen {
     }
 }
 
+crate::utils::impl_serde_raw!(ContestIdTypeGeen);
+
 #[cfg(test)]
 mod tests {
     use super::*;
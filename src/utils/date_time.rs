@@ -82,6 +82,8 @@ impl StringValueData for XsDate {
     }
 }
 
+crate::utils::impl_serde_raw!(XsDate);
+
 /// Represents an `xs:dateTime`.
 ///
 /// These kinds of date-times may optionally contain timezone information using
@@ -192,6 +194,8 @@ impl StringValueData for XsDateTime {
     }
 }
 
+crate::utils::impl_serde_raw!(XsDateTime);
+
 /// Represents either an `xs:date` or an `xs:dateTime`.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum XsDateOrDateTime {
@@ -263,6 +267,8 @@ impl StringValueData for XsDateOrDateTime {
     }
 }
 
+crate::utils::impl_serde_raw!(XsDateOrDateTime);
+
 #[cfg(test)]
 mod tests {
     use chrono::{Datelike as _, Timelike as _};
@@ -94,6 +94,8 @@ impl StringValueData for ElectionCategory {
     }
 }
 
+crate::utils::impl_serde_raw!(ElectionCategory);
+
 /// Subcategory of the election, providing more specific information about the type of election.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ElectionSubcategory {
@@ -176,6 +178,25 @@ impl ElectionSubcategory {
             ElectionSubcategory::IR => "IR",
         }
     }
+
+    /// Get the election category this subcategory belongs to.
+    pub fn category(&self) -> ElectionCategory {
+        match self {
+            ElectionSubcategory::PS1 | ElectionSubcategory::PS2 => ElectionCategory::PS,
+            ElectionSubcategory::AB1 | ElectionSubcategory::AB2 => ElectionCategory::AB,
+            ElectionSubcategory::GR1 | ElectionSubcategory::GR2 => ElectionCategory::GR,
+            ElectionSubcategory::BC => ElectionCategory::BC,
+            ElectionSubcategory::GC => ElectionCategory::GC,
+            ElectionSubcategory::ER1 => ElectionCategory::ER,
+            ElectionSubcategory::TK => ElectionCategory::TK,
+            ElectionSubcategory::EK => ElectionCategory::EK,
+            ElectionSubcategory::EP => ElectionCategory::EP,
+            ElectionSubcategory::NR => ElectionCategory::NR,
+            ElectionSubcategory::PR => ElectionCategory::PR,
+            ElectionSubcategory::LR => ElectionCategory::LR,
+            ElectionSubcategory::IR => ElectionCategory::IR,
+        }
+    }
 }
 
 /// Error returned when an unknown election subcategory string is encountered.
@@ -198,6 +219,8 @@ impl StringValueData for ElectionSubcategory {
     }
 }
 
+crate::utils::impl_serde_raw!(ElectionSubcategory);
+
 #[cfg(test)]
 mod tests {
     use super::*;
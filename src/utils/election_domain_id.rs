@@ -51,6 +51,8 @@ impl StringValueData for ElectionDomainIdType {
     }
 }
 
+crate::utils::impl_serde_raw!(ElectionDomainIdType);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -52,6 +52,8 @@ impl StringValueData for ElectionIdType {
     }
 }
 
+crate::utils::impl_serde_raw!(ElectionIdType);
+
 #[cfg(test)]
 mod tests {
     use super::*;
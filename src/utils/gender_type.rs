@@ -53,3 +53,5 @@ impl StringValueData for GenderType {
         self.to_str_value().to_string()
     }
 }
+
+crate::utils::impl_serde_raw!(GenderType);
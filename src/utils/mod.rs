@@ -2,7 +2,9 @@
 
 mod affiliation_id_type;
 mod affiliation_type;
+mod base64_binary;
 mod candidate_id_type;
+mod committee_category;
 mod contest_id;
 mod date_time;
 mod election_category;
@@ -11,15 +13,20 @@ mod election_id;
 mod gender_type;
 mod name_short_code_type;
 mod publication_language_type;
+mod region_category;
+mod rejected_votes_reason;
 mod reporting_unit_identifier_id;
 mod string_value;
 mod voting_channel;
 mod voting_method;
 mod xsb;
+mod yes_no_type;
 
 pub use affiliation_id_type::*;
 pub use affiliation_type::*;
+pub use base64_binary::*;
 pub use candidate_id_type::*;
+pub use committee_category::*;
 pub use contest_id::*;
 pub use date_time::*;
 pub use election_category::*;
@@ -28,8 +35,11 @@ pub use election_id::*;
 pub use gender_type::*;
 pub use name_short_code_type::*;
 pub use publication_language_type::*;
+pub use region_category::*;
+pub use rejected_votes_reason::*;
 pub use reporting_unit_identifier_id::*;
 pub use string_value::*;
 pub use voting_channel::*;
 pub use voting_method::*;
 pub use xsb::*;
+pub use yes_no_type::*;
@@ -52,6 +52,8 @@ impl StringValueData for NameShortCodeType {
     }
 }
 
+crate::utils::impl_serde_raw!(NameShortCodeType);
+
 #[cfg(test)]
 mod tests {
     use super::*;
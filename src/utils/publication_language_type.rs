@@ -51,3 +51,5 @@ impl StringValueData for PublicationLanguageType {
         self.to_str_value().to_string()
     }
 }
+
+crate::utils::impl_serde_raw!(PublicationLanguageType);
@@ -0,0 +1,65 @@
+use thiserror::Error;
+
+use crate::utils::StringValueData;
+
+/// Category of a region in the election tree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RegionCategory {
+    /// Borough of a municipality ("deelgemeente")
+    Borough,
+    /// Municipality ("gemeente")
+    Municipality,
+    /// Electoral district ("kieskring")
+    ElectoralDistrict,
+    /// Province ("provincie")
+    Province,
+    /// Water board ("waterschap")
+    WaterBoard,
+}
+
+impl RegionCategory {
+    /// Create a RegionCategory from a `&str`, if possible.
+    pub fn from_str_value(s: &str) -> Option<Self> {
+        match s {
+            "DEELGEMEENTE" => Some(RegionCategory::Borough),
+            "GEMEENTE" => Some(RegionCategory::Municipality),
+            "KIESKRING" => Some(RegionCategory::ElectoralDistrict),
+            "PROVINCIE" => Some(RegionCategory::Province),
+            "WATERSCHAP" => Some(RegionCategory::WaterBoard),
+            _ => None,
+        }
+    }
+
+    /// Get the `&str` representation of this RegionCategory.
+    pub fn to_str_value(&self) -> &'static str {
+        match self {
+            RegionCategory::Borough => "DEELGEMEENTE",
+            RegionCategory::Municipality => "GEMEENTE",
+            RegionCategory::ElectoralDistrict => "KIESKRING",
+            RegionCategory::Province => "PROVINCIE",
+            RegionCategory::WaterBoard => "WATERSCHAP",
+        }
+    }
+}
+
+/// Error returned when an unknown region category string is encountered.
+#[derive(Debug, Clone, Error)]
+#[error("Unknown region category: {0}")]
+pub struct UnknownRegionCategoryError(String);
+
+impl StringValueData for RegionCategory {
+    type Error = UnknownRegionCategoryError;
+
+    fn parse_from_str(s: &str) -> Result<Self, Self::Error>
+    where
+        Self: Sized,
+    {
+        Self::from_str_value(s).ok_or(UnknownRegionCategoryError(s.to_string()))
+    }
+
+    fn to_raw_value(&self) -> String {
+        self.to_str_value().to_string()
+    }
+}
+
+crate::utils::impl_serde_raw!(RegionCategory);
@@ -0,0 +1,53 @@
+use thiserror::Error;
+
+use crate::utils::StringValueData;
+
+/// Reason code for votes that were rejected during counting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RejectedVotesReason {
+    /// Invalid votes ("ongeldig")
+    Invalid,
+    /// Blank votes ("blanco")
+    Blank,
+}
+
+impl RejectedVotesReason {
+    /// Create a RejectedVotesReason from a `&str`, if possible.
+    pub fn from_str_value(s: &str) -> Option<Self> {
+        match s {
+            "ongeldig" => Some(RejectedVotesReason::Invalid),
+            "blanco" => Some(RejectedVotesReason::Blank),
+            _ => None,
+        }
+    }
+
+    /// Get the `&str` representation of this RejectedVotesReason.
+    pub fn to_str_value(&self) -> &'static str {
+        match self {
+            RejectedVotesReason::Invalid => "ongeldig",
+            RejectedVotesReason::Blank => "blanco",
+        }
+    }
+}
+
+/// Error returned when an unknown rejected votes reason string is encountered.
+#[derive(Debug, Clone, Error)]
+#[error("Unknown rejected votes reason: {0}")]
+pub struct UnknownRejectedVotesReasonError(String);
+
+impl StringValueData for RejectedVotesReason {
+    type Error = UnknownRejectedVotesReasonError;
+
+    fn parse_from_str(s: &str) -> Result<Self, Self::Error>
+    where
+        Self: Sized,
+    {
+        Self::from_str_value(s).ok_or(UnknownRejectedVotesReasonError(s.to_string()))
+    }
+
+    fn to_raw_value(&self) -> String {
+        self.to_str_value().to_string()
+    }
+}
+
+crate::utils::impl_serde_raw!(RejectedVotesReason);
@@ -51,6 +51,8 @@ impl StringValueData for ReportingUnitIdentifierId {
     }
 }
 
+crate::utils::impl_serde_raw!(ReportingUnitIdentifierId);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -84,6 +84,79 @@ impl<T: StringValueData> StringValue<T> {
     }
 }
 
+impl<T: StringValueData> StringValue<T> {
+    /// Get the canonical raw string value.
+    ///
+    /// Raw values that can be parsed are normalized through `T`, so that for
+    /// example `"012"` and `12` result in the same canonical value. Raw values
+    /// that cannot be parsed are returned as is.
+    fn canonical_raw(&self) -> Cow<'_, str> {
+        match self {
+            StringValue::Raw(s) => match T::parse_from_str(s) {
+                Ok(v) => Cow::Owned(v.to_raw_value()),
+                Err(_) => Cow::Borrowed(s),
+            },
+            StringValue::Parsed(v) => Cow::Owned(v.to_raw_value()),
+        }
+    }
+}
+
+/// Values are compared by their canonical raw form, regardless of whether
+/// they are stored as [`StringValue::Raw`] or [`StringValue::Parsed`]. This
+/// makes documents parsed in different parsing modes compare equal.
+impl<T: StringValueData> PartialEq for StringValue<T> {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (StringValue::Raw(a), StringValue::Raw(b)) if a == b => true,
+            _ => self.canonical_raw() == other.canonical_raw(),
+        }
+    }
+}
+
+impl<T: StringValueData> Eq for StringValue<T> {}
+
+/// Serializes as the raw string value.
+#[cfg(feature = "serde")]
+impl<T: StringValueData> serde::Serialize for StringValue<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.raw())
+    }
+}
+
+/// Deserializes from a string, which is stored as a [`StringValue::Raw`] value.
+#[cfg(feature = "serde")]
+impl<'de, T: StringValueData> serde::Deserialize<'de> for StringValue<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer).map(StringValue::Raw)
+    }
+}
+
+/// Implements `serde::Serialize` and `serde::Deserialize` for a [`StringValueData`]
+/// type when the `serde` feature is enabled, using its raw string representation.
+///
+/// Deserializing fails if the string is not a valid value.
+macro_rules! impl_serde_raw {
+    ($ty:ty) => {
+        #[cfg(feature = "serde")]
+        impl serde::Serialize for $ty {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_str(&$crate::utils::StringValueData::to_raw_value(self))
+            }
+        }
+
+        #[cfg(feature = "serde")]
+        impl<'de> serde::Deserialize<'de> for $ty {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let s = <String as serde::Deserialize>::deserialize(deserializer)?;
+                <$ty as $crate::utils::StringValueData>::parse_from_str(&s)
+                    .map_err(serde::de::Error::custom)
+            }
+        }
+    };
+}
+
+pub(crate) use impl_serde_raw;
+
 impl StringValueData for String {
     type Error = Infallible;
 
@@ -152,3 +225,24 @@ impl StringValueData for bool {
         self.to_string()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_string_value_equality() {
+        let raw = StringValue::<u64>::from_raw("012");
+        let parsed = StringValue::<u64>::from_value(12);
+        assert_eq!(raw, parsed);
+        assert_eq!(parsed, raw);
+        assert_eq!(raw, StringValue::from_raw("12"));
+        assert_ne!(raw, StringValue::from_value(13));
+
+        // Values that cannot be parsed are compared as is
+        let invalid = StringValue::<u64>::from_raw("twelve");
+        assert_eq!(invalid, StringValue::from_raw("twelve"));
+        assert_ne!(invalid, StringValue::from_raw("Twelve"));
+        assert_ne!(invalid, parsed);
+    }
+}
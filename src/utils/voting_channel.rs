@@ -49,3 +49,5 @@ impl StringValueData for VotingChannelType {
         self.to_str_value().to_string()
     }
 }
+
+crate::utils::impl_serde_raw!(VotingChannelType);
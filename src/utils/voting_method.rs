@@ -101,3 +101,5 @@ impl StringValueData for VotingMethod {
         self.to_str_value().to_string()
     }
 }
+
+crate::utils::impl_serde_raw!(VotingMethod);
@@ -52,6 +52,8 @@ impl StringValueData for XSBType {
     }
 }
 
+crate::utils::impl_serde_raw!(XSBType);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,65 @@
+use thiserror::Error;
+
+use crate::utils::StringValueData;
+
+/// A yes or no value, as used in EML_NL documents.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum YesNoType {
+    /// Yes
+    Yes,
+    /// No
+    No,
+}
+
+impl YesNoType {
+    /// Create a YesNoType from a `&str`, if possible.
+    pub fn from_str_value(s: &str) -> Option<Self> {
+        match s {
+            "yes" => Some(YesNoType::Yes),
+            "no" => Some(YesNoType::No),
+            _ => None,
+        }
+    }
+
+    /// Get the `&str` representation of this YesNoType.
+    pub fn to_str_value(&self) -> &'static str {
+        match self {
+            YesNoType::Yes => "yes",
+            YesNoType::No => "no",
+        }
+    }
+}
+
+impl From<bool> for YesNoType {
+    fn from(value: bool) -> Self {
+        if value { YesNoType::Yes } else { YesNoType::No }
+    }
+}
+
+impl From<YesNoType> for bool {
+    fn from(value: YesNoType) -> Self {
+        value == YesNoType::Yes
+    }
+}
+
+/// Error returned when an unknown yes/no string is encountered.
+#[derive(Debug, Clone, Error)]
+#[error("Unknown yes/no value: {0}")]
+pub struct UnknownYesNoTypeError(String);
+
+impl StringValueData for YesNoType {
+    type Error = UnknownYesNoTypeError;
+
+    fn parse_from_str(s: &str) -> Result<Self, Self::Error>
+    where
+        Self: Sized,
+    {
+        Self::from_str_value(s).ok_or(UnknownYesNoTypeError(s.to_string()))
+    }
+
+    fn to_raw_value(&self) -> String {
+        self.to_str_value().to_string()
+    }
+}
+
+crate::utils::impl_serde_raw!(YesNoType);
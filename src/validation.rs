@@ -0,0 +1,130 @@
+//! Semantic validation of EML_NL documents.
+//!
+//! Parsing a document only checks that it is well-formed and structurally
+//! valid. Documents that support it also offer a `validate` method that
+//! checks the rules of the Kiesraad for that document, such as
+//! [`ElectionDefinition::validate`](crate::documents::election_definition::ElectionDefinition::validate).
+//! Validation never fails, instead it returns a list of findings which is
+//! empty when no problems were found.
+
+use crate::utils::{StringValue, StringValueData};
+
+/// A semantic problem found while validating an EML_NL document.
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ValidationFinding {
+    /// A value could not be interpreted, so rules depending on it were not checked
+    #[error("Invalid value for {0}: {1}")]
+    InvalidValue(String, String),
+
+    /// The election subcategory does not belong to the election category
+    #[error("Election subcategory {1} does not belong to election category {0}")]
+    SubcategoryMismatch(String, String),
+
+    /// The election subcategory does not match the number of seats
+    #[error("Election subcategory {0} does not match {1} seats, expected {2}")]
+    SubcategorySeatsMismatch(String, u64, String),
+
+    /// The number of seats is not allowed for the election category
+    #[error("Number of seats {1} is out of range for election category {0}, expected {2} to {3}")]
+    NumberOfSeatsOutOfRange(String, u64, u64, u64),
+
+    /// The preference threshold does not match the one required for the election
+    #[error("Preference threshold {0}% does not match the expected {1}%")]
+    PreferenceThresholdMismatch(u64, u64),
+
+    /// The nomination date is not before the election date
+    #[error("Nomination date {0} is not before the election date {1}")]
+    NominationDateNotBeforeElectionDate(String, String),
+
+    /// The election tree does not contain any regions
+    #[error("Election tree does not contain any regions")]
+    EmptyElectionTree,
+
+    /// Multiple polling stations use the same id
+    #[error("Polling station id {0} is used more than once")]
+    DuplicatePollingStationId(String),
+
+    /// A polling station id is not higher than the id of the polling station before it
+    #[error("Polling station id {0} is not higher than the preceding id {1}")]
+    PollingStationIdOutOfOrder(String, String),
+
+    /// The number of registered voters of a polling station is not a number
+    #[error("Polling station {0} has an invalid number of registered voters: {1}")]
+    InvalidNumberOfVoters(String, String),
+
+    /// The sum of registered voters of all polling stations does not match the maximum number of votes
+    #[error("Sum of registered voters {0} does not match the maximum number of votes {1}")]
+    NumberOfVotersMismatch(u64, u64),
+
+    /// The document contains fewer polling stations than required
+    #[error("Found {0} polling stations, at least {1} are required")]
+    TooFewPollingStations(usize, usize),
+
+    /// The reporting unit does not belong to the managing authority
+    #[error("Reporting unit {0} does not match managing authority {1}")]
+    ReportingUnitAuthorityMismatch(String, String),
+
+    /// The candidate lists do not contain any affiliations
+    #[error("Candidate lists do not contain any affiliations")]
+    NoAffiliations,
+
+    /// Multiple affiliations use the same id
+    #[error("Affiliation id {0} is used more than once")]
+    DuplicateAffiliationId(String),
+
+    /// An affiliation does not contain any candidates
+    #[error("Affiliation {0} does not contain any candidates")]
+    AffiliationWithoutCandidates(String),
+
+    /// Multiple candidates in an affiliation use the same id
+    #[error("Candidate id {1} is used more than once in affiliation {0}")]
+    DuplicateCandidateId(String, String),
+
+    /// The candidates in an affiliation are not numbered sequentially
+    #[error("Candidate id {2} in affiliation {0} is out of sequence, expected {1}")]
+    CandidateIdOutOfSequence(String, u64, String),
+
+    /// An affiliation contains more candidates than allowed
+    #[error("Affiliation {0} contains {1} candidates, at most {2} are allowed")]
+    TooManyCandidates(String, usize, usize),
+
+    /// A list data attribute is used with an affiliation type that does not support it
+    #[error("Affiliation {0} uses {1} but is of type {2}")]
+    ListDataTypeMismatch(String, String, String),
+
+    /// Two documents in an election package describe a different election
+    #[error("{0} differs between documents: {2} in {1}, {4} in {3}")]
+    ElectionMismatch(String, String, String, String, String),
+
+    /// A value describing the election is present in one document of an election package, but missing in another
+    #[error("{0} is missing in {3}, but is {2} in {1}")]
+    ElectionValueMissing(String, String, String, String),
+
+    /// An affiliation in the candidate lists is not a registered party in the election definition
+    #[error("Affiliation {0} is not a registered party in the election definition")]
+    UnregisteredAffiliation(String),
+
+    /// An election package contains a document that does not belong in it
+    #[error("Document of type {0} does not belong in this election package")]
+    UnexpectedPackageDocument(String),
+}
+
+/// Get the parsed value of a [`StringValue`], adding a finding if the value
+/// cannot be parsed.
+pub(crate) fn parsed_value<T: StringValueData>(
+    value: &StringValue<T>,
+    field: &'static str,
+    findings: &mut Vec<ValidationFinding>,
+) -> Option<T> {
+    match value.value() {
+        Ok(v) => Some(v.into_owned()),
+        Err(_) => {
+            findings.push(ValidationFinding::InvalidValue(
+                field.to_string(),
+                value.raw().into_owned(),
+            ));
+            None
+        }
+    }
+}
@@ -0,0 +1,892 @@
+//! Enveloped XML signatures on EML_NL documents, as defined by
+//! [XML Signature Syntax and Processing](https://www.w3.org/TR/xmldsig-core1/).
+//!
+//! This module is only available with the `xmldsig` feature enabled.
+//!
+//! An enveloped signature is a [`Signature`] element as the last child of the
+//! root element, which signs the document it is contained in. Use
+//! [`verify_signature`] to check the signature of a document, and
+//! [`sign_document`] to write a signed document.
+//!
+//! The RSA-SHA256 algorithm is included as [`RsaSha256PublicKey`] for
+//! verifying and [`RsaSha256PrivateKey`] for signing. The public key can be
+//! taken from an X.509 certificate, for example the certificate contained in
+//! the [`KeyInfo`] of a signature. The HMAC-SHA256 algorithm is included as
+//! [`HmacSha256Key`]. Other algorithms can be used by implementing
+//! [`VerificationKey`] and [`SigningKey`].
+//!
+//! Only signatures with a single reference to the entire document (`URI=""`)
+//! are supported, using SHA-256 digests, the enveloped signature transform
+//! and the algorithms of [`CanonicalizationAlgorithm`].
+//!
+//! ```
+//! use eml_nl::{
+//!     documents::EML,
+//!     io::{CanonicalizationAlgorithm, EMLParsingMode, EMLRead as _},
+//!     xmldsig::{HmacSha256Key, sign_document, verify_signature},
+//! };
+//!
+//! let doc = include_str!("../test-emls/polling_stations/eml110b_test.eml.xml");
+//! let eml = EML::parse_eml(doc, EMLParsingMode::Strict).ok().unwrap();
+//!
+//! let key = HmacSha256Key::new(b"secret key");
+//! let signed = sign_document(&eml, &key, CanonicalizationAlgorithm::ExclusiveC14N).unwrap();
+//! assert!(verify_signature(&signed, &key).is_ok());
+//! assert!(verify_signature(&signed, &HmacSha256Key::new(b"other key")).is_err());
+//! ```
+
+use std::{fmt, ops::Range};
+
+use hmac::{Hmac, Mac as _};
+use rsa::{
+    RsaPrivateKey, RsaPublicKey, pkcs1v15,
+    pkcs8::{DecodePrivateKey as _, DecodePublicKey as _},
+    signature::{Keypair as _, SignatureEncoding as _, Signer as _, Verifier as _},
+};
+use sha2::{Digest as _, Sha256};
+use x509_cert::{
+    Certificate,
+    der::{Decode as _, Encode as _},
+};
+
+use crate::{
+    EMLError, EMLErrorKind, EMLResultExt, NS_DS,
+    common::{
+        CanonicalizationMethod, DigestMethod, KeyInfo, Reference, Signature, SignatureMethod,
+        SignedInfo, Transform, Transforms, X509Data,
+    },
+    io::{
+        CanonicalizationAlgorithm, EMLElement, EMLParsingMode, EMLReader, EMLWrite,
+        EMLWriteInternal, Extensions, canonicalize, canonicalize_element,
+    },
+    utils::{Base64Binary, StringValue},
+};
+
+/// URI of the SHA-256 digest algorithm.
+pub const DIGEST_SHA256: &str = "http://www.w3.org/2001/04/xmlenc#sha256";
+
+/// URI of the HMAC-SHA256 signature algorithm.
+pub const SIGNATURE_HMAC_SHA256: &str = "http://www.w3.org/2001/04/xmldsig-more#hmac-sha256";
+
+/// URI of the RSA PKCS#1 v1.5 signature algorithm with SHA-256.
+pub const SIGNATURE_RSA_SHA256: &str = "http://www.w3.org/2001/04/xmldsig-more#rsa-sha256";
+
+/// URI of the enveloped signature transform, which removes the signature
+/// itself from the signed document.
+pub const TRANSFORM_ENVELOPED_SIGNATURE: &str =
+    "http://www.w3.org/2000/09/xmldsig#enveloped-signature";
+
+/// A key that can verify signature values.
+pub trait VerificationKey {
+    /// Returns whether this key can verify signatures created with the
+    /// signature algorithm identified by the given URI.
+    fn supports(&self, algorithm: &str) -> bool;
+
+    /// Verifies the signature value over the data, which is the canonical form
+    /// of the `SignedInfo` element of the signature.
+    fn verify(&self, algorithm: &str, data: &[u8], signature: &[u8]) -> bool;
+}
+
+/// A key that can create signature values.
+pub trait SigningKey {
+    /// Returns the URI of the signature algorithm used by this key.
+    fn algorithm(&self) -> &str;
+
+    /// Creates the signature value over the data, which is the canonical form
+    /// of the `SignedInfo` element of the signature.
+    fn sign(&self, data: &[u8]) -> Result<Vec<u8>, Box<dyn std::error::Error + Send + Sync>>;
+
+    /// Returns the key information to include in the signature, such as the
+    /// certificate of the signer.
+    fn key_info(&self) -> Option<KeyInfo> {
+        None
+    }
+}
+
+/// A secret key for the HMAC-SHA256 signature algorithm, used both for
+/// signing and verifying.
+#[derive(Clone)]
+pub struct HmacSha256Key(Vec<u8>);
+
+impl HmacSha256Key {
+    /// Create a key from its secret bytes.
+    pub fn new(key: impl Into<Vec<u8>>) -> Self {
+        HmacSha256Key(key.into())
+    }
+
+    /// Returns a MAC instance keyed with this key, which accepts keys of any
+    /// length.
+    fn hmac(&self) -> Hmac<Sha256> {
+        Hmac::new_from_slice(&self.0).expect("HMAC accepts keys of any length")
+    }
+
+    /// Computes the message authentication code of the data.
+    pub fn mac(&self, data: &[u8]) -> [u8; 32] {
+        self.hmac()
+            .chain_update(data)
+            .finalize()
+            .into_bytes()
+            .into()
+    }
+}
+
+/// The secret key is not shown.
+impl fmt::Debug for HmacSha256Key {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("HmacSha256Key").finish_non_exhaustive()
+    }
+}
+
+impl VerificationKey for HmacSha256Key {
+    fn supports(&self, algorithm: &str) -> bool {
+        algorithm == SIGNATURE_HMAC_SHA256
+    }
+
+    fn verify(&self, _algorithm: &str, data: &[u8], signature: &[u8]) -> bool {
+        // Compared in constant time, so the time taken does not reveal the
+        // mismatch
+        self.hmac()
+            .chain_update(data)
+            .verify_slice(signature)
+            .is_ok()
+    }
+}
+
+impl SigningKey for HmacSha256Key {
+    fn algorithm(&self) -> &str {
+        SIGNATURE_HMAC_SHA256
+    }
+
+    fn sign(&self, data: &[u8]) -> Result<Vec<u8>, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(self.mac(data).to_vec())
+    }
+}
+
+/// A public key for the RSA-SHA256 signature algorithm, used for verifying.
+#[derive(Debug, Clone)]
+pub struct RsaSha256PublicKey(pkcs1v15::VerifyingKey<Sha256>);
+
+impl RsaSha256PublicKey {
+    /// Create a key from an RSA public key.
+    fn new(key: RsaPublicKey) -> Self {
+        RsaSha256PublicKey(pkcs1v15::VerifyingKey::new(key))
+    }
+
+    /// Create a key from a DER encoded `SubjectPublicKeyInfo`.
+    pub fn from_public_key_der(der: &[u8]) -> Result<Self, EMLError> {
+        let key = RsaPublicKey::from_public_key_der(der)
+            .map_err(|e| EMLErrorKind::InvalidKey(Box::new(e)))
+            .without_span()?;
+        Ok(Self::new(key))
+    }
+
+    /// Create a key from the public key of a DER encoded X.509 certificate.
+    ///
+    /// Note that the certificate itself is not validated.
+    pub fn from_certificate_der(der: &[u8]) -> Result<Self, EMLError> {
+        let invalid = |e: x509_cert::der::Error| EMLErrorKind::InvalidKey(Box::new(e));
+        let certificate = Certificate::from_der(der).map_err(invalid).without_span()?;
+        let public_key = certificate
+            .tbs_certificate
+            .subject_public_key_info
+            .to_der()
+            .map_err(invalid)
+            .without_span()?;
+        Self::from_public_key_der(&public_key)
+    }
+
+    /// Create a key from the first certificate in the key info of a signature.
+    ///
+    /// Note that this key is provided by the signer of the document, the
+    /// caller must check whether the certificate can be trusted.
+    pub fn from_key_info(key_info: &KeyInfo) -> Result<Self, EMLError> {
+        let certificate = key_info
+            .certificates()
+            .next()
+            .ok_or(EMLErrorKind::MissingCertificate)
+            .without_span()?
+            .value_err(("X509Certificate", NS_DS), None)?;
+        Self::from_certificate_der(certificate.bytes())
+    }
+}
+
+impl VerificationKey for RsaSha256PublicKey {
+    fn supports(&self, algorithm: &str) -> bool {
+        algorithm == SIGNATURE_RSA_SHA256
+    }
+
+    fn verify(&self, _algorithm: &str, data: &[u8], signature: &[u8]) -> bool {
+        pkcs1v15::Signature::try_from(signature)
+            .is_ok_and(|signature| self.0.verify(data, &signature).is_ok())
+    }
+}
+
+/// A private key for the RSA-SHA256 signature algorithm, used for signing.
+///
+/// When a certificate is added with
+/// [`with_certificate`](RsaSha256PrivateKey::with_certificate), it is included
+/// in the key info of the signature.
+#[derive(Clone)]
+pub struct RsaSha256PrivateKey {
+    key: pkcs1v15::SigningKey<Sha256>,
+    certificate: Option<Vec<u8>>,
+}
+
+impl RsaSha256PrivateKey {
+    /// Create a key from an RSA private key.
+    fn new(key: RsaPrivateKey) -> Self {
+        RsaSha256PrivateKey {
+            key: pkcs1v15::SigningKey::new(key),
+            certificate: None,
+        }
+    }
+
+    /// Create a key from a DER encoded PKCS#8 private key.
+    pub fn from_pkcs8_der(der: &[u8]) -> Result<Self, EMLError> {
+        let key = RsaPrivateKey::from_pkcs8_der(der)
+            .map_err(|e| EMLErrorKind::InvalidKey(Box::new(e)))
+            .without_span()?;
+        Ok(Self::new(key))
+    }
+
+    /// Include the given DER encoded X.509 certificate in signatures.
+    pub fn with_certificate(mut self, der: impl Into<Vec<u8>>) -> Self {
+        self.certificate = Some(der.into());
+        self
+    }
+
+    /// Returns the public key belonging to this private key.
+    pub fn public_key(&self) -> RsaSha256PublicKey {
+        RsaSha256PublicKey(self.key.verifying_key())
+    }
+}
+
+/// The private key is not shown.
+impl fmt::Debug for RsaSha256PrivateKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RsaSha256PrivateKey")
+            .field("certificate", &self.certificate.is_some())
+            .finish_non_exhaustive()
+    }
+}
+
+impl SigningKey for RsaSha256PrivateKey {
+    fn algorithm(&self) -> &str {
+        SIGNATURE_RSA_SHA256
+    }
+
+    fn sign(&self, data: &[u8]) -> Result<Vec<u8>, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(self.key.try_sign(data)?.to_vec())
+    }
+
+    fn key_info(&self) -> Option<KeyInfo> {
+        let certificate = self.certificate.as_ref()?;
+        Some(KeyInfo {
+            id: None,
+            key_names: Vec::new(),
+            x509_data: vec![X509Data {
+                certificates: vec![StringValue::from_value(Base64Binary::new(
+                    certificate.clone(),
+                ))],
+                extensions: Extensions::default(),
+            }],
+            extensions: Extensions::default(),
+        })
+    }
+}
+
+/// Verifies the enveloped signature of a document with the given key,
+/// returning the signature if it is valid.
+///
+/// Both the digest of the document and the signature value are checked. Note
+/// that this does not check whether the key can be trusted, that is up to the
+/// caller, e.g. by checking the certificate in the [`KeyInfo`] of the
+/// signature before using it as the key with
+/// [`RsaSha256PublicKey::from_key_info`].
+pub fn verify_signature(xml: &str, key: &impl VerificationKey) -> Result<Signature, EMLError> {
+    // Offsets are used on the normalized document, as canonicalization
+    // normalizes line endings
+    let xml = xml.replace("\r\n", "\n").replace('\r', "\n");
+    let EnvelopedSignature {
+        signature,
+        span,
+        signed_info_span,
+    } = read_enveloped_signature(&xml)?
+        .ok_or(EMLErrorKind::MissingSignature)
+        .without_span()?;
+    let signed_info = &signature.signed_info;
+
+    // Validate the reference to the document
+    let [reference] = signed_info.references.as_slice() else {
+        return Err(EMLErrorKind::UnsupportedReference).without_span();
+    };
+    if reference.uri.as_deref() != Some("") {
+        return Err(EMLErrorKind::UnsupportedReference).without_span();
+    }
+    if reference.digest_method.algorithm != DIGEST_SHA256 {
+        return Err(EMLErrorKind::UnsupportedAlgorithm(
+            reference.digest_method.algorithm.clone(),
+        ))
+        .without_span();
+    }
+    let mut content = xml.clone();
+    let mut enveloped = false;
+    let mut algorithm = None;
+    let transforms = reference.transforms.iter().flat_map(|t| &t.transforms);
+    for transform in transforms {
+        if transform.algorithm == TRANSFORM_ENVELOPED_SIGNATURE {
+            // The signature can only be removed once
+            if enveloped {
+                return Err(EMLErrorKind::UnsupportedReference).without_span();
+            }
+            enveloped = true;
+            content.replace_range(span.clone(), "");
+        } else if algorithm.is_some() {
+            return Err(EMLErrorKind::UnsupportedReference).without_span();
+        } else {
+            algorithm = Some(
+                CanonicalizationAlgorithm::from_uri(&transform.algorithm)
+                    .ok_or_else(|| EMLErrorKind::UnsupportedAlgorithm(transform.algorithm.clone()))
+                    .without_span()?,
+            );
+        }
+    }
+    // Inclusive canonicalization is used when no algorithm is given
+    let algorithm = algorithm.unwrap_or(CanonicalizationAlgorithm::C14N);
+    let digest = Sha256::digest(canonicalize(&content, without_comments(algorithm))?);
+    let expected_digest = reference
+        .digest_value
+        .value_err(("DigestValue", NS_DS), None)?;
+    if digest.as_slice() != expected_digest.bytes() {
+        return Err(EMLErrorKind::DigestMismatch).without_span();
+    }
+
+    // Validate the signature value over the signed info
+    let method = &signed_info.signature_method.algorithm;
+    if !key.supports(method) {
+        return Err(EMLErrorKind::UnsupportedAlgorithm(method.clone())).without_span();
+    }
+    let canonical_signed_info =
+        canonicalize_signed_info(&xml, signed_info_span.start, signed_info)?;
+    let signature_value = signature
+        .signature_value
+        .value_err(("SignatureValue", NS_DS), None)?;
+    if !key.verify(
+        method,
+        canonical_signed_info.as_bytes(),
+        signature_value.bytes(),
+    ) {
+        return Err(EMLErrorKind::InvalidSignature).without_span();
+    }
+
+    Ok(signature)
+}
+
+/// Writes a document in canonical form with an enveloped signature created
+/// with the given key.
+///
+/// Any existing signature of the document is replaced. The signature
+/// contains a single reference to the entire document, using the given
+/// algorithm for canonicalizing both the document and the signed info.
+pub fn sign_document(
+    document: &impl EMLWrite,
+    key: &impl SigningKey,
+    algorithm: CanonicalizationAlgorithm,
+) -> Result<String, EMLError> {
+    let mut xml = document.write_eml_root_canonical_str(algorithm)?;
+    if let Some(existing) = read_enveloped_signature(&xml)? {
+        xml.replace_range(existing.span, "");
+    }
+
+    let digest = Sha256::digest(canonicalize(&xml, without_comments(algorithm))?);
+    let reference = Reference {
+        id: None,
+        uri: Some(String::new()),
+        transforms: Some(Transforms {
+            transforms: vec![
+                Transform::new(TRANSFORM_ENVELOPED_SIGNATURE),
+                Transform::new(algorithm.uri()),
+            ],
+            extensions: Extensions::default(),
+        }),
+        digest_method: DigestMethod::new(DIGEST_SHA256),
+        digest_value: StringValue::from_value(Base64Binary::new(digest.to_vec())),
+        extensions: Extensions::default(),
+    };
+    let mut signature = Signature {
+        id: None,
+        signed_info: SignedInfo {
+            canonicalization_method: CanonicalizationMethod::new(algorithm),
+            signature_method: SignatureMethod::new(key.algorithm()),
+            references: vec![reference],
+            extensions: Extensions::default(),
+        },
+        signature_value: StringValue::from_value(Base64Binary::new(Vec::new())),
+        key_info: key.key_info(),
+        extensions: Extensions::default(),
+    };
+
+    // The signed info is canonicalized in the context of the document, so
+    // the signature is added without a value first
+    let signature_start = xml
+        .rfind("</")
+        .ok_or(EMLErrorKind::UnexpectedEof)
+        .without_span()?;
+    let unsigned = write_signature(&signature)?;
+    xml.insert_str(signature_start, &unsigned);
+    let signed_info_span = read_enveloped_signature(&xml)?
+        .ok_or(EMLErrorKind::MissingSignature)
+        .without_span()?
+        .signed_info_span;
+    let canonical_signed_info =
+        canonicalize_signed_info(&xml, signed_info_span.start, &signature.signed_info)?;
+    let signature_value = key
+        .sign(canonical_signed_info.as_bytes())
+        .map_err(EMLErrorKind::SigningError)
+        .without_span()?;
+    signature.signature_value = StringValue::from_value(Base64Binary::new(signature_value));
+    xml.replace_range(
+        signature_start..signature_start + unsigned.len(),
+        &write_signature(&signature)?,
+    );
+
+    // Removes the namespace declarations of the signature that are already
+    // declared on the root element
+    canonicalize(&xml, algorithm)
+}
+
+/// An enveloped signature read from a document.
+struct EnvelopedSignature {
+    signature: Signature,
+    /// Byte range of the signature element in the document.
+    span: Range<usize>,
+    /// Byte range of the signed info element in the document.
+    signed_info_span: Range<usize>,
+}
+
+/// Reads the signature that is a direct child of the root element of the
+/// document.
+///
+/// Any error while reading fails, including errors that are not fatal when
+/// parsing a document. The signed info must be the first child element of the
+/// signature and may occur only once, so that the signed info that is checked
+/// is the one the signature value is computed over.
+fn read_enveloped_signature(xml: &str) -> Result<Option<EnvelopedSignature>, EMLError> {
+    let mut reader = EMLReader::init_from_str(xml, EMLParsingMode::Strict);
+    let result = reader.with_next_element(|root| {
+        let root_name = root.name()?.as_owned();
+        let mut signature = None;
+        while let Some(mut child) = root.next_child()? {
+            if !child.has_name(Signature::EML_NAME)? {
+                child.skip()?;
+            } else if signature.is_some() {
+                return Err(EMLErrorKind::UnexpectedElement(
+                    Signature::EML_NAME.as_owned(),
+                    root_name,
+                ))
+                .with_span(child.span());
+            } else {
+                let value = Signature::read_eml(&mut child)?;
+                let span = child.full_span();
+                signature = Some((value, span.start as usize..span.end as usize));
+            }
+        }
+        Ok(signature)
+    });
+    let signature = with_reader_errors(reader, result)?;
+
+    let Some((signature, span)) = signature else {
+        return Ok(None);
+    };
+    let signed_info_span = read_signed_info_span(xml, span.start)?;
+    Ok(Some(EnvelopedSignature {
+        signature,
+        span,
+        signed_info_span,
+    }))
+}
+
+/// Reads the byte range of the signed info of the signature element starting
+/// at the given offset, checking that it is the only signed info and that no
+/// other element comes before it.
+fn read_signed_info_span(xml: &str, signature_start: usize) -> Result<Range<usize>, EMLError> {
+    let mut reader = EMLReader::init_from_str(xml, EMLParsingMode::Strict);
+    let result = reader.with_next_element(|root| {
+        while let Some(mut child) = root.next_child()? {
+            if child.span().start as usize != signature_start {
+                child.skip()?;
+                continue;
+            }
+
+            let signature_name = child.name()?.as_owned();
+            let mut signed_info_span = None;
+            let mut is_first = true;
+            while let Some(mut signature_child) = child.next_child()? {
+                let is_signed_info = signature_child.has_name(SignedInfo::EML_NAME)?;
+                if is_signed_info && is_first {
+                    signature_child.skip()?;
+                    let span = signature_child.full_span();
+                    signed_info_span = Some(span.start as usize..span.end as usize);
+                } else if is_signed_info || is_first {
+                    return Err(EMLErrorKind::UnexpectedElement(
+                        signature_child.name()?.as_owned(),
+                        signature_name,
+                    ))
+                    .with_span(signature_child.span());
+                } else {
+                    signature_child.skip()?;
+                }
+                is_first = false;
+            }
+            return signed_info_span
+                .ok_or_else(|| EMLErrorKind::MissingElement(SignedInfo::EML_NAME.as_owned()))
+                .with_span(child.span());
+        }
+        Err(EMLErrorKind::MissingSignature).without_span()
+    });
+    with_reader_errors(reader, result)
+}
+
+/// Fails with the errors collected by the reader, if there are any.
+fn with_reader_errors<T>(
+    reader: EMLReader<'_>,
+    result: Result<T, EMLError>,
+) -> Result<T, EMLError> {
+    let mut errors = reader.into_errors();
+    match (result, errors.pop()) {
+        (Ok(value), None) => Ok(value),
+        (Ok(_), Some(last)) => Err(EMLError::from_vec_with_additional(errors, last)),
+        (Err(e), Some(last)) => {
+            errors.push(last);
+            Err(EMLError::from_vec_with_additional(errors, e))
+        }
+        (Err(e), None) => Err(e),
+    }
+}
+
+/// Canonicalizes the signed info element starting at the given offset, with
+/// the canonicalization method of the signed info.
+fn canonicalize_signed_info(
+    xml: &str,
+    signed_info_start: usize,
+    signed_info: &SignedInfo,
+) -> Result<String, EMLError> {
+    let method = &signed_info.canonicalization_method;
+    let algorithm = method
+        .algorithm()
+        .ok_or_else(|| EMLErrorKind::UnsupportedAlgorithm(method.algorithm_uri().to_string()))
+        .without_span()?;
+    canonicalize_element(xml, signed_info_start, algorithm)
+}
+
+/// Writes a signature as a separate XML fragment declaring the `ds` prefix.
+fn write_signature(signature: &Signature) -> Result<String, EMLError> {
+    signature.write_root_str(
+        Some(Signature::EML_NAME),
+        Some(None),
+        Some([("ds", NS_DS)].into()),
+        false,
+        false,
+        None,
+    )
+}
+
+/// Same document references without a fragment remove comments from the
+/// document before it is canonicalized.
+fn without_comments(algorithm: CanonicalizationAlgorithm) -> CanonicalizationAlgorithm {
+    match algorithm {
+        CanonicalizationAlgorithm::C14NWithComments => CanonicalizationAlgorithm::C14N,
+        CanonicalizationAlgorithm::ExclusiveC14NWithComments => {
+            CanonicalizationAlgorithm::ExclusiveC14N
+        }
+        algorithm => algorithm,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand_chacha::{ChaCha8Rng, rand_core::SeedableRng as _};
+    use rsa::pkcs8::EncodePublicKey as _;
+
+    use crate::{
+        documents::EML,
+        io::{EMLRead as _, EMLWrite as _},
+    };
+
+    use super::*;
+
+    fn test_document() -> EML {
+        let doc = include_str!("../test-emls/polling_stations/eml110b_test.eml.xml");
+        EML::parse_eml(doc, EMLParsingMode::Strict)
+            .ok()
+            .expect("Failed to parse EML document")
+    }
+
+    #[test]
+    fn test_hmac_sha256() {
+        // Test case 2 from RFC 4231
+        let key = HmacSha256Key::new(b"Jefe");
+        let mac = key.mac(b"what do ya want for nothing?");
+        assert_eq!(
+            Base64Binary::new(mac.to_vec()),
+            Base64Binary::new(vec![
+                0x5b, 0xdc, 0xc1, 0x46, 0xbf, 0x60, 0x75, 0x4e, 0x6a, 0x04, 0x24, 0x26, 0x08, 0x95,
+                0x75, 0xc7, 0x5a, 0x00, 0x3f, 0x08, 0x9d, 0x27, 0x39, 0x83, 0x9d, 0xec, 0x58, 0xb9,
+                0x64, 0xec, 0x38, 0x43,
+            ])
+        );
+        assert!(key.verify(SIGNATURE_HMAC_SHA256, b"what do ya want for nothing?", &mac));
+        assert!(!key.verify(
+            SIGNATURE_HMAC_SHA256,
+            b"what do ya want for something?",
+            &mac
+        ));
+        assert!(!key.verify(
+            SIGNATURE_HMAC_SHA256,
+            b"what do ya want for nothing?",
+            &mac[1..]
+        ));
+    }
+
+    #[test]
+    fn test_sign_and_verify() {
+        let eml = test_document();
+        let key = HmacSha256Key::new(*b"0123456789abcdef0123456789abcdef");
+        for algorithm in [
+            CanonicalizationAlgorithm::C14N,
+            CanonicalizationAlgorithm::ExclusiveC14NWithComments,
+        ] {
+            let signed = sign_document(&eml, &key, algorithm).unwrap();
+            let signature = verify_signature(&signed, &key).unwrap();
+            assert_eq!(
+                signature.signed_info.canonicalization_method.algorithm(),
+                Some(algorithm)
+            );
+
+            // Formatting changes that do not change the canonical form
+            let reformatted = signed
+                .replace("\n", "\r\n")
+                .replace(r#"Id="110b""#, "Id='110b'");
+            verify_signature(&reformatted, &key).unwrap();
+
+            // Signed documents can be read, and signing again replaces the signature
+            let EML::PollingStations(parsed) = EML::parse_eml(&signed, EMLParsingMode::Strict)
+                .ok()
+                .expect("Failed to parse signed EML document")
+            else {
+                panic!("Expected a polling stations document");
+            };
+            assert_eq!(parsed.signature, Some(signature));
+            assert!(parsed.write_eml_root_str(true, true).is_ok());
+            assert_eq!(
+                sign_document(parsed.as_ref(), &key, algorithm).unwrap(),
+                signed
+            );
+        }
+    }
+
+    #[test]
+    fn test_verify_errors() {
+        let key = HmacSha256Key::new(b"key");
+        let signed =
+            sign_document(&test_document(), &key, CanonicalizationAlgorithm::C14N).unwrap();
+        assert!(signed.ends_with("</ds:Signature></EML>"));
+
+        let error = verify_signature(&signed, &HmacSha256Key::new(b"other")).unwrap_err();
+        assert!(matches!(error.kind(), EMLErrorKind::InvalidSignature));
+
+        let tampered = signed.replacen("<ElectionName>", "<ElectionName>Not ", 1);
+        assert_ne!(tampered, signed);
+        let error = verify_signature(&tampered, &key).unwrap_err();
+        assert!(matches!(error.kind(), EMLErrorKind::DigestMismatch));
+
+        let unsigned = test_document().write_eml_root_str(false, false).unwrap();
+        let error = verify_signature(&unsigned, &key).unwrap_err();
+        assert!(matches!(error.kind(), EMLErrorKind::MissingSignature));
+
+        let other_method = signed.replace(SIGNATURE_HMAC_SHA256, SIGNATURE_RSA_SHA256);
+        let error = verify_signature(&other_method, &key).unwrap_err();
+        assert!(matches!(
+            error.kind(),
+            EMLErrorKind::UnsupportedAlgorithm(algorithm) if algorithm == SIGNATURE_RSA_SHA256
+        ));
+    }
+
+    #[test]
+    fn test_verify_rejects_second_signed_info() {
+        let key = HmacSha256Key::new(b"key");
+        let signed =
+            sign_document(&test_document(), &key, CanonicalizationAlgorithm::C14N).unwrap();
+        let forged = signed.replace("Stadhuis", "FORGED");
+        assert_ne!(forged, signed);
+
+        // Add a signed info with the digest of the forged document after the
+        // one the signature value was computed over
+        let start = forged.find("<ds:SignedInfo>").unwrap();
+        let end = forged.find("</ds:SignedInfo>").unwrap() + "</ds:SignedInfo>".len();
+        let signed_info = &forged[start..end];
+        let signature_start = forged.find("<ds:Signature").unwrap();
+        let mut unsigned = forged.clone();
+        unsigned.replace_range(signature_start..forged.rfind("</EML>").unwrap(), "");
+        let digest =
+            Sha256::digest(canonicalize(&unsigned, CanonicalizationAlgorithm::C14N).unwrap());
+        let digest = StringValue::from_value(Base64Binary::new(digest.to_vec()));
+        let digest_start = signed_info.find("<ds:DigestValue>").unwrap() + "<ds:DigestValue>".len();
+        let digest_end = signed_info.find("</ds:DigestValue>").unwrap();
+        let mut forged_signed_info = signed_info.to_string();
+        forged_signed_info.replace_range(digest_start..digest_end, &digest.raw());
+        let mut attack = forged.clone();
+        attack.insert_str(end, &forged_signed_info);
+
+        let error = verify_signature(&attack, &key).unwrap_err();
+        assert!(matches!(
+            error.kind(),
+            EMLErrorKind::UnexpectedElement(name, _) if name.local_name == "SignedInfo"
+        ));
+
+        // Only the signed info may come before the signed info
+        let mut reordered = signed.clone();
+        let signature_value = "<ds:SignatureValue>";
+        let value_start = reordered.find(signature_value).unwrap();
+        let value_end =
+            reordered.find("</ds:SignatureValue>").unwrap() + "</ds:SignatureValue>".len();
+        let value = reordered[value_start..value_end].to_string();
+        reordered.replace_range(value_start..value_end, "");
+        reordered.insert_str(reordered.find("<ds:SignedInfo>").unwrap(), &value);
+        let error = verify_signature(&reordered, &key).unwrap_err();
+        assert!(matches!(error.kind(), EMLErrorKind::UnexpectedElement(..)));
+
+        // Errors that are not fatal when parsing also fail verification
+        let unknown = signed.replacen("</ds:Signature>", "<ds:Object/></ds:Signature>", 1);
+        let error = verify_signature(&unknown, &key).unwrap_err();
+        assert!(matches!(error.kind(), EMLErrorKind::UnexpectedElement(..)));
+    }
+
+    #[test]
+    fn test_verify_rejects_repeated_transforms() {
+        let key = HmacSha256Key::new(b"key");
+        let signed =
+            sign_document(&test_document(), &key, CanonicalizationAlgorithm::C14N).unwrap();
+        let enveloped =
+            format!(r#"<ds:Transform Algorithm="{TRANSFORM_ENVELOPED_SIGNATURE}"></ds:Transform>"#);
+        assert!(signed.contains(&enveloped));
+
+        let twice = signed.replacen(&enveloped, &enveloped.repeat(2), 1);
+        let error = verify_signature(&twice, &key).unwrap_err();
+        assert!(matches!(error.kind(), EMLErrorKind::UnsupportedReference));
+
+        let canonicalization = format!(
+            r#"<ds:Transform Algorithm="{}"></ds:Transform>"#,
+            CanonicalizationAlgorithm::C14N.uri()
+        );
+        assert!(signed.contains(&canonicalization));
+        let twice = signed.replacen(
+            &canonicalization,
+            &format!(
+                r#"{canonicalization}<ds:Transform Algorithm="{}"></ds:Transform>"#,
+                CanonicalizationAlgorithm::ExclusiveC14N.uri()
+            ),
+            1,
+        );
+        let error = verify_signature(&twice, &key).unwrap_err();
+        assert!(matches!(error.kind(), EMLErrorKind::UnsupportedReference));
+    }
+
+    /// Generates an RSA key with a self-signed certificate.
+    fn generate_rsa_key() -> (RsaPrivateKey, Vec<u8>) {
+        use std::{str::FromStr as _, time::Duration};
+
+        use x509_cert::{
+            builder::{Builder as _, CertificateBuilder, Profile},
+            name::Name,
+            serial_number::SerialNumber,
+            spki::SubjectPublicKeyInfoOwned,
+            time::Validity,
+        };
+
+        let mut rng = ChaCha8Rng::seed_from_u64(510);
+        let key = RsaPrivateKey::new(&mut rng, 1024).unwrap();
+        let signer = pkcs1v15::SigningKey::<Sha256>::new(key.clone());
+        let public_key = SubjectPublicKeyInfoOwned::from_key(key.to_public_key()).unwrap();
+        let certificate = CertificateBuilder::new(
+            Profile::Root,
+            SerialNumber::from(1u32),
+            Validity::from_now(Duration::from_secs(3600)).unwrap(),
+            Name::from_str("CN=Gemeente Test").unwrap(),
+            public_key,
+            &signer,
+        )
+        .unwrap()
+        .build::<pkcs1v15::Signature>()
+        .unwrap();
+        (key, certificate.to_der().unwrap())
+    }
+
+    #[test]
+    fn test_sign_and_verify_rsa() {
+        let (key, certificate) = generate_rsa_key();
+        let public_key_der = key.to_public_key().to_public_key_der().unwrap();
+        let signing_key = RsaSha256PrivateKey::new(key).with_certificate(certificate.clone());
+        let signed = sign_document(
+            &test_document(),
+            &signing_key,
+            CanonicalizationAlgorithm::ExclusiveC14N,
+        )
+        .unwrap();
+        assert!(signed.contains(SIGNATURE_RSA_SHA256));
+
+        // The key can be given directly, or taken from a certificate
+        let signature = verify_signature(&signed, &signing_key.public_key()).unwrap();
+        let from_key_info =
+            RsaSha256PublicKey::from_key_info(signature.key_info.as_ref().unwrap()).unwrap();
+        verify_signature(&signed, &from_key_info).unwrap();
+        let from_certificate = RsaSha256PublicKey::from_certificate_der(&certificate).unwrap();
+        verify_signature(&signed, &from_certificate).unwrap();
+        let from_der = RsaSha256PublicKey::from_public_key_der(public_key_der.as_bytes()).unwrap();
+        verify_signature(&signed, &from_der).unwrap();
+
+        // A signature value created with another key is rejected
+        let mut rng = ChaCha8Rng::seed_from_u64(520);
+        let other_key = RsaSha256PrivateKey::new(RsaPrivateKey::new(&mut rng, 1024).unwrap());
+        let error = verify_signature(&signed, &other_key.public_key()).unwrap_err();
+        assert!(matches!(error.kind(), EMLErrorKind::InvalidSignature));
+
+        let tampered = signed.replacen("<ElectionName>", "<ElectionName>Not ", 1);
+        let error = verify_signature(&tampered, &from_certificate).unwrap_err();
+        assert!(matches!(error.kind(), EMLErrorKind::DigestMismatch));
+
+        // An RSA key cannot verify HMAC signatures and the other way around
+        let hmac_key = HmacSha256Key::new(b"key");
+        let error = verify_signature(&signed, &hmac_key).unwrap_err();
+        assert!(matches!(
+            error.kind(),
+            EMLErrorKind::UnsupportedAlgorithm(_)
+        ));
+        let hmac_signed =
+            sign_document(&test_document(), &hmac_key, CanonicalizationAlgorithm::C14N).unwrap();
+        let error = verify_signature(&hmac_signed, &from_certificate).unwrap_err();
+        assert!(matches!(
+            error.kind(),
+            EMLErrorKind::UnsupportedAlgorithm(_)
+        ));
+    }
+
+    #[test]
+    fn test_rsa_key_errors() {
+        let error = RsaSha256PublicKey::from_certificate_der(b"not a certificate").unwrap_err();
+        assert!(matches!(error.kind(), EMLErrorKind::InvalidKey(_)));
+        let error = RsaSha256PublicKey::from_public_key_der(b"not a key").unwrap_err();
+        assert!(matches!(error.kind(), EMLErrorKind::InvalidKey(_)));
+        let error = RsaSha256PrivateKey::from_pkcs8_der(b"not a key").unwrap_err();
+        assert!(matches!(error.kind(), EMLErrorKind::InvalidKey(_)));
+
+        let key_info = KeyInfo {
+            id: None,
+            key_names: vec!["Gemeente Test".to_string()],
+            x509_data: Vec::new(),
+            extensions: Extensions::default(),
+        };
+        let error = RsaSha256PublicKey::from_key_info(&key_info).unwrap_err();
+        assert!(matches!(error.kind(), EMLErrorKind::MissingCertificate));
+    }
+}
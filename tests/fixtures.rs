@@ -0,0 +1,228 @@
+//! Data driven tests for the EML documents in `test-emls`.
+//!
+//! Every directory in `test-emls` contains a `manifest.txt` describing the
+//! expected outcome of parsing and validating each fixture in that directory,
+//! for every parsing mode. Each non-empty line that does not start with `#`
+//! has the form:
+//!
+//! ```text
+//! <file name> <strict> <strict-fallback> <loose>
+//! ```
+//!
+//! Where each outcome is one of:
+//!
+//! * `accept`: the document is parsed without any errors, and its validation
+//!   has no findings.
+//! * `warn:<kind>,...`: the document is parsed, and the kinds of the non-fatal
+//!   errors and validation findings are exactly the given kinds, in any order
+//!   and ignoring repetitions. Each kind is either an `EMLErrorKind` or a
+//!   `ValidationFinding`.
+//! * `reject:<EMLErrorKind>`: the document cannot be parsed, and the (last)
+//!   fatal error is of the given kind.
+//!
+//! Documents are validated with the `validate` method of their document type,
+//! if it has one. Every fixture must be listed in the manifest of its
+//! directory.
+
+use std::{
+    collections::BTreeMap,
+    fmt,
+    path::{Path, PathBuf},
+};
+
+use eml_nl::{
+    documents::EML,
+    io::{EMLParsingMode, EMLRead as _, EMLReadResult},
+    validation::ValidationFinding,
+};
+
+const MANIFEST_NAME: &str = "manifest.txt";
+const PARSING_MODES: [EMLParsingMode; 3] = [
+    EMLParsingMode::Strict,
+    EMLParsingMode::StrictFallback,
+    EMLParsingMode::Loose,
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Outcome {
+    Accept,
+    Warn(Vec<String>),
+    Reject(String),
+}
+
+impl Outcome {
+    fn parse(value: &str) -> Option<Outcome> {
+        match value.split_once(':') {
+            None if value == "accept" => Some(Outcome::Accept),
+            Some(("warn", kinds)) => Some(Outcome::Warn(sorted_kinds(
+                kinds.split(',').map(|kind| kind.to_string()),
+            ))),
+            Some(("reject", kind)) => Some(Outcome::Reject(kind.to_string())),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Outcome::Accept => write!(f, "accept"),
+            Outcome::Warn(kinds) => write!(f, "warn:{}", kinds.join(",")),
+            Outcome::Reject(kind) => write!(f, "reject:{kind}"),
+        }
+    }
+}
+
+/// Returns the name of the variant of the given error kind or finding.
+fn kind_name(kind: &impl fmt::Debug) -> String {
+    format!("{kind:?}")
+        .chars()
+        .take_while(|c| c.is_ascii_alphanumeric())
+        .collect()
+}
+
+/// Returns the given kinds sorted and without repetitions.
+fn sorted_kinds(kinds: impl IntoIterator<Item = String>) -> Vec<String> {
+    let mut kinds: Vec<_> = kinds.into_iter().collect();
+    kinds.sort();
+    kinds.dedup();
+    kinds
+}
+
+/// Returns the validation findings of the document, if its type supports
+/// validation.
+fn validate(eml: &EML) -> Vec<ValidationFinding> {
+    match eml {
+        EML::ElectionDefinition(ed) => ed.validate(),
+        EML::PollingStations(ps) => ps.validate(),
+        EML::CandidateLists(cl) => cl.validate(),
+        _ => Vec::new(),
+    }
+}
+
+/// Parse and validate the fixture and check it against the expected outcome,
+/// returning a description of the actual outcome if it does not match.
+fn check_fixture(input: &str, mode: EMLParsingMode, expected: &Outcome) -> Result<(), String> {
+    let warnings = match EML::parse_eml(input, mode) {
+        EMLReadResult::Ok(eml, errors) => sorted_kinds(
+            errors
+                .iter()
+                .map(|e| kind_name(e.kind()))
+                .chain(validate(&eml).iter().map(kind_name)),
+        ),
+        EMLReadResult::Err(err) => {
+            return match expected {
+                Outcome::Reject(kind) if &kind_name(err.kind()) == kind => Ok(()),
+                _ => Err(format!("reject:{} ({err})", kind_name(err.kind()))),
+            };
+        }
+    };
+    match expected {
+        Outcome::Accept if warnings.is_empty() => Ok(()),
+        Outcome::Warn(kinds) if *kinds == warnings => Ok(()),
+        _ if warnings.is_empty() => Err("accept".to_string()),
+        _ => Err(format!("warn:{}", warnings.join(","))),
+    }
+}
+
+fn read_manifest(path: &Path) -> BTreeMap<String, [Outcome; 3]> {
+    let contents = std::fs::read_to_string(path)
+        .unwrap_or_else(|e| panic!("Failed to read manifest {}: {e}", path.display()));
+    let mut entries = BTreeMap::new();
+    for (line_number, line) in contents.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let fields: Vec<_> = line.split_whitespace().collect();
+        let location = format!("{}:{}", path.display(), line_number + 1);
+        let [file, outcomes @ ..] = fields.as_slice() else {
+            unreachable!("Line cannot be empty");
+        };
+        let outcomes: [Outcome; 3] = outcomes
+            .iter()
+            .map(|o| {
+                Outcome::parse(o).unwrap_or_else(|| panic!("{location}: invalid outcome '{o}'"))
+            })
+            .collect::<Vec<_>>()
+            .try_into()
+            .unwrap_or_else(|_| panic!("{location}: expected exactly three outcomes"));
+        if entries.insert(file.to_string(), outcomes).is_some() {
+            panic!("{location}: duplicate entry for {file}");
+        }
+    }
+    entries
+}
+
+fn fixture_dirs(root: &Path) -> Vec<PathBuf> {
+    let mut dirs = vec![root.to_path_buf()];
+    let mut i = 0;
+    while i < dirs.len() {
+        let mut children: Vec<_> = std::fs::read_dir(&dirs[i])
+            .expect("Failed to read fixture directory")
+            .map(|entry| entry.expect("Failed to read directory entry").path())
+            .filter(|path| path.is_dir())
+            .collect();
+        children.sort();
+        dirs.extend(children);
+        i += 1;
+    }
+    dirs
+}
+
+#[test]
+fn test_fixtures() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("test-emls");
+    let mut failures = Vec::new();
+    let mut checked = 0;
+
+    for dir in fixture_dirs(&root) {
+        let mut manifest = read_manifest(&dir.join(MANIFEST_NAME));
+
+        let mut fixtures: Vec<_> = std::fs::read_dir(&dir)
+            .expect("Failed to read fixture directory")
+            .map(|entry| entry.expect("Failed to read directory entry").path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "xml"))
+            .collect();
+        fixtures.sort();
+
+        for fixture in fixtures {
+            let name = fixture
+                .file_name()
+                .and_then(|n| n.to_str())
+                .expect("Fixture name must be valid UTF-8")
+                .to_string();
+            let Some(outcomes) = manifest.remove(&name) else {
+                failures.push(format!("{}: missing manifest entry", fixture.display()));
+                continue;
+            };
+            let input = std::fs::read_to_string(&fixture)
+                .unwrap_or_else(|e| panic!("Failed to read {}: {e}", fixture.display()));
+
+            for (mode, expected) in PARSING_MODES.iter().zip(&outcomes) {
+                if let Err(actual) = check_fixture(&input, *mode, expected) {
+                    failures.push(format!(
+                        "{} ({mode:?}): expected {expected}, got {actual}",
+                        fixture.display()
+                    ));
+                }
+            }
+            checked += 1;
+        }
+
+        for name in manifest.keys() {
+            failures.push(format!(
+                "{}: manifest entry for missing fixture {name}",
+                dir.display()
+            ));
+        }
+    }
+
+    assert!(checked > 0, "No fixtures found in {}", root.display());
+    assert!(
+        failures.is_empty(),
+        "{} fixture check(s) failed:\n{}",
+        failures.len(),
+        failures.join("\n")
+    );
+}